@@ -0,0 +1,252 @@
+//! Procedural macros for yaiouom.
+//!
+//! Use these through feature `derive` of crate `yaiouom` rather than directly.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use]
+extern crate quote;
+#[macro_use]
+extern crate syn;
+
+mod pretty;
+mod trusted;
+mod unify;
+
+use proc_macro::TokenStream;
+use syn::{Data, DeriveInput, Field, Fields};
+
+/// Derive `HasUnit` for a struct with a single field, using the unit of that field.
+///
+/// ```ignore
+/// #[derive(HasUnit)]
+/// struct Altitude(Measure<f64, Meter>);
+/// ```
+#[proc_macro_derive(HasUnit)]
+pub fn derive_has_unit(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let field = match single_field(&input) {
+        Ok(field) => field,
+        Err(err) => return compile_error(err),
+    };
+    let name = &input.ident;
+    let ty = &field.ty;
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#ty: ::yaiouom::HasUnit));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let expanded = quote! {
+        impl #impl_generics ::yaiouom::HasUnit for #name #ty_generics #where_clause {
+            type Unit = <#ty as ::yaiouom::HasUnit>::Unit;
+        }
+    };
+    expanded.into()
+}
+
+/// Derive `Debug` for a struct of measures, displaying each field along
+/// with its unit, as well as `yaiouom::pretty::UnitFields`, which lets
+/// `yaiouom::pretty::table` display the struct as a table.
+///
+/// Each field must be a `Measure` or a struct that derives `UnitDebug`.
+///
+/// ```ignore
+/// #[derive(UnitDebug)]
+/// struct State {
+///     position: Measure<f64, Meter>,
+///     speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+/// }
+///
+/// // State { position: 1.5 m, speed: 10.0 m * s^-1 }
+/// println!("{:?}", state);
+/// ```
+#[proc_macro_derive(UnitDebug)]
+pub fn derive_unit_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match pretty::expand_debug(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Derive `serde::Serialize` for a struct of measures, serializing each
+/// measure as `{ "value": ..., "unit": "..." }`.
+///
+/// Each field must be a `Measure` or a struct that derives `UnitSerialize`.
+/// Requires feature `serde` of crate `yaiouom`.
+#[proc_macro_derive(UnitSerialize)]
+pub fn derive_unit_serialize(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match pretty::expand_serialize(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Derive `yaiouom::reflect::UnitReflect` for a struct of measures, to
+/// inspect the units of its fields at runtime.
+///
+/// Each field must be a `Measure` or a struct that derives `UnitReflect`.
+///
+/// ```ignore
+/// #[derive(UnitReflect)]
+/// struct State {
+///     position: Measure<f64, Meter>,
+///     speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+/// }
+///
+/// let schema = UnitSchema::of::<State>();
+/// assert_eq!(schema.unit("speed").unwrap().to_string(), "m * s^-1");
+/// ```
+#[proc_macro_derive(UnitReflect)]
+pub fn derive_unit_reflect(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match pretty::expand_reflect(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Derive `yaiouom::io::UnitRecord` for a struct of measures, to write it
+/// to CSV and read it back.
+///
+/// Each field must be a `Measure` or a struct that derives `UnitRecord`.
+/// The struct must also derive `UnitReflect`.
+///
+/// ```ignore
+/// #[derive(UnitReflect, UnitRecord)]
+/// struct State {
+///     position: Measure<f64, Meter>,
+///     speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+/// }
+///
+/// yaiouom::io::write_csv(file, &states)?;
+/// ```
+#[proc_macro_derive(UnitRecord)]
+pub fn derive_unit_record(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match pretty::expand_record(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// Derive a builder for a struct with named fields, e.g. a configuration,
+/// whose setters only accept values with the unit of the field.
+///
+/// For a struct `Config`, this generates `Config::builder()`, which returns
+/// a `ConfigBuilder`, with one setter per field and a method `build`.
+///
+/// ```ignore
+/// #[derive(UnitBuilder)]
+/// struct Config {
+///     timeout: Measure<u64, Second>,
+/// }
+///
+/// let config = Config::builder().timeout(Second::new(5)).build()?;
+/// ```
+#[proc_macro_derive(UnitBuilder)]
+pub fn derive_unit_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match pretty::expand_builder(&input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => compile_error(err),
+    }
+}
+
+/// The only field of a newtype-style struct.
+fn single_field(input: &DeriveInput) -> Result<&Field, syn::Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            Fields::Unnamed(ref fields) => &fields.unnamed,
+            Fields::Unit => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "HasUnit can only be derived for a struct with a single field",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "HasUnit can only be derived for a struct with a single field",
+            ))
+        }
+    };
+    if fields.len() != 1 {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "HasUnit can only be derived for a struct with a single field",
+        ));
+    }
+    Ok(&fields[0])
+}
+
+/// Convert a measure to an equivalent unit, with a proof that both units
+/// are equal, for use when the linter is not available.
+///
+/// Macros cannot see the types of expressions, so the unit of the measure
+/// must be written down, as well as the target unit. The macro expands to
+/// a sequence of calls to `rewrite`, so the result is checked by the Rust
+/// type system.
+///
+/// ```ignore
+/// let speed = auto_unify!(distance / duration, Mul<Meter, Inv<Second>> => Mul<Inv<Second>, Meter>);
+/// ```
+#[proc_macro]
+pub fn auto_unify(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as unify::Input);
+    match unify::expand(input) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => {
+            // We're in expression position.
+            let errors: proc_macro2::TokenStream = compile_error(err).into();
+            let expanded = quote!({ #errors });
+            expanded.into()
+        }
+    }
+}
+
+/// Skip the dynamic check of `unify` in a function verified by the linter,
+/// e.g. a hot function inside a tight loop of a debug build.
+///
+/// The calls to `unify` in the body of the function are replaced with
+/// calls to `unify_trusted`, which the linter checks in the same way.
+/// Calls within macro invocations are left unchanged.
+///
+/// ```ignore
+/// #[yaiouom::trusted]
+/// fn step(position: Measure<f64, Meter>, speed: Measure<f64, Speed>, dt: Measure<f64, Second>) -> Measure<f64, Meter> {
+///     position + (speed * dt).unify()
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn trusted(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        let attr = proc_macro2::TokenStream::from(attr);
+        return compile_error(syn::Error::new_spanned(
+            attr,
+            "#[trusted] does not take arguments",
+        ));
+    }
+    let item = parse_macro_input!(item as syn::ItemFn);
+    trusted::expand(item).into()
+}
+
+/// Report `err` at its span.
+///
+/// Unlike `syn::Error::to_compile_error`, this does not expand to
+/// `::core::compile_error!`, which cannot be resolved from a crate
+/// in edition 2015.
+fn compile_error(err: syn::Error) -> TokenStream {
+    let errors = err.into_iter().map(|err| {
+        let message = err.to_string();
+        quote_spanned!(err.span() => compile_error!(#message);)
+    });
+    let expanded = quote! {
+        #(#errors)*
+    };
+    expanded.into()
+}
@@ -0,0 +1,268 @@
+//! Implementation of the derives for structs of measures: `UnitDebug`,
+//! `UnitSerialize`, `UnitReflect`, `UnitRecord` and `UnitBuilder`.
+
+use proc_macro2::TokenStream;
+use syn::{Data, DeriveInput, Fields, Member, Type};
+
+/// A field of the struct, as accessed by the generated code.
+struct Field<'a> {
+    member: Member,
+    name: String,
+    ty: &'a Type,
+}
+
+/// The fields of a struct with named or unnamed fields.
+fn fields<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<Field<'a>>, syn::Error> {
+    let fields = match input.data {
+        Data::Struct(ref data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                format!("{} can only be derived for a struct", derive),
+            ))
+        }
+    };
+    if let Fields::Unit = *fields {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!("{} can only be derived for a struct with fields", derive),
+        ));
+    }
+    Ok(fields
+        .iter()
+        .zip(fields.members())
+        .map(|(field, member)| {
+            let name = match member {
+                Member::Named(ref ident) => ident.to_string(),
+                Member::Unnamed(ref index) => index.index.to_string(),
+            };
+            Field {
+                member,
+                name,
+                ty: &field.ty,
+            }
+        })
+        .collect())
+}
+
+/// Add `bound` to each field type of `input`.
+fn bounded(input: &DeriveInput, fields: &[Field], bound: TokenStream) -> syn::Generics {
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = field.ty;
+        where_clause.predicates.push(parse_quote!(#ty: #bound));
+    }
+    generics
+}
+
+pub fn expand_debug(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = fields(input, "UnitDebug")?;
+    let generics = bounded(
+        input,
+        &fields,
+        quote!(::std::fmt::Debug + ::yaiouom::pretty::UnitFields),
+    );
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let name = ident.to_string();
+
+    let is_tuple = match fields[0].member {
+        Member::Named(_) => false,
+        Member::Unnamed(_) => true,
+    };
+    let debug_fields = fields.iter().map(|field| {
+        let member = &field.member;
+        let name = &field.name;
+        if is_tuple {
+            quote!(.field(&::yaiouom::pretty::WithUnit(&self.#member)))
+        } else {
+            quote!(.field(#name, &::yaiouom::pretty::WithUnit(&self.#member)))
+        }
+    });
+    let builder = if is_tuple {
+        quote!(debug_tuple)
+    } else {
+        quote!(debug_struct)
+    };
+    let unit_fields = fields.iter().map(|field| {
+        let member = &field.member;
+        let name = &field.name;
+        quote! {
+            for mut field in ::yaiouom::pretty::UnitFields::unit_fields(&self.#member) {
+                field.path = if field.path.is_empty() {
+                    #name.to_string()
+                } else {
+                    format!("{}.{}", #name, field.path)
+                };
+                fields.push(field);
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::std::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, fmt: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                fmt.#builder(#name)
+                    #(#debug_fields)*
+                    .finish()
+            }
+        }
+        impl #impl_generics ::yaiouom::pretty::UnitFields for #ident #ty_generics #where_clause {
+            fn unit_fields(&self) -> ::std::vec::Vec<::yaiouom::pretty::UnitField> {
+                let mut fields = ::std::vec::Vec::new();
+                #(#unit_fields)*
+                fields
+            }
+        }
+    })
+}
+
+pub fn expand_serialize(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = fields(input, "UnitSerialize")?;
+    let generics = bounded(input, &fields, quote!(::yaiouom::pretty::UnitSerialize));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let name = ident.to_string();
+    let len = fields.len();
+    let serialize_fields = fields.iter().map(|field| {
+        let member = &field.member;
+        let name = &field.name;
+        quote! {
+            state.serialize_field(#name, &::yaiouom::pretty::WithUnit(&self.#member))?;
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::yaiouom::pretty::UnitSerialize for #ident #ty_generics #where_clause {
+            fn serialize_with_unit<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where S: ::yaiouom::pretty::__serde::Serializer
+            {
+                use ::yaiouom::pretty::__serde::ser::SerializeStruct;
+                let mut state = serializer.serialize_struct(#name, #len)?;
+                #(#serialize_fields)*
+                state.end()
+            }
+        }
+        impl #impl_generics ::yaiouom::pretty::__serde::Serialize for #ident #ty_generics #where_clause {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+                where S: ::yaiouom::pretty::__serde::Serializer
+            {
+                ::yaiouom::pretty::UnitSerialize::serialize_with_unit(self, serializer)
+            }
+        }
+    })
+}
+
+pub fn expand_reflect(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = fields(input, "UnitReflect")?;
+    let generics = bounded(input, &fields, quote!(::yaiouom::reflect::UnitReflect));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let push_fields = fields.iter().map(|field| {
+        let ty = field.ty;
+        let name = &field.name;
+        quote! {
+            schema.push_nested(#name, <#ty as ::yaiouom::reflect::UnitReflect>::unit_schema());
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::yaiouom::reflect::UnitReflect for #ident #ty_generics #where_clause {
+            fn unit_schema() -> ::yaiouom::reflect::UnitSchema {
+                let mut schema = ::yaiouom::reflect::UnitSchema::new();
+                #(#push_fields)*
+                schema
+            }
+        }
+    })
+}
+
+pub fn expand_record(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = fields(input, "UnitRecord")?;
+    let generics = bounded(input, &fields, quote!(::yaiouom::io::UnitRecord));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let ident = &input.ident;
+    let write_fields = fields.iter().map(|field| {
+        let member = &field.member;
+        quote!(::yaiouom::io::UnitRecord::write_record(&self.#member, record);)
+    });
+    let read_fields = fields.iter().map(|field| {
+        let member = &field.member;
+        let ty = field.ty;
+        quote!(#member: <#ty as ::yaiouom::io::UnitRecord>::read_record(record)?,)
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::yaiouom::io::UnitRecord for #ident #ty_generics #where_clause {
+            fn write_record(&self, record: &mut ::std::vec::Vec<::std::string::String>) {
+                #(#write_fields)*
+            }
+            fn read_record(record: &mut ::std::slice::Iter<&str>)
+                -> ::std::result::Result<Self, ::yaiouom::io::InvalidValue>
+            {
+                // Fields are initialized in order of declaration, i.e. in the order
+                // of the schema.
+                Ok(#ident {
+                    #(#read_fields)*
+                })
+            }
+        }
+    })
+}
+
+pub fn expand_builder(input: &DeriveInput) -> Result<TokenStream, syn::Error> {
+    let fields = fields(input, "UnitBuilder")?;
+    if let Member::Unnamed(_) = fields[0].member {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "UnitBuilder can only be derived for a struct with named fields",
+        ));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let ident = &input.ident;
+    let vis = &input.vis;
+    let builder = format_ident!("{}Builder", ident);
+    let builder_doc = format!("A builder for `{}`.", ident);
+
+    let members: Vec<_> = fields.iter().map(|field| &field.member).collect();
+    let types: Vec<_> = fields.iter().map(|field| field.ty).collect();
+    let names: Vec<_> = fields.iter().map(|field| &field.name).collect();
+
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder #impl_generics #where_clause {
+            #(#members: ::std::option::Option<#types>,)*
+        }
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Start building a value, field by field.
+            #vis fn builder() -> #builder #ty_generics {
+                #builder {
+                    #(#members: ::std::option::Option::None,)*
+                }
+            }
+        }
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                #vis fn #members<V: ::std::convert::Into<#types>>(mut self, value: V) -> Self {
+                    self.#members = ::std::option::Option::Some(value.into());
+                    self
+                }
+            )*
+
+            /// Build the value, if all fields have been set.
+            #vis fn build(self) -> ::std::result::Result<#ident #ty_generics, ::yaiouom::builder::MissingField> {
+                Ok(#ident {
+                    #(
+                        #members: match self.#members {
+                            ::std::option::Option::Some(value) => value,
+                            ::std::option::Option::None => {
+                                return ::std::result::Result::Err(::yaiouom::builder::MissingField::new(#names))
+                            }
+                        },
+                    )*
+                })
+            }
+        }
+    })
+}
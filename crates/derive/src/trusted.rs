@@ -0,0 +1,26 @@
+//! Implementation of `#[trusted]`.
+//!
+//! Macros cannot see the types of expressions, so any method call
+//! `.unify()` is assumed to be `Measure::unify`, and replaced with
+//! `.unify_trusted()`, which has the same signature.
+
+use proc_macro2::TokenStream;
+use syn::visit_mut::{self, VisitMut};
+use syn::{ExprMethodCall, ItemFn};
+
+/// Replace the calls to `unify` in a function body.
+struct Rewriter;
+
+impl VisitMut for Rewriter {
+    fn visit_expr_method_call_mut(&mut self, call: &mut ExprMethodCall) {
+        if call.method == "unify" && call.args.is_empty() {
+            call.method = syn::Ident::new("unify_trusted", call.method.span());
+        }
+        visit_mut::visit_expr_method_call_mut(self, call);
+    }
+}
+
+pub fn expand(mut item: ItemFn) -> TokenStream {
+    Rewriter.visit_item_fn_mut(&mut item);
+    quote!(#item)
+}
@@ -0,0 +1,386 @@
+//! Implementation of `auto_unify!`.
+//!
+//! Both units are normalized, by rewriting them with the combinators of
+//! `yaiouom::logics`, into a right-nested product of sorted factors, each
+//! of them an atom or the inverse of an atom, from which pairs `A * A^-1`
+//! have been cancelled. If both normal forms are identical, the proof is
+//! the rewriting of the source into the normal form, followed by the
+//! rewriting of the normal form back into the target.
+//!
+//! Atoms are compared syntactically, so `Meter` and `si::Meter` are
+//! considered distinct.
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, GenericArgument, PathArguments, Type};
+
+/// The arguments of `auto_unify!(expr, Source => Target)`.
+pub struct Input {
+    expr: Expr,
+    source: Type,
+    target: Type,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let expr = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let source = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let target = input.parse()?;
+        Ok(Input {
+            expr,
+            source,
+            target,
+        })
+    }
+}
+
+/// A unit, as written by the user.
+#[derive(Clone)]
+enum Term {
+    Mul(Box<Term>, Box<Term>),
+    Inv(Box<Term>),
+    Dimensionless,
+    /// Anything else, along with its textual representation,
+    /// used for comparison.
+    Atom(Box<Type>, String),
+}
+
+impl Term {
+    fn from_type(ty: &Type) -> Term {
+        match *ty {
+            Type::Paren(ref paren) => return Term::from_type(&paren.elem),
+            Type::Group(ref group) => return Term::from_type(&group.elem),
+            Type::Path(ref path) if path.qself.is_none() => {
+                let segment = path.path.segments.last().unwrap();
+                let args: Vec<&Type> = match segment.arguments {
+                    PathArguments::AngleBracketed(ref args) => args
+                        .args
+                        .iter()
+                        .filter_map(|arg| match *arg {
+                            GenericArgument::Type(ref ty) => Some(ty),
+                            _ => None,
+                        })
+                        .collect(),
+                    _ => vec![],
+                };
+                match (segment.ident.to_string().as_str(), args.len()) {
+                    ("Mul", 2) => {
+                        return Term::Mul(
+                            Box::new(Term::from_type(args[0])),
+                            Box::new(Term::from_type(args[1])),
+                        )
+                    }
+                    ("Inv", 1) => return Term::Inv(Box::new(Term::from_type(args[0]))),
+                    ("Dimensionless", 0) => return Term::Dimensionless,
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+        let key = ty.to_token_stream().to_string();
+        Term::Atom(Box::new(ty.clone()), key)
+    }
+
+    fn mul(left: Term, right: Term) -> Term {
+        Term::Mul(Box::new(left), Box::new(right))
+    }
+
+    fn inv(inner: Term) -> Term {
+        Term::Inv(Box::new(inner))
+    }
+
+    /// The key used to sort factors of a normal form: atoms are sorted by
+    /// name, and each atom comes before its inverse. `None` if this is not
+    /// a factor, i.e. neither an atom nor the inverse of an atom.
+    fn key(&self) -> Option<(&str, bool)> {
+        match *self {
+            Term::Atom(_, ref key) => Some((key, false)),
+            Term::Inv(ref inner) => match **inner {
+                Term::Atom(_, ref key) => Some((key, true)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        match *self {
+            Term::Mul(ref left, ref right) => format!("{} * {}", left.describe(), right.describe()),
+            Term::Inv(ref inner) => match **inner {
+                Term::Atom(..) => format!("{}^-1", inner.describe()),
+                _ => format!("({})^-1", inner.describe()),
+            },
+            Term::Dimensionless => "Dimensionless".to_string(),
+            Term::Atom(_, ref key) => key.clone(),
+        }
+    }
+}
+
+impl ToTokens for Term {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match *self {
+            Term::Mul(ref left, ref right) => quote!(::yaiouom::Mul<#left, #right>),
+            Term::Inv(ref inner) => quote!(::yaiouom::Inv<#inner>),
+            Term::Dimensionless => quote!(::yaiouom::Dimensionless),
+            Term::Atom(ref ty, _) => quote!(#ty),
+        })
+    }
+}
+
+/// A proof, mirroring the combinators of `yaiouom::logics`.
+enum Proof {
+    Id(Term),
+    Comm(Term, Term),
+    Assoc(Term, Term, Term),
+    Dimensionless(Term),
+    Inverse(Term),
+    InvMul(Term, Term),
+    InvInv(Term),
+    InvDimensionless,
+    Mul(Box<Proof>, Box<Proof>),
+    Inv(Box<Proof>),
+    Sym(Box<Proof>),
+}
+
+impl ToTokens for Proof {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match *self {
+            Proof::Id(ref a) => quote!(::yaiouom::logics::PId<#a>),
+            Proof::Comm(ref a, ref b) => quote!(::yaiouom::logics::PComm<#a, #b>),
+            Proof::Assoc(ref a, ref b, ref c) => quote!(::yaiouom::logics::PAssoc<#a, #b, #c>),
+            Proof::Dimensionless(ref a) => quote!(::yaiouom::logics::PDimensionless<#a>),
+            Proof::Inverse(ref a) => quote!(::yaiouom::logics::PInverse<#a>),
+            Proof::InvMul(ref a, ref b) => quote!(::yaiouom::logics::PInvMul<#a, #b>),
+            Proof::InvInv(ref a) => quote!(::yaiouom::logics::PInvInv<#a>),
+            Proof::InvDimensionless => quote!(::yaiouom::logics::PInvDimensionless),
+            Proof::Mul(ref p, ref q) => quote!(::yaiouom::logics::PMul<#p, #q>),
+            Proof::Inv(ref p) => quote!(::yaiouom::logics::PInv<#p>),
+            Proof::Sym(ref p) => quote!(::yaiouom::logics::PSym<#p>),
+        })
+    }
+}
+
+/// A sequence of rewrites, each of them a proof that the previous unit
+/// is equal to the next one.
+struct Rewrites {
+    steps: Vec<Proof>,
+}
+
+impl Rewrites {
+    /// Apply `rule` to the first subterm of `term` (in pre-order) for
+    /// which it returns `Some`, recording the corresponding steps.
+    ///
+    /// Returns `None` if the rule applies nowhere.
+    fn rewrite_once<F>(&mut self, term: &Term, rule: &F) -> Option<Term>
+    where
+        F: Fn(&Term) -> Option<(Vec<Proof>, Term)>,
+    {
+        if let Some((steps, result)) = rule(term) {
+            self.steps.extend(steps);
+            return Some(result);
+        }
+        let mut inner = Rewrites { steps: vec![] };
+        match *term {
+            Term::Mul(ref left, ref right) => {
+                if let Some(result) = inner.rewrite_once(left, rule) {
+                    self.steps.extend(inner.steps.into_iter().map(|step| {
+                        Proof::Mul(Box::new(step), Box::new(Proof::Id((**right).clone())))
+                    }));
+                    return Some(Term::mul(result, (**right).clone()));
+                }
+                if let Some(result) = inner.rewrite_once(right, rule) {
+                    self.steps.extend(inner.steps.into_iter().map(|step| {
+                        Proof::Mul(Box::new(Proof::Id((**left).clone())), Box::new(step))
+                    }));
+                    return Some(Term::mul((**left).clone(), result));
+                }
+                None
+            }
+            Term::Inv(ref item) => {
+                let result = inner.rewrite_once(item, rule)?;
+                self.steps.extend(
+                    inner
+                        .steps
+                        .into_iter()
+                        .map(|step| Proof::Inv(Box::new(step))),
+                );
+                Some(Term::inv(result))
+            }
+            _ => None,
+        }
+    }
+
+    /// Apply `rule` until it applies nowhere.
+    fn rewrite_all<F>(&mut self, mut term: Term, rule: &F) -> Term
+    where
+        F: Fn(&Term) -> Option<(Vec<Proof>, Term)>,
+    {
+        while let Some(result) = self.rewrite_once(&term, rule) {
+            term = result;
+        }
+        term
+    }
+}
+
+/// Push inverses down to atoms, flatten products to the right and remove
+/// `Dimensionless` factors.
+fn flatten(term: &Term) -> Option<(Vec<Proof>, Term)> {
+    match *term {
+        Term::Inv(ref inner) => match **inner {
+            Term::Mul(ref a, ref b) => Some((
+                vec![Proof::InvMul((**a).clone(), (**b).clone())],
+                Term::mul(Term::inv((**a).clone()), Term::inv((**b).clone())),
+            )),
+            Term::Inv(ref a) => Some((vec![Proof::InvInv((**a).clone())], (**a).clone())),
+            Term::Dimensionless => Some((vec![Proof::InvDimensionless], Term::Dimensionless)),
+            _ => None,
+        },
+        Term::Mul(ref left, ref right) => match (&**left, &**right) {
+            (Term::Mul(a, b), c) => Some((
+                vec![Proof::Assoc((**a).clone(), (**b).clone(), c.clone())],
+                Term::mul((**a).clone(), Term::mul((**b).clone(), c.clone())),
+            )),
+            (a, Term::Dimensionless) => Some((vec![Proof::Dimensionless(a.clone())], a.clone())),
+            (Term::Dimensionless, b) => Some((
+                vec![
+                    Proof::Comm(Term::Dimensionless, b.clone()),
+                    Proof::Dimensionless(b.clone()),
+                ],
+                b.clone(),
+            )),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn unsorted(a: &Term, b: &Term) -> bool {
+    match (a.key(), b.key()) {
+        (Some(a), Some(b)) => a > b,
+        _ => false,
+    }
+}
+
+/// In a flattened product, swap the first two factors that are not sorted.
+fn sort(term: &Term) -> Option<(Vec<Proof>, Term)> {
+    let (a, rest) = match *term {
+        Term::Mul(ref a, ref rest) => (&**a, &**rest),
+        _ => return None,
+    };
+    match *rest {
+        Term::Mul(ref b, ref rest) if unsorted(a, b) => Some((
+            vec![
+                Proof::Sym(Box::new(Proof::Assoc(
+                    a.clone(),
+                    (**b).clone(),
+                    (**rest).clone(),
+                ))),
+                Proof::Mul(
+                    Box::new(Proof::Comm(a.clone(), (**b).clone())),
+                    Box::new(Proof::Id((**rest).clone())),
+                ),
+                Proof::Assoc((**b).clone(), a.clone(), (**rest).clone()),
+            ],
+            Term::mul((**b).clone(), Term::mul(a.clone(), (**rest).clone())),
+        )),
+        Term::Mul(..) => None,
+        ref b if unsorted(a, b) => Some((
+            vec![Proof::Comm(a.clone(), b.clone())],
+            Term::mul(b.clone(), a.clone()),
+        )),
+        _ => None,
+    }
+}
+
+/// In a sorted product, cancel the first factor followed by its inverse.
+fn cancel(term: &Term) -> Option<(Vec<Proof>, Term)> {
+    let (a, rest) = match *term {
+        Term::Mul(ref a, ref rest) => (&**a, &**rest),
+        _ => return None,
+    };
+    let cancels = |b: &Term| match (a.key(), b.key()) {
+        (Some((a, false)), Some((b, true))) => a == b,
+        _ => false,
+    };
+    match *rest {
+        Term::Mul(ref b, ref rest) if cancels(b) => Some((
+            vec![
+                Proof::Sym(Box::new(Proof::Assoc(
+                    a.clone(),
+                    (**b).clone(),
+                    (**rest).clone(),
+                ))),
+                Proof::Mul(
+                    Box::new(Proof::Inverse(a.clone())),
+                    Box::new(Proof::Id((**rest).clone())),
+                ),
+                Proof::Comm(Term::Dimensionless, (**rest).clone()),
+                Proof::Dimensionless((**rest).clone()),
+            ],
+            (**rest).clone(),
+        )),
+        Term::Mul(..) => None,
+        ref b if cancels(b) => Some((vec![Proof::Inverse(a.clone())], Term::Dimensionless)),
+        _ => None,
+    }
+}
+
+/// Rewrite `term` into its normal form.
+fn normalize(term: Term) -> (Vec<Proof>, Term) {
+    let mut rewrites = Rewrites { steps: vec![] };
+    let term = rewrites.rewrite_all(term, &flatten);
+    let term = rewrites.rewrite_all(term, &sort);
+    let term = rewrites.rewrite_all(term, &cancel);
+    // Cancelling the last two factors leaves a `Dimensionless`.
+    let term = rewrites.rewrite_all(term, &flatten);
+    (rewrites.steps, term)
+}
+
+fn same(left: &Term, right: &Term) -> bool {
+    match (left, right) {
+        (Term::Mul(a, b), Term::Mul(c, d)) => same(a, c) && same(b, d),
+        (Term::Inv(a), Term::Inv(b)) => same(a, b),
+        (Term::Dimensionless, Term::Dimensionless) => true,
+        (Term::Atom(_, a), Term::Atom(_, b)) => a == b,
+        _ => false,
+    }
+}
+
+pub fn expand(input: Input) -> Result<TokenStream, syn::Error> {
+    let source = Term::from_type(&input.source);
+    let target = Term::from_type(&input.target);
+    let (source_steps, source_normal) = normalize(source.clone());
+    let (target_steps, target_normal) = normalize(target.clone());
+    if !same(&source_normal, &target_normal) {
+        return Err(syn::Error::new_spanned(
+            &input.target,
+            format!(
+                "cannot unify `{}` with `{}`: these units normalize into `{}` and `{}`",
+                source.describe(),
+                target.describe(),
+                source_normal.describe(),
+                target_normal.describe()
+            ),
+        ));
+    }
+    let steps = source_steps.into_iter().chain(
+        target_steps
+            .into_iter()
+            .rev()
+            .map(|step| Proof::Sym(Box::new(step))),
+    );
+    let expr = &input.expr;
+    let source = &input.source;
+    let target = &input.target;
+    Ok(quote! {
+        {
+            let measure: ::yaiouom::Measure<_, #source> = #expr;
+            let measure: ::yaiouom::Measure<_, #target> = measure
+                #(.rewrite::<#steps>())*;
+            measure
+        }
+    })
+}
@@ -0,0 +1,57 @@
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+#[test]
+fn commutativity() {
+    let speed = Meter::new(10.) / Second::new(2.);
+    let speed: Measure<f64, Mul<Inv<Second>, Meter>> =
+        auto_unify!(speed, Mul<Meter, Inv<Second>> => Mul<Inv<Second>, Meter>);
+    assert_eq!(speed.as_ref(), &5.);
+}
+
+#[test]
+fn associativity() {
+    let volume = Meter::new(1) * Meter::new(2) * Meter::new(3);
+    let volume = auto_unify!(volume, Mul<Mul<Meter, Meter>, Meter> => Mul<Meter, Mul<Meter, Meter>>);
+    assert_eq!(volume.as_ref(), &6);
+}
+
+#[test]
+fn cancellation() {
+    let distance = Meter::new(6.) / Second::new(2.) * Second::new(2.);
+    let distance = auto_unify!(distance, Mul<Mul<Meter, Inv<Second>>, Second> => Meter);
+    assert_eq!(distance, Meter::new(6.));
+
+    let ratio = Meter::new(6.) / Meter::new(2.);
+    let ratio = auto_unify!(ratio, Mul<Meter, Inv<Meter>> => Dimensionless);
+    assert_eq!(ratio.unwrap(), 3.);
+}
+
+#[test]
+fn inverses() {
+    // (m * s^-1)^-1 == s * m^-1
+    let pace = 1. / (Meter::new(6.) / Second::new(2.));
+    let pace = auto_unify!(pace, Inv<Mul<Meter, Inv<Second>>> => Mul<Second, Inv<Meter>>);
+    assert_eq!(pace.as_ref(), &(1. / 3.));
+
+    // (m^-1)^-1 * Dimensionless^-1 == m
+    let distance: Measure<f64, Mul<Inv<Inv<Meter>>, Inv<Dimensionless>>> = Measure::new(1.);
+    let distance = auto_unify!(distance, Mul<Inv<Inv<Meter>>, Inv<Dimensionless>> => Meter);
+    assert_eq!(distance, Meter::new(1.));
+}
+
+#[test]
+fn generics() {
+    fn speed<A: Unit, B: Unit, W: Unit>(
+        distance: Measure<f64, Mul<W, A>>,
+        duration: Measure<f64, Mul<B, W>>,
+    ) -> Measure<f64, Mul<A, Inv<B>>> {
+        auto_unify!(distance / duration, Mul<Mul<W, A>, Inv<Mul<B, W>>> => Mul<A, Inv<B>>)
+    }
+    let distance = speed::<Meter, Second, Kg>(Measure::new(6.), Measure::new(2.));
+    assert_eq!(distance.as_ref(), &3.);
+}
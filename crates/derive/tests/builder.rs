@@ -0,0 +1,53 @@
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+#[derive(UnitBuilder, Debug, PartialEq)]
+struct Config {
+    timeout: Measure<u64, Second>,
+    max_speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+    retries: Measure<u32, Dimensionless>,
+    name: String,
+}
+
+#[derive(UnitBuilder)]
+struct Tagged<U: Unit> {
+    value: Measure<f32, U>,
+}
+
+#[test]
+fn derive_unit_builder() {
+    let config = Config::builder()
+        .timeout(Second::new(5))
+        .max_speed(Meter::new(10.) / Second::new(1.))
+        .retries(3)
+        .name("test")
+        .build()
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            timeout: Second::new(5),
+            max_speed: Meter::new(10.) / Second::new(1.),
+            retries: Dimensionless::new(3),
+            name: "test".to_string(),
+        }
+    );
+
+    let tagged: Tagged<Kg> = Tagged::builder().value(Kg::new(1.)).build().unwrap();
+    assert_eq!(tagged.value, Kg::new(1.));
+}
+
+#[test]
+fn missing_field() {
+    let err = Config::builder()
+        .timeout(Second::new(5))
+        .retries(3)
+        .build()
+        .unwrap_err();
+    assert_eq!(err.name(), "max_speed");
+    assert_eq!(err.to_string(), "Missing value for field `max_speed`");
+}
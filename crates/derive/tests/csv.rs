@@ -0,0 +1,105 @@
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::io::*;
+use yaiouom::si::*;
+use yaiouom::*;
+
+#[derive(UnitReflect, UnitRecord, Debug, PartialEq)]
+struct Position {
+    x: Measure<f64, Meter>,
+    y: Measure<f64, Meter>,
+}
+
+#[derive(UnitReflect, UnitRecord, Debug, PartialEq)]
+struct State {
+    position: Position,
+    speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+    bounces: Measure<u32, Dimensionless>,
+}
+
+#[derive(UnitReflect, UnitRecord, Debug, PartialEq)]
+struct Sample(Measure<f64, Second>, Measure<f64, Kg>);
+
+fn states() -> Vec<State> {
+    (0..3)
+        .map(|i| State {
+            position: Position {
+                x: Meter::new(i as f64),
+                y: Meter::new(1.5 * i as f64),
+            },
+            speed: Meter::new(10.) / Second::new(1. + i as f64),
+            bounces: Dimensionless::new(i),
+        })
+        .collect()
+}
+
+#[test]
+fn roundtrip() {
+    let mut file = vec![];
+    write_csv(&mut file, &states()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&file).unwrap(),
+        "\
+position.x (m),position.y (m),speed (m * s^-1),bounces
+0,0,10,0
+1,1.5,5,1
+2,3,3.3333333333333335,2
+"
+    );
+    let read: Vec<State> = read_csv(&file[..]).unwrap();
+    assert_eq!(read, states());
+
+    let samples = vec![Sample(Second::new(1.), Kg::new(2.))];
+    let mut file = vec![];
+    write_csv(&mut file, &samples).unwrap();
+    assert_eq!(std::str::from_utf8(&file).unwrap(), "0 (s),1 (kg)\n1,2\n");
+    assert_eq!(read_csv::<_, Sample>(&file[..]).unwrap(), samples);
+}
+
+#[test]
+fn read_checks_units() {
+    // Factors may appear in any order.
+    let file = "position.x (m),position.y (m),speed (s^-1 * m),bounces\n1,2,3,4\n";
+    let read: Vec<State> = read_csv(file.as_bytes()).unwrap();
+    assert_eq!(read[0].speed, Meter::new(3.) / Second::new(1.));
+
+    let file = "position.x (m),position.y (m),speed (m * s^-2),bounces\n1,2,3,4\n";
+    let err = read_csv::<_, State>(file.as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Column `speed`: expected unit `m * s^-1`, found `m * s^-2`"
+    );
+
+    let file = "position.x (m),speed (m * s^-1),bounces\n1,3,4\n";
+    let err = read_csv::<_, State>(file.as_bytes()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Expected columns `position.x,position.y,speed,bounces`, found `position.x,speed,bounces`"
+    );
+}
+
+#[test]
+fn read_checks_values() {
+    let header = "position.x (m),position.y (m),speed (m * s^-1),bounces\n";
+    let errors = vec![
+        (
+            "1,2,3,4\n1,x,3,4\n",
+            "Line 3: invalid value `x` for column `position.y`",
+        ),
+        (
+            "1,2,3,-4\n",
+            "Line 2: invalid value `-4` for column `bounces`",
+        ),
+        ("1,2\n", "Line 2: missing value for column `speed`"),
+        ("1,2,3,4,5\n", "Line 2: too many values"),
+    ];
+    for (values, expected) in errors {
+        let file = format!("{}{}", header, values);
+        let err = read_csv::<_, State>(file.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+    let err = read_csv::<_, State>("".as_bytes()).unwrap_err();
+    assert_eq!(err.to_string(), "Missing header");
+}
@@ -0,0 +1,39 @@
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+#[derive(HasUnit)]
+struct Altitude(Measure<f64, Meter>);
+
+#[derive(HasUnit)]
+struct Timeout {
+    duration: Measure<u64, Second>,
+}
+
+#[derive(HasUnit)]
+struct Tagged<U: Unit>(Measure<f32, U>);
+
+#[derive(HasUnit)]
+struct Nested(Altitude);
+
+fn unit_of<T: HasUnit>() -> String {
+    T::runtime_unit().to_string()
+}
+
+#[test]
+fn derive_has_unit() {
+    let altitude = Nested(Altitude(Meter::new(1.)));
+    let timeout = Timeout { duration: Second::new(1) };
+    let tagged: Tagged<Kg> = Tagged(Kg::new(1.));
+    assert_eq!((altitude.0).0, Meter::new(1.));
+    assert_eq!(timeout.duration, Second::new(1));
+    assert_eq!(tagged.0, Kg::new(1.));
+
+    assert_eq!(unit_of::<Altitude>(), "m");
+    assert_eq!(unit_of::<Timeout>(), "s");
+    assert_eq!(unit_of::<Tagged<Mul<Meter, Inv<Second>>>>(), "m * s^-1");
+    assert_eq!(unit_of::<Nested>(), "m");
+}
@@ -0,0 +1,62 @@
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::reflect::*;
+use yaiouom::si::*;
+use yaiouom::*;
+
+#[derive(UnitReflect)]
+#[allow(dead_code)]
+struct Position {
+    x: Measure<f64, Meter>,
+    y: Measure<f64, Meter>,
+}
+
+#[derive(UnitReflect)]
+#[allow(dead_code)]
+struct State {
+    position: Position,
+    speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+    bounces: Measure<u32, Dimensionless>,
+}
+
+#[derive(UnitReflect)]
+#[allow(dead_code)]
+struct Tagged<U: Unit>(Measure<f32, U>, Measure<f32, Inv<U>>);
+
+fn describe<T: UnitReflect>() -> Vec<(String, String)> {
+    UnitSchema::of::<T>()
+        .fields()
+        .iter()
+        .map(|(path, unit)| (path.clone(), unit.to_string()))
+        .collect()
+}
+
+#[test]
+fn derive_unit_reflect() {
+    let expected = vec![
+        ("position.x", "m"),
+        ("position.y", "m"),
+        ("speed", "m * s^-1"),
+        ("bounces", ""),
+    ];
+    let expected: Vec<_> = expected
+        .into_iter()
+        .map(|(path, unit)| (path.to_string(), unit.to_string()))
+        .collect();
+    assert_eq!(describe::<State>(), expected);
+
+    let tagged = describe::<Tagged<Kg>>();
+    assert_eq!(
+        tagged,
+        vec![
+            ("0".to_string(), "kg".to_string()),
+            ("1".to_string(), "kg^-1".to_string())
+        ]
+    );
+
+    let schema = UnitSchema::of::<State>();
+    assert_eq!(schema.unit("position.y"), Some(&Meter::as_runtime()));
+    assert_eq!(schema.unit("position"), None);
+}
@@ -0,0 +1,64 @@
+extern crate yaiouom;
+extern crate yaiouom_derive;
+
+use yaiouom::si::*;
+use yaiouom::*;
+use yaiouom_derive::trusted;
+
+type Speed = Mul<Meter, Inv<Second>>;
+
+#[trusted]
+fn step(
+    position: Measure<f64, Meter>,
+    speed: Measure<f64, Speed>,
+    dt: Measure<f64, Second>,
+) -> Measure<f64, Meter> {
+    position + (speed * dt).unify()
+}
+
+#[test]
+fn unify() {
+    let position = step(
+        Meter::new(1.),
+        Meter::new(2.) / Second::new(1.),
+        Second::new(3.),
+    );
+    assert_eq!(position, Meter::new(7.));
+}
+
+// Only the linter would reject this function.
+#[trusted]
+fn wrong(speed: Measure<f64, Speed>) -> Measure<f64, Meter> {
+    let closure = |speed: Measure<f64, Speed>| -> Measure<f64, Meter> { speed.unify() };
+    closure(speed)
+}
+
+#[test]
+fn no_dynamic_check() {
+    let speed = Meter::new(2.) / Second::new(1.);
+    assert_eq!(wrong(speed), Meter::new(2.));
+    let result = std::panic::catch_unwind(|| {
+        let _: Measure<f64, Meter> = speed.unify();
+    });
+    assert_eq!(result.is_err(), cfg!(debug_assertions));
+}
+
+struct Integrator {
+    position: Measure<f64, Meter>,
+}
+
+impl Integrator {
+    #[trusted]
+    fn step(&mut self, speed: Measure<f64, Speed>, dt: Measure<f64, Second>) {
+        self.position = self.position + (speed * dt).unify();
+    }
+}
+
+#[test]
+fn methods() {
+    let mut integrator = Integrator {
+        position: Meter::new(0.),
+    };
+    integrator.step(Meter::new(2.) / Second::new(1.), Second::new(3.));
+    assert_eq!(integrator.position, Meter::new(6.));
+}
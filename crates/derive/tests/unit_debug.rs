@@ -0,0 +1,66 @@
+extern crate serde_json;
+extern crate yaiouom;
+#[macro_use]
+extern crate yaiouom_derive;
+
+use yaiouom::pretty::*;
+use yaiouom::si::*;
+use yaiouom::*;
+
+#[derive(UnitDebug, UnitSerialize)]
+struct Position {
+    x: Measure<f64, Meter>,
+    y: Measure<f64, Meter>,
+}
+
+#[derive(UnitDebug, UnitSerialize)]
+struct State {
+    position: Position,
+    speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+    bounces: Measure<u32, Dimensionless>,
+}
+
+#[derive(UnitDebug)]
+struct Tagged<U: Unit>(Measure<f32, U>);
+
+fn state() -> State {
+    State {
+        position: Position {
+            x: Meter::new(1.5),
+            y: Meter::new(-2.),
+        },
+        speed: Meter::new(10.) / Second::new(1.),
+        bounces: Dimensionless::new(3),
+    }
+}
+
+#[test]
+fn derive_unit_debug() {
+    assert_eq!(
+        format!("{:?}", state()),
+        "State { position: Position { x: 1.5 m, y: -2.0 m }, speed: 10.0 m * s^-1, bounces: 3 }"
+    );
+    assert_eq!(format!("{:?}", Tagged(Kg::new(2.))), "Tagged(2.0 kg)");
+}
+
+#[test]
+fn table_of_fields() {
+    assert_eq!(
+        table(&state()),
+        "\
+field      | value | unit
+position.x | 1.5   | m
+position.y | -2.0  | m
+speed      | 10.0  | m * s^-1
+bounces    | 3     |
+"
+    );
+}
+
+#[test]
+fn derive_unit_serialize() {
+    assert_eq!(
+        serde_json::to_string(&state()).unwrap(),
+        r#"{"position":{"x":{"value":1.5,"unit":"m"},"y":{"value":-2.0,"unit":"m"}},"speed":{"value":10.0,"unit":"m * s^-1"},"bounces":{"value":3,"unit":""}}"#
+    );
+}
@@ -0,0 +1,92 @@
+//! Configuration of yaiouom-checker, read from `yaiouom.toml` at the root
+//! of the package being checked.
+//!
+//! ```toml
+//! [lints]
+//! # Flag the multiplication or division of measures by plain numbers,
+//! # e.g. `measure * 2.0`. One of "allow" (default), "warn" or "deny".
+//! scalar-arithmetic = "warn"
+//! ```
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use toml;
+
+const FILE_NAME: &'static str = "yaiouom.toml";
+
+/// The level of a lint of yaiouom-checker.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Allow = 0,
+    Warn = 1,
+    Deny = 2,
+}
+impl Level {
+    fn parse(level: &str) -> Option<Level> {
+        match level {
+            "allow" => Some(Level::Allow),
+            "warn" => Some(Level::Warn),
+            "deny" => Some(Level::Deny),
+            _ => None,
+        }
+    }
+
+    fn load(level: &AtomicUsize) -> Level {
+        match level.load(Ordering::Relaxed) {
+            0 => Level::Allow,
+            1 => Level::Warn,
+            _ => Level::Deny,
+        }
+    }
+}
+
+/// `lints.scalar-arithmetic`.
+static SCALAR_ARITHMETIC: AtomicUsize = AtomicUsize::new(Level::Allow as usize);
+
+/// How to report arithmetic between a measure and a scalar.
+pub fn scalar_arithmetic() -> Level {
+    Level::load(&SCALAR_ARITHMETIC)
+}
+
+/// Read `yaiouom.toml`, if it exists, recording its values.
+///
+/// Cargo runs the compiler with `CARGO_MANIFEST_DIR` set to the root of the
+/// package. Otherwise, the file is looked up in the current directory.
+pub fn load() -> Result<(), String> {
+    let directory = env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("."));
+    let path = directory.join(FILE_NAME);
+    let source = match fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(format!("Cannot read {}: {}", path.display(), err)),
+    };
+    let config: toml::Value = source
+        .parse()
+        .map_err(|err| format!("Cannot parse {}: {}", path.display(), err))?;
+    let lints = match config.get("lints") {
+        Some(lints) => lints
+            .as_table()
+            .ok_or_else(|| format!("{}: `lints` should be a table", path.display()))?,
+        None => return Ok(()),
+    };
+    for (name, level) in lints {
+        let level = level.as_str().and_then(Level::parse).ok_or_else(|| {
+            format!(
+                "{}: the level of lint `{}` should be one of \"allow\", \"warn\" or \"deny\"",
+                path.display(),
+                name
+            )
+        })?;
+        match name.as_str() {
+            "scalar-arithmetic" => SCALAR_ARITHMETIC.store(level as usize, Ordering::Relaxed),
+            _ => return Err(format!("{}: unknown lint `{}`", path.display(), name)),
+        }
+    }
+    Ok(())
+}
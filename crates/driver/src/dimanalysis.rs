@@ -1,6 +1,8 @@
 use rustc::hir;
 use rustc::hir::def_id::DefId;
 use rustc::hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc::lint;
+use rustc::session::Session;
 use rustc::ty;
 use rustc::ty::{Ty, TyCtxt, TypeckTables};
 
@@ -8,15 +10,37 @@ use syntax::ast;
 use syntax::attr;
 use syntax::codemap::Span;
 
+use rustc_errors::DiagnosticBuilder;
+
 use std;
 use std::collections::{HashMap, HashSet};
 
+use config;
+use dot;
+use options;
+use passes;
+use yaiouom_solve::{Term, Unification};
+
+pub const YAOIOUM_ATTR_BASE_UNIT: &'static str = "rustc_yaiouom_base_unit";
 const YAOIOUM_ATTR_CHECK_UNIFY: &'static str = "rustc_yaiouom_check_unify";
+pub const YAOIOUM_ATTR_MEASURE: &'static str = "rustc_yaiouom_check_unify_measure";
+const YAOIOUM_ATTR_REINTERPRET: &'static str = "rustc_yaiouom_reinterpret";
 const YAOIOUM_ATTR_COMBINATOR_MUL: &'static str = "rustc_yaiouom_combinator_mul";
 const YAOIOUM_ATTR_COMBINATOR_INV: &'static str = "rustc_yaiouom_combinator_inv";
 const YAOIOUM_ATTR_COMBINATOR_DIMENSIONLESS: &'static str =
     "rustc_yaiouom_combinator_dimensionless";
 
+/// Start reporting a problem with units of measure.
+///
+/// This is an error, unless lints are capped to warnings, as Cargo may do
+/// for dependencies, in which case this is a warning.
+fn struct_span_diagnostic<'a>(sess: &'a Session, span: Span, msg: &str) -> DiagnosticBuilder<'a> {
+    match sess.opts.lint_cap {
+        Some(lint::Level::Warn) => sess.struct_span_warn(span, msg),
+        _ => sess.struct_span_err(span, msg),
+    }
+}
+
 /// If this def-id is a "primary tables entry", returns `Some((body_id, decl))`
 /// with information about it's body-id and fn-decl (if any). Otherwise,
 /// returns `None`.
@@ -66,47 +90,199 @@ fn primary_body_of<'a, 'tcx>(
 
 struct UnitConstraints<'v, 'tcx: 'v> {
     tcx: TyCtxt<'v, 'tcx, 'tcx>,
-    left: HashMap<Ty<'tcx>, (HashSet<Span>, i32)>,
-    right: HashMap<Ty<'tcx>, (HashSet<Span>, i32)>,
+    unification: Unification<Ty<'tcx>, Span>,
     def_id: DefId,
     span: Span,
 }
 impl<'v, 'tcx> std::fmt::Debug for UnitConstraints<'v, 'tcx> {
     fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
-        write!(formatter, "{:?}/{:?}", self.left, self.right)
+        write!(
+            formatter,
+            "{:?}/{:?}",
+            self.unification.left, self.unification.right
+        )
     }
 }
 impl<'v, 'tcx> UnitConstraints<'v, 'tcx> {
-    fn describe(&self, left: bool) -> String {
-        let mut buf = String::new();
-        let mut first = true;
-        let table = if left { &self.left } else { &self.right };
-        for (ref ty, &(_, ref number)) in table {
-            let name = match ty.sty {
-                ty::TyAdt(ref def, _) => self.tcx.item_path_str(def.did),
-                ty::TyParam(ref param) => {
-                    let generics = self.tcx.generics_of(self.def_id);
-                    let def = generics.type_param(&param, self.tcx);
-                    self.tcx.item_path_str(def.def_id)
+    /// The fully qualified name of an atom, e.g. `yaiouom::si::Meter`.
+    fn full_name(&self, ty: Ty<'tcx>) -> String {
+        match ty.sty {
+            ty::TyAdt(ref def, _) => self.tcx.item_path_str(def.did),
+            ty::TyParam(ref param) => {
+                let generics = self.tcx.generics_of(self.def_id);
+                let def = generics.type_param(&param, self.tcx);
+                self.tcx.item_path_str(def.def_id)
+            }
+            ty::TyProjection(_) => format!("{}", ty),
+            _ => span_bug!(self.span, "yaiouom: unexpected atom `{:?}` in a unit", ty),
+        }
+    }
+
+    /// The path of an atom that resolves from any module of a crate of
+    /// edition 2015, e.g. `::yaiouom::si::Meter`.
+    fn absolute_name(&self, ty: Ty<'tcx>) -> String {
+        match ty.sty {
+            ty::TyAdt(ref def, _) => format!("::{}", self.tcx.item_path_str(def.did)),
+            // In scope in the function that calls `unify`.
+            ty::TyParam(ref param) => param.name.to_string(),
+            ty::TyProjection(_) => format!("{}", ty),
+            _ => span_bug!(self.span, "yaiouom: unexpected atom `{:?}` in a unit", ty),
+        }
+    }
+
+    /// The short name of an atom, e.g. `Meter`.
+    fn short_name(&self, ty: Ty<'tcx>) -> String {
+        match ty.sty {
+            ty::TyAdt(ref def, _) => self.tcx.item_name(def.did).to_string(),
+            ty::TyParam(ref param) => param.name.to_string(),
+            ty::TyProjection(_) => format!("{}", ty),
+            _ => span_bug!(self.span, "yaiouom: unexpected atom `{:?}` in a unit", ty),
+        }
+    }
+
+    /// The names of all the atoms of this constraint, as displayed in diagnostics.
+    ///
+    /// Use short names, unless this would make two distinct atoms look identical.
+    fn names(&self) -> HashMap<Ty<'tcx>, String> {
+        let atoms: HashSet<Ty<'tcx>> = self
+            .unification
+            .left
+            .iter()
+            .chain(self.unification.right.iter())
+            .map(|(ty, _)| *ty)
+            .collect();
+        if options::full_paths() {
+            return atoms
+                .into_iter()
+                .map(|ty| (ty, self.full_name(ty)))
+                .collect();
+        }
+        let mut atoms_by_name = HashMap::new();
+        for ty in &atoms {
+            atoms_by_name
+                .entry(self.short_name(*ty))
+                .or_insert_with(HashSet::new)
+                .insert(*ty);
+        }
+        atoms
+            .into_iter()
+            .map(|ty| {
+                let name = self.short_name(ty);
+                if atoms_by_name[&name].len() > 1 {
+                    (ty, self.full_name(ty))
+                } else {
+                    (ty, name)
                 }
-                _ => unimplemented!(),
-            };
-            let exp = if *number == 1 {
-                "".to_string()
+            })
+            .collect()
+    }
+
+    fn describe(&self, left: bool) -> String {
+        let product = if left {
+            &self.unification.left
+        } else {
+            &self.unification.right
+        };
+        let names = self.names();
+        product.describe(|ty| names[ty].clone())
+    }
+
+    /// Describe what the unit found is missing and has in excess,
+    /// e.g. `missing: s^-1; extra: Meter`.
+    ///
+    /// Atoms are sorted by name, as the solver stores them in no particular
+    /// order.
+    fn describe_diff(&self) -> String {
+        let names = self.names();
+        let diff = self.unification.diff();
+        let format = |atoms: &[(&Ty<'tcx>, i32)]| {
+            let mut atoms: Vec<(&String, i32)> = atoms
+                .iter()
+                .map(|&(ty, number)| (&names[ty], number))
+                .collect();
+            atoms.sort();
+            atoms
+                .into_iter()
+                .map(|(name, number)| {
+                    if number == 1 {
+                        name.clone()
+                    } else {
+                        format!("{}^{}", name, number)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" * ")
+        };
+        let mut parts = vec![];
+        if !diff.missing.is_empty() {
+            parts.push(format!("missing: {}", format(&diff.missing)));
+        }
+        if !diff.extra.is_empty() {
+            parts.push(format!("extra: {}", format(&diff.extra)));
+        }
+        parts.join("; ")
+    }
+
+    /// The source unit, if `left`, or the target unit otherwise, as a Rust
+    /// type with absolute paths, e.g.
+    /// `::yaiouom::Mul<::yaiouom::si::Meter, ::yaiouom::Inv<::yaiouom::si::Second>>`.
+    ///
+    /// Factors are sorted by name, so that the result does not depend on
+    /// the order in which the solver stores atoms.
+    fn unit_type(&self, left: bool) -> String {
+        let product = if left {
+            &self.unification.left
+        } else {
+            &self.unification.right
+        };
+        let mut numerator = vec![];
+        let mut denominator = vec![];
+        for (ty, number) in product.iter() {
+            let factors = if number > 0 {
+                &mut numerator
             } else {
-                format!("^{}", number)
+                &mut denominator
             };
-            buf.push_str(&format!(
-                "{mul}{name}{exp}",
-                mul = if first { "" } else { " * " },
-                name = name,
-                exp = exp
-            ));
-            if first {
-                first = false;
+            for _ in 0..number.abs() {
+                factors.push(self.absolute_name(*ty));
             }
         }
-        buf
+        fn product(mut factors: Vec<String>) -> Option<String> {
+            factors.sort();
+            let last = factors.pop()?;
+            Some(factors.into_iter().rev().fold(last, |acc, factor| {
+                format!("::yaiouom::Mul<{}, {}>", factor, acc)
+            }))
+        }
+        match (product(numerator), product(denominator)) {
+            (None, None) => "::yaiouom::Dimensionless".to_string(),
+            (Some(numerator), None) => numerator,
+            (None, Some(denominator)) => format!("::yaiouom::Inv<{}>", denominator),
+            (Some(numerator), Some(denominator)) => {
+                format!(
+                    "::yaiouom::Mul<{}, ::yaiouom::Inv<{}>>",
+                    numerator, denominator
+                )
+            }
+        }
+    }
+
+    /// Name the units of this unification, to help fixing the unit that is
+    /// wrong.
+    ///
+    /// This is not a suggestion: making the source unit the target of
+    /// `unify` would only move the error to the expected type, e.g. the
+    /// return type, which is typically where the target unit comes from.
+    fn help<'a>(&self, builder: &mut DiagnosticBuilder<'a>) {
+        builder.span_help(
+            self.span,
+            &format!(
+                "the unit of the receiver of `unify` is `{}`, but the expected type, e.g. an \
+                 annotation or the return type, requires `{}`",
+                self.unit_type(true),
+                self.unit_type(false)
+            ),
+        );
     }
 }
 
@@ -115,77 +291,88 @@ impl<'v, 'tcx> UnitConstraints<'v, 'tcx> {
         Self {
             tcx,
             def_id,
-            left: HashMap::new(),
-            right: HashMap::new(),
+            unification: Unification::new(),
             span,
         }
     }
-    fn add_one(&mut self, ty: Ty<'tcx>, span: Span, left: bool, positive: bool) {
-        let table = if left {
-            &mut self.left
-        } else {
-            &mut self.right
-        };
-        let known = table.entry(&ty).or_insert_with(|| (HashSet::new(), 0));
-        known.0.insert(span);
-        if positive {
-            known.1 += 1;
-        } else {
-            known.1 -= 1;
-        }
-    }
 
     /// Add a type involved in a unit-of-measure level unification.
-    fn add(&mut self, ty: Ty<'tcx>, left: bool, positive: bool) -> Result<(), ()> {
-        match ty.sty {
-            ty::TyAdt(def, subst) => {
-                // A constructor `Foo<A, B, C...>`.
-                //
-                // Since we are in a unit-of-measure unification, `Foo` could be
-                // `Mul`, `Inv`, `Dimensionless` (in which case they are handled
-                // as operators) or any other type (in which case they are handled
-                // as base units).
-                let span = self.tcx.def_span(def.did).clone();
-                if attr::contains_name(&self.tcx.get_attrs(def.did), YAOIOUM_ATTR_COMBINATOR_MUL) {
-                    for item in subst.types() {
-                        self.add(&item, left, positive)?;
-                    }
-                } else if attr::contains_name(
-                    &self.tcx.get_attrs(def.did),
-                    YAOIOUM_ATTR_COMBINATOR_INV,
-                ) {
-                    for item in subst.types() {
-                        self.add(&item, left, !positive)?;
-                    }
-                } else if attr::contains_name(
-                    &self.tcx.get_attrs(def.did),
-                    YAOIOUM_ATTR_COMBINATOR_DIMENSIONLESS,
-                ) {
-                    // Nothing to do.
-                } else {
-                    self.add_one(&ty, span, left, positive);
-                }
-                Ok(())
-            }
-            ty::TyParam(param) => {
-                let generics = self.tcx.generics_of(self.def_id);
-                let def = generics.type_param(&param, self.tcx);
-                let span = self.tcx.def_span(def.def_id);
-                self.add_one(&ty, span, left, positive);
-                Ok(())
+    fn add(&mut self, ty: Ty<'tcx>, left: bool, positive: bool) -> Result<(), ViewError<'tcx>> {
+        let tcx = self.tcx;
+        let def_id = self.def_id;
+        self.unification
+            .add_term(ty, left, positive, &mut |ty| view(tcx, def_id, ty))
+    }
+}
+
+/// A type that the solver cannot handle.
+enum ViewError<'tcx> {
+    /// The type contains an error, which has already been reported.
+    AlreadyReported,
+
+    /// The type cannot be used as a unit of measure, e.g. a reference
+    /// or a tuple.
+    NotAUnit(Ty<'tcx>),
+
+    /// Type inference could not determine the type, e.g. because the
+    /// result of `unify` is immediately discarded.
+    Unconstrained,
+}
+
+/// Determine how a type involved in a unit-of-measure level unification
+/// should be handled by the solver.
+fn view<'v, 'tcx>(
+    tcx: TyCtxt<'v, 'tcx, 'tcx>,
+    def_id: DefId,
+    ty: Ty<'tcx>,
+) -> Result<Term<Ty<'tcx>, Ty<'tcx>, Span>, ViewError<'tcx>> {
+    match ty.sty {
+        ty::TyAdt(def, subst) => {
+            // A constructor `Foo<A, B, C...>`.
+            //
+            // Since we are in a unit-of-measure unification, `Foo` could be
+            // `Mul`, `Inv`, `Dimensionless` (in which case they are handled
+            // as operators) or any other type (in which case they are handled
+            // as base units).
+            let attrs = tcx.get_attrs(def.did);
+            if attr::contains_name(&attrs, YAOIOUM_ATTR_COMBINATOR_MUL) {
+                Ok(Term::Mul(subst.types().collect()))
+            } else if attr::contains_name(&attrs, YAOIOUM_ATTR_COMBINATOR_INV) {
+                Ok(Term::Inv(subst.type_at(0)))
+            } else if attr::contains_name(&attrs, YAOIOUM_ATTR_COMBINATOR_DIMENSIONLESS) {
+                Ok(Term::Dimensionless)
+            } else {
+                let span = tcx.def_span(def.did).clone();
+                Ok(Term::Atom(ty, span))
             }
-            ty::TyError => {
-                // There's already a type error, skipping.
-                Err(())
+        }
+        ty::TyParam(param) => {
+            let generics = tcx.generics_of(def_id);
+            let def = generics.type_param(&param, tcx);
+            let span = tcx.def_span(def.def_id);
+            Ok(Term::Atom(ty, span))
+        }
+        ty::TyProjection(ref projection) => {
+            // An associated type `<X as Trait>::Unit`. If trait resolution
+            // can determine the actual type, proceed with it, otherwise this
+            // is an atom, just as a type parameter.
+            let param_env = tcx.param_env(def_id);
+            let normalized = tcx.normalize_erasing_regions(param_env, ty);
+            match normalized.sty {
+                ty::TyProjection(_) => {
+                    let span = tcx.def_span(projection.item_def_id);
+                    Ok(Term::Atom(normalized, span))
+                }
+                // A product with a single factor is just that factor.
+                _ => Ok(Term::Mul(vec![normalized])),
             }
-            _ => panic!("I shouldn't have received ty {:?}", ty),
         }
-    }
-
-    /// Remove everything that has multiplicity 0.
-    fn simplify(&mut self) {
-        self.left.retain(|_, v| v.1 != 0);
-        self.right.retain(|_, v| v.1 != 0);
+        ty::TyError => {
+            // There's already a type error, skipping.
+            Err(ViewError::AlreadyReported)
+        }
+        ty::TyInfer(_) => Err(ViewError::Unconstrained),
+        _ => Err(ViewError::NotAUnit(ty)),
     }
 }
 
@@ -194,25 +381,150 @@ struct GatherConstraintsVisitor<'v, 'tcx: 'v> {
     tables: &'tcx TypeckTables<'tcx>,
     constraints: Vec<UnitConstraints<'v, 'tcx>>,
     def_id: DefId,
+
+    /// All the unifications, if they are to be dumped.
+    dumps: Vec<dot::Dump<'tcx>>,
 }
 impl<'v, 'tcx> GatherConstraintsVisitor<'v, 'tcx> {
     fn add_unification(&mut self, left: Ty<'tcx>, right: Ty<'tcx>, span: Span) {
         // eprintln!("dim_analyzer: We need to unify {:?} == {:?}", left, right);
 
+        // If the call to `unify` was generated by a macro, `span` points into
+        // the definition of the macro, which is seldom where the user can fix
+        // anything. Walk the expansion info back to the outermost call site.
+        let span = span.source_callsite();
+
         let mut constraint = UnitConstraints::from(self.tcx, span, self.def_id);
-        if constraint.add(&left, true, true).is_err() {
-            // Don't pile up constraints on top of existing errors.
+        if let Err(err) = constraint.add(&left, true, true) {
+            self.report(err, span, "source");
             return;
         }
-        if constraint.add(&right, false, true).is_err() {
-            // Don't pile up constraints on top of existing errors.
+        if let Err(err) = constraint.add(&right, false, true) {
+            self.report(err, span, "target");
             return;
         }
-        constraint.simplify();
-        if constraint.left != constraint.right {
+        let unsimplified = if options::dump_constraints().is_some() {
+            Some(constraint.unification.clone())
+        } else {
+            None
+        };
+        constraint.unification.simplify();
+        let is_solved = constraint.unification.is_solved();
+        passes::check_unification(&passes::UnifySite {
+            tcx: self.tcx,
+            def_id: self.def_id,
+            span,
+            source: left,
+            target: right,
+            unification: &constraint.unification,
+            is_solved,
+        });
+        if let Some(unification) = unsimplified {
+            self.dumps.push(dot::Dump {
+                span,
+                unification,
+                is_solved,
+            });
+        }
+        if !is_solved {
             self.constraints.push(constraint)
         }
     }
+
+    /// List a call to `reinterpret_with`, from unit `source` to unit `target`.
+    fn report_reinterpret(
+        &self,
+        source: Ty<'tcx>,
+        target: Ty<'tcx>,
+        justification: &hir::Expr,
+        span: Span,
+    ) {
+        let span = span.source_callsite();
+        let literal = match justification.node {
+            hir::ExprLit(ref lit) => match lit.node {
+                ast::LitKind::Str(ref symbol, _) => Some(symbol.to_string()),
+                _ => None,
+            },
+            _ => None,
+        };
+        // E.g. a constant or an argument, show it as written.
+        let justification = literal.unwrap_or_else(|| {
+            self.tcx
+                .sess
+                .codemap()
+                .span_to_snippet(justification.span)
+                .unwrap_or_else(|_| "<non-literal justification>".to_string())
+        });
+        self.tcx.sess.span_note_without_error(
+            span,
+            &format!(
+                "Reinterpreting `{}` as `{}`: {}",
+                source, target, justification
+            ),
+        );
+    }
+
+    /// Flag the multiplication or division of a measure by a scalar, or of
+    /// a scalar by a measure, if `lints.scalar-arithmetic` is enabled.
+    fn check_scalar_arithmetic(&self, expr: &hir::Expr, lhs: &hir::Expr, rhs: &hir::Expr) {
+        let level = config::scalar_arithmetic();
+        if level == config::Level::Allow {
+            return;
+        }
+        let is_measure = |operand: &hir::Expr| match self.tables.expr_ty(operand).sty {
+            ty::TyAdt(def, _) => {
+                attr::contains_name(&self.tcx.get_attrs(def.did), YAOIOUM_ATTR_MEASURE)
+            }
+            _ => false,
+        };
+        let scalar = match (is_measure(lhs), is_measure(rhs)) {
+            (true, false) => rhs,
+            (false, true) => lhs,
+            _ => return,
+        };
+        let span = expr.span.source_callsite();
+        let msg = "Arithmetic between a measure and a scalar";
+        let mut builder = match level {
+            config::Level::Deny => struct_span_diagnostic(self.tcx.sess, span, msg),
+            _ => self.tcx.sess.struct_span_warn(span, msg),
+        };
+        builder.span_label(scalar.span, "this scalar has no unit");
+        builder.help("consider using a dimensionless measure, e.g. `Dimensionless::new(2.)`");
+        builder.note("`lints.scalar-arithmetic` is enabled in yaiouom.toml");
+        builder.emit();
+    }
+
+    /// Report an error in the `side` ("source" or "target") of a unification.
+    fn report(&self, err: ViewError<'tcx>, span: Span, side: &str) {
+        match err {
+            ViewError::AlreadyReported => {
+                // Don't pile up constraints on top of existing errors.
+            }
+            ViewError::NotAUnit(ty) => {
+                struct_span_diagnostic(
+                    self.tcx.sess,
+                    span,
+                    "This type cannot be used as a unit of measure",
+                )
+                .span_label(span, format!("`{}` is not a unit of measure", ty))
+                .emit();
+            }
+            ViewError::Unconstrained => {
+                let mut builder = struct_span_diagnostic(
+                    self.tcx.sess,
+                    span,
+                    &format!("Cannot infer the {} unit of this unification", side),
+                );
+                builder.span_label(span, format!("the {} unit is unconstrained", side));
+                if side == "target" {
+                    builder.help("consider specifying the target unit, e.g. `.unify::<Meter>()`");
+                } else {
+                    builder.help("consider annotating the type of the measure being unified");
+                }
+                builder.emit();
+            }
+        }
+    }
 }
 
 impl<'v, 'tcx> Visitor<'v> for GatherConstraintsVisitor<'v, 'tcx> {
@@ -223,11 +535,26 @@ impl<'v, 'tcx> Visitor<'v> for GatherConstraintsVisitor<'v, 'tcx> {
     fn visit_expr(&mut self, expr: &'v hir::Expr) {
         use rustc::hir::Expr_::*;
         match expr.node {
-            ExprMethodCall(_, _, _) => {
+            ExprMethodCall(_, _, ref args) => {
                 // Main interesting case: a call to `some_expr.unify()`
                 let def_id = self.tables.type_dependent_defs()[expr.hir_id].def_id();
+                let attrs = self.tcx.get_attrs(def_id);
+
+                if options::report_reinterpret()
+                    && attr::contains_name(&attrs, YAOIOUM_ATTR_REINTERPRET)
+                {
+                    // A call to `some_expr.reinterpret_with(justification)`, with
+                    // the same substs as `unify`. `args[0]` is `some_expr`.
+                    let substs = self.tables.node_substs(expr.hir_id);
+                    self.report_reinterpret(
+                        substs.type_at(1),
+                        substs.type_at(2),
+                        &args[1],
+                        expr.span,
+                    );
+                }
 
-                if attr::contains_name(&self.tcx.get_attrs(def_id), YAOIOUM_ATTR_CHECK_UNIFY) {
+                if attr::contains_name(&attrs, YAOIOUM_ATTR_CHECK_UNIFY) {
                     // Ok, this is a call to `unify`.
                     let substs = self.tables.node_substs(expr.hir_id);
 
@@ -238,6 +565,11 @@ impl<'v, 'tcx> Visitor<'v> for GatherConstraintsVisitor<'v, 'tcx> {
                     self.add_unification(substs.type_at(1), substs.type_at(2), expr.span);
                 }
             }
+            ExprBinary(op, ref lhs, ref rhs) | ExprAssignOp(op, ref lhs, ref rhs) => {
+                if op.node == hir::BiMul || op.node == hir::BiDiv {
+                    self.check_scalar_arithmetic(expr, lhs, rhs);
+                }
+            }
             // eddyb: Yoric: for everything else (i.e. calling Foo::unify(...)) you just need to look at ExprPath and check that its (unadjusted!) type is TyFnDef (which gives you the def_id)
             _ => {
                 // Nothing to do.
@@ -279,6 +611,13 @@ where
             return;
         }
 
+        // With `--cap-lints allow`, typically passed by Cargo for dependencies
+        // from a registry or a git repository, nobody can fix what we would
+        // report, so don't report anything.
+        if self.tcx.sess.opts.lint_cap == Some(lint::Level::Allow) {
+            return;
+        }
+
         // Closures' tables come from their outermost function,
         // as they are part of the same "inference environment".
         let outer_def_id = self.tcx.closure_base_def_id(self.def_id);
@@ -306,26 +645,64 @@ where
                 tables: self.tables,
                 constraints: vec![],
                 def_id: self.def_id,
+                dumps: vec![],
             };
             visitor.visit_body(body);
-            if visitor.constraints.len() != 0 {
+            if let Some(directory) = options::dump_constraints() {
+                if !visitor.dumps.is_empty() {
+                    if let Err(err) = dot::write(self.tcx, directory, self.def_id, &visitor.dumps) {
+                        self.tcx.sess.warn(&format!(
+                            "Cannot dump units of measure to {}: {}",
+                            directory.display(),
+                            err
+                        ));
+                    }
+                }
+            }
+            // A single mistake often breaks several unifications of the same
+            // function, so report the first one as an error and the others
+            // as notes of this error.
+            let mut constraints = visitor.constraints.drain(..);
+            if let Some(constraint) = constraints.next() {
                 use rustc_errors::*;
-                for constraint in visitor.constraints.drain(..) {
-                    let mut builder = self.tcx.sess.struct_span_err(
-                        constraint.span,
-                        "Cannot resolve the following units of measures:",
-                    );
-                    let mut expected = DiagnosticStyledString::new();
-                    expected.push_normal(constraint.describe(true));
+                let mut builder = struct_span_diagnostic(
+                    self.tcx.sess,
+                    constraint.span,
+                    "Cannot resolve the following units of measures:",
+                );
+                let mut expected = DiagnosticStyledString::new();
+                expected.push_normal(constraint.describe(true));
+
+                let mut found = DiagnosticStyledString::new();
+                found.push_normal(constraint.describe(false));
 
-                    let mut found = DiagnosticStyledString::new();
-                    found.push_normal(constraint.describe(false));
+                builder.note_expected_found(&"unit of measure:", expected, found);
+                builder.note(&constraint.describe_diff());
+                builder.span_label(constraint.span, "in this unification");
+                builder.span_label(span.clone(), "While examining this function");
+                constraint.help(&mut builder);
 
-                    builder.note_expected_found(&"unit of measure:", expected, found);
-                    builder.span_label(constraint.span, "in this unification");
-                    builder.span_label(span.clone(), "While examining this function");
-                    builder.emit();
+                let mut others = 0;
+                for constraint in constraints {
+                    others += 1;
+                    builder.span_note(
+                        constraint.span,
+                        &format!(
+                            "Cannot resolve this unification either: expected `{}`, found `{}` ({})",
+                            constraint.describe(true),
+                            constraint.describe(false),
+                            constraint.describe_diff()
+                        ),
+                    );
+                    constraint.help(&mut builder);
+                }
+                if others != 0 {
+                    builder.note(&format!(
+                        "{} unifications of this function cannot be resolved, possibly because of a single mistake",
+                        others + 1
+                    ));
                 }
+                builder.emit();
             }
         } else {
             return;
@@ -0,0 +1,117 @@
+//! Dumping the unifications of each function as DOT graphs, with
+//! `--yaiouom-dump-constraints=<directory>`.
+//!
+//! Each graph has one node per call to `unify` and one node per atom (base
+//! unit, type parameter, ...), with an edge from each atom to each
+//! unification that involves it, labelled with its exponents in the source
+//! and target units. Exponents are displayed before simplification, so
+//! atoms that cancel out appear with an exponent of 0. Unifications that
+//! cannot be resolved are displayed in red.
+//!
+//! Render with e.g. `dot -Tsvg -O <directory>/*.dot`.
+
+use std;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::ty::{Ty, TyCtxt};
+use syntax::codemap::Span;
+
+use yaiouom_solve::Unification;
+
+/// A call to `unify`, as recorded for a dump.
+pub struct Dump<'tcx> {
+    pub span: Span,
+
+    /// The unification, before simplification.
+    pub unification: Unification<Ty<'tcx>, Span>,
+
+    pub is_solved: bool,
+}
+
+/// Escape a string for use in a DOT label.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// The name of the file for a function, e.g. `mycrate-module.function.dot`.
+fn file_name(crate_name: &str, path: &str) -> String {
+    let name: String = format!("{}-{}", crate_name, path.replace("::", "."))
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '.' || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}.dot", name)
+}
+
+/// Write the unifications of function `def_id` to `directory`.
+pub fn write<'a, 'tcx>(
+    tcx: TyCtxt<'a, 'tcx, 'tcx>,
+    directory: &Path,
+    def_id: DefId,
+    dumps: &[Dump<'tcx>],
+) -> std::io::Result<()> {
+    let path = tcx.item_path_str(def_id);
+    let crate_name = tcx.crate_name(LOCAL_CRATE).to_string();
+    fs::create_dir_all(directory)?;
+    let mut out = File::create(directory.join(file_name(&crate_name, &path)))?;
+
+    writeln!(out, "digraph \"{}\" {{", escape(&path))?;
+    writeln!(out, "    label=\"{}\";", escape(&path))?;
+
+    let mut atoms: HashMap<Ty<'tcx>, usize> = HashMap::new();
+    for (index, dump) in dumps.iter().enumerate() {
+        let location = tcx.sess.codemap().span_to_string(dump.span);
+        writeln!(
+            out,
+            "    unify{} [shape=box, label=\"unify at {}\", color={}];",
+            index,
+            escape(&location),
+            if dump.is_solved { "black" } else { "red" }
+        )?;
+
+        // The exponents of each atom, in the source and target units.
+        let mut exponents: Vec<(Ty<'tcx>, i32, i32)> = vec![];
+        for (&ty, number) in dump.unification.left.iter() {
+            exponents.push((ty, number, dump.unification.right.exponent(&ty)));
+        }
+        for (&ty, number) in dump.unification.right.iter() {
+            if !exponents.iter().any(|&(other, _, _)| other == ty) {
+                exponents.push((ty, 0, number));
+            }
+        }
+        // Sorted by name, for the graph to be the same from one run to
+        // the next.
+        exponents.sort_by_key(|&(ty, _, _)| format!("{}", ty));
+        for (ty, source, target) in exponents {
+            let next = atoms.len();
+            let atom = *atoms.entry(ty).or_insert_with(|| next);
+            if atom == next {
+                writeln!(
+                    out,
+                    "    atom{} [label=\"{}\"];",
+                    atom,
+                    escape(&format!("{}", ty))
+                )?;
+            }
+            writeln!(
+                out,
+                "    atom{} -> unify{} [label=\"source: {}, target: {}\"{}];",
+                atom,
+                index,
+                source,
+                target,
+                if source == target { "" } else { ", color=red" }
+            )?;
+        }
+    }
+    writeln!(out, "}}")
+}
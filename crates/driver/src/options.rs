@@ -0,0 +1,87 @@
+//! Command-line options specific to yaiouom-checker.
+//!
+//! These options are removed from the command line before it is handed
+//! over to rustc.
+
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
+
+/// `--yaiouom-full-paths`: display fully qualified paths in diagnostics.
+static FULL_PATHS: AtomicBool = AtomicBool::new(false);
+
+/// `true` if diagnostics should display fully qualified paths.
+pub fn full_paths() -> bool {
+    FULL_PATHS.load(Ordering::Relaxed)
+}
+
+/// `--yaiouom-report-reinterpret`: list the calls to `reinterpret_with`.
+static REPORT_REINTERPRET: AtomicBool = AtomicBool::new(false);
+
+/// `true` if the calls to `reinterpret_with` should be listed, along with
+/// their justifications.
+pub fn report_reinterpret() -> bool {
+    REPORT_REINTERPRET.load(Ordering::Relaxed)
+}
+
+/// `--yaiouom-report-unused-units`: list the base units that are never used.
+static REPORT_UNUSED_UNITS: AtomicBool = AtomicBool::new(false);
+
+/// `true` if the base units of the crate that are never used in a measure,
+/// and are not exported, should be listed.
+pub fn report_unused_units() -> bool {
+    REPORT_UNUSED_UNITS.load(Ordering::Relaxed)
+}
+
+/// `--yaiouom-dump-constraints=<directory>`: write the unifications of each
+/// function to `directory`, as DOT graphs.
+///
+/// The path is leaked, as it lives as long as the process.
+static DUMP_CONSTRAINTS: AtomicPtr<PathBuf> = AtomicPtr::new(ptr::null_mut());
+
+/// The directory to which unifications should be dumped, if any.
+pub fn dump_constraints() -> Option<&'static PathBuf> {
+    let path = DUMP_CONSTRAINTS.load(Ordering::Acquire);
+    // Safe, as the pointer is either null or leaked by `extract`.
+    unsafe { path.as_ref() }
+}
+
+/// Remove yaiouom-specific options from `args`, recording their values.
+pub fn extract(args: &mut Vec<String>) {
+    args.retain(|arg| match arg.as_str() {
+        "--yaiouom-full-paths" => {
+            FULL_PATHS.store(true, Ordering::Relaxed);
+            false
+        }
+        "--yaiouom-report-reinterpret" => {
+            REPORT_REINTERPRET.store(true, Ordering::Relaxed);
+            false
+        }
+        "--yaiouom-report-unused-units" => {
+            REPORT_UNUSED_UNITS.store(true, Ordering::Relaxed);
+            false
+        }
+        arg if arg.starts_with("--yaiouom-dump-constraints=") => {
+            let path = PathBuf::from(&arg["--yaiouom-dump-constraints=".len()..]);
+            DUMP_CONSTRAINTS.store(Box::into_raw(Box::new(path)), Ordering::Release);
+            false
+        }
+        _ => true,
+    });
+}
+
+/// If `args` build crate `yaiouom`, make sure that its feature `checker` is
+/// enabled, as we need the attributes it defines.
+pub fn enable_checker(args: &mut Vec<String>) {
+    const FEATURE: &'static str = "feature=\"checker\"";
+    let is_yaiouom = args
+        .windows(2)
+        .any(|pair| pair[0] == "--crate-name" && pair[1] == "yaiouom");
+    let is_enabled = args
+        .windows(2)
+        .any(|pair| pair[0] == "--cfg" && pair[1] == FEATURE);
+    if is_yaiouom && !is_enabled {
+        args.push("--cfg".to_owned());
+        args.push(FEATURE.to_owned());
+    }
+}
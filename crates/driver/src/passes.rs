@@ -0,0 +1,84 @@
+//! Passes provided by other tools, which receive the unifications that
+//! the checker analyzes.
+//!
+//! ```ignore
+//! extern crate rustc;
+//! extern crate yaiouom_checker;
+//!
+//! use yaiouom_checker::passes::*;
+//!
+//! /// Warn about every unification that involves more than 4 atoms.
+//! struct Complexity;
+//! impl UnitPass for Complexity {
+//!     fn check_unification<'a, 'tcx>(&self, site: &UnifySite<'a, 'tcx>) {
+//!         if site.unification.left.iter().count() > 4 {
+//!             site.tcx.sess.span_warn(site.span, "This unit is too complex");
+//!         }
+//!     }
+//! }
+//!
+//! fn main() {
+//!     yaiouom_checker::run(vec![Box::new(Complexity)]);
+//! }
+//! ```
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use rustc::hir::def_id::DefId;
+use rustc::ty::{Ty, TyCtxt};
+use syntax::codemap::Span;
+
+use yaiouom_solve::Unification;
+
+/// A call to `unify`, once its unification has been simplified.
+pub struct UnifySite<'a, 'tcx: 'a> {
+    pub tcx: TyCtxt<'a, 'tcx, 'tcx>,
+
+    /// The function that contains the call.
+    pub def_id: DefId,
+
+    /// The call, or the outermost macro call that expands to it.
+    pub span: Span,
+
+    /// The source unit, i.e. `U` in `Measure<T, U>::unify::<V>`.
+    pub source: Ty<'tcx>,
+
+    /// The target unit, i.e. `V` in `Measure<T, U>::unify::<V>`.
+    pub target: Ty<'tcx>,
+
+    /// The source unit on the left and the target unit on the right.
+    pub unification: &'a Unification<Ty<'tcx>, Span>,
+
+    /// `true` unless the checker reports an error for this unification.
+    pub is_solved: bool,
+}
+
+/// A pass that receives each call to `unify` and may emit its own
+/// diagnostics, through `site.tcx.sess`.
+///
+/// Calls whose units cannot be analyzed, e.g. because they cannot be
+/// inferred, are reported by the checker and not passed to passes.
+pub trait UnitPass: Send + Sync {
+    fn check_unification<'a, 'tcx>(&self, site: &UnifySite<'a, 'tcx>);
+}
+
+/// The passes given to `run`.
+///
+/// The vector is leaked, as it lives as long as the process.
+static PASSES: AtomicPtr<Vec<Box<UnitPass>>> = AtomicPtr::new(ptr::null_mut());
+
+pub(crate) fn register(passes: Vec<Box<UnitPass>>) {
+    PASSES.store(Box::into_raw(Box::new(passes)), Ordering::Release);
+}
+
+/// Hand `site` over to all passes.
+pub(crate) fn check_unification<'a, 'tcx>(site: &UnifySite<'a, 'tcx>) {
+    let passes = PASSES.load(Ordering::Acquire);
+    // Safe, as the pointer is either null or leaked by `register`.
+    if let Some(passes) = unsafe { passes.as_ref() } {
+        for pass in passes {
+            pass.check_unification(site);
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Listing the base units that are never used in a measure, with
+//! `--yaiouom-report-unused-units`.
+//!
+//! A base unit is used if it appears in the unit of a `Measure`, either in
+//! the type of an expression or pattern of the crate, or in the type of a
+//! field of a struct, enum or union of the crate.
+//!
+//! Each crate is checked on its own, so a base unit that other crates may
+//! use, i.e. one that is reachable from outside its crate, is never listed:
+//! a note only tells how many such units were skipped.
+
+use std::collections::HashSet;
+
+use rustc::hir;
+use rustc::hir::def_id::{DefId, LOCAL_CRATE};
+use rustc::ty::{self, Ty, TyCtxt};
+use syntax::attr;
+
+use dimanalysis::{YAOIOUM_ATTR_BASE_UNIT, YAOIOUM_ATTR_MEASURE};
+
+/// Record the base units that appear in the unit of a measure in `ty`.
+fn gather<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>, ty: Ty<'tcx>, used: &mut HashSet<DefId>) {
+    for ty in ty.walk() {
+        if let ty::TyAdt(def, substs) = ty.sty {
+            if attr::contains_name(&tcx.get_attrs(def.did), YAOIOUM_ATTR_MEASURE) {
+                // `Measure<T, U>`, with `U` made of base units and combinators.
+                for atom in substs.type_at(1).walk() {
+                    if let ty::TyAdt(def, _) = atom.sty {
+                        used.insert(def.did);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// List the base units of the current crate that are never used, and that
+/// other crates cannot use.
+pub fn report<'a, 'tcx>(tcx: TyCtxt<'a, 'tcx, 'tcx>) {
+    let mut used = HashSet::new();
+    for def_id in tcx.body_owners() {
+        let tables = tcx.typeck_tables_of(def_id);
+        for (_, &ty) in tables.node_types().iter() {
+            gather(tcx, ty, &mut used);
+        }
+    }
+
+    let mut units = vec![];
+    for item in tcx.hir.krate().items.values() {
+        let def_id = tcx.hir.local_def_id(item.id);
+        match item.node {
+            hir::ItemStruct(..) | hir::ItemEnum(..) | hir::ItemUnion(..) => {
+                for field in tcx.adt_def(def_id).all_fields() {
+                    gather(tcx, tcx.type_of(field.did), &mut used);
+                }
+            }
+            hir::ItemImpl(..) => {
+                // An implementation of `BaseUnit` for a type of this crate.
+                if let Some(trait_ref) = tcx.impl_trait_ref(def_id) {
+                    let attrs = tcx.get_attrs(trait_ref.def_id);
+                    if !attr::contains_name(&attrs, YAOIOUM_ATTR_BASE_UNIT) {
+                        continue;
+                    }
+                    if let ty::TyAdt(def, _) = trait_ref.self_ty().sty {
+                        if def.did.is_local() {
+                            units.push(def.did);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let access_levels = tcx.privacy_access_levels(LOCAL_CRATE);
+    let mut exported = 0;
+    for unit in units {
+        let is_exported = match tcx.hir.as_local_node_id(unit) {
+            Some(node_id) => access_levels.is_exported(node_id),
+            None => false,
+        };
+        if is_exported {
+            // Possibly used by other crates, which we do not see.
+            exported += 1;
+        } else if !used.contains(&unit) {
+            tcx.sess.span_note_without_error(
+                tcx.def_span(unit),
+                &format!(
+                    "Base unit `{}` is never used in a measure",
+                    tcx.item_path_str(unit)
+                ),
+            );
+        }
+    }
+    if exported != 0 {
+        tcx.sess.note_without_error(&format!(
+            "{} exported base units are not listed, as other crates may use them",
+            exported
+        ));
+    }
+}
@@ -0,0 +1,414 @@
+//! Unification of units of measure.
+//!
+//! Units of measure form an abelian group: multiplication is associative
+//! and commutative, `Dimensionless` is the neutral element and `Inv<A>`
+//! is the inverse of `A`. Consequently, any unit can be represented as
+//! a product of atoms (base units, type parameters, ...), each of them
+//! raised to an integer exponent, and two units are equal if and only
+//! if they have the same representation.
+//!
+//! This crate implements this representation, independently from the
+//! representation of atoms. The rustc driver uses it with `Ty<'tcx>`,
+//! but any type that implements `Eq + Hash` will do.
+//!
+//! Similarly, units are walked through `Term`, so that the handling of
+//! `Mul`, `Inv` and `Dimensionless` does not depend on how the caller
+//! represents units.
+
+use std::cmp;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// The shape of a unit of measure, as seen by the solver.
+///
+/// `T` is the caller's representation of units, `A` the representation
+/// of atoms and `O` the representation of origins.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Term<T, A, O> {
+    /// The product of all these units (e.g. `Mul<A, B>`).
+    Mul(Vec<T>),
+
+    /// The inverse of a unit (e.g. `Inv<A>`).
+    Inv(T),
+
+    /// The neutral element.
+    Dimensionless,
+
+    /// Anything else, e.g. a base unit or a type parameter, along with
+    /// the place it comes from.
+    Atom(A, O),
+}
+
+/// A product of atoms, each of them raised to an exponent.
+///
+/// Each atom also remembers the origins (typically, source spans) at
+/// which it was encountered, for the sake of error reporting.
+///
+/// Atoms are stored in a vector sorted by key, a hash of the atom, so
+/// that comparing two products is a merge of both vectors. Atoms rarely
+/// share a key, but when they do, they are told apart with `Eq`.
+#[derive(Clone, Debug)]
+pub struct Product<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    atoms: Vec<Entry<A, O>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<A, O> {
+    key: u64,
+    atom: A,
+    exponent: i32,
+    origins: HashSet<O>,
+}
+
+/// A multiplicative hash, in the style of rustc's `FxHasher`, which is
+/// faster than the default hasher on pointers, such as interned types,
+/// and on strings, and does not need to resist collisions.
+struct KeyHasher(u64);
+impl KeyHasher {
+    fn mix(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+}
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+    fn write(&mut self, bytes: &[u8]) {
+        let mut words = bytes.chunks_exact(8);
+        for word in &mut words {
+            let mut buf = [0; 8];
+            buf.copy_from_slice(word);
+            self.mix(u64::from_le_bytes(buf));
+        }
+        for byte in words.remainder() {
+            self.mix(u64::from(*byte));
+        }
+    }
+    fn write_u64(&mut self, n: u64) {
+        self.mix(n);
+    }
+    fn write_usize(&mut self, n: usize) {
+        self.mix(n as u64);
+    }
+}
+
+fn key<A: Hash>(atom: &A) -> u64 {
+    let mut hasher = KeyHasher(0);
+    atom.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<A, O> Product<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    /// Create an empty (i.e. dimensionless) product.
+    pub fn new() -> Self {
+        Self { atoms: Vec::new() }
+    }
+
+    /// The index of `atom` if it appears in this product, otherwise the
+    /// index at which it should be inserted.
+    fn find(&self, key: u64, atom: &A) -> Result<usize, usize> {
+        let start = self.atoms.partition_point(|entry| entry.key < key);
+        let mut index = start;
+        while index < self.atoms.len() && self.atoms[index].key == key {
+            if self.atoms[index].atom == *atom {
+                return Ok(index);
+            }
+            index += 1;
+        }
+        Err(index)
+    }
+
+    /// Multiply this product by `atom` (if `positive` is `true`)
+    /// or by its inverse (if `positive` is `false`).
+    pub fn add(&mut self, atom: A, origin: O, positive: bool) {
+        let key = key(&atom);
+        let index = match self.find(key, &atom) {
+            Ok(index) => index,
+            Err(index) => {
+                self.atoms.insert(
+                    index,
+                    Entry {
+                        key,
+                        atom,
+                        exponent: 0,
+                        origins: HashSet::new(),
+                    },
+                );
+                index
+            }
+        };
+        let known = &mut self.atoms[index];
+        known.origins.insert(origin);
+        if positive {
+            known.exponent += 1;
+        } else {
+            known.exponent -= 1;
+        }
+    }
+
+    /// Remove everything that has multiplicity 0.
+    pub fn simplify(&mut self) {
+        self.atoms.retain(|entry| entry.exponent != 0);
+    }
+
+    fn get(&self, atom: &A) -> Option<&Entry<A, O>> {
+        self.find(key(atom), atom)
+            .ok()
+            .map(|index| &self.atoms[index])
+    }
+
+    /// The exponent of `atom` in this product, `0` if it does not appear.
+    pub fn exponent(&self, atom: &A) -> i32 {
+        self.get(atom).map(|entry| entry.exponent).unwrap_or(0)
+    }
+
+    /// The places at which `atom` was encountered, if any.
+    pub fn origins(&self, atom: &A) -> Option<&HashSet<O>> {
+        self.get(atom).map(|entry| &entry.origins)
+    }
+
+    /// Iterate through the atoms of this product and their exponents.
+    ///
+    /// Atoms may appear with an exponent of 0 unless the product has been
+    /// `simplify`-ed. The order of atoms is not specified.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = (&'a A, i32)> + 'a {
+        self.atoms.iter().map(|entry| (&entry.atom, entry.exponent))
+    }
+
+    /// `true` if all exponents are 0.
+    pub fn is_dimensionless(&self) -> bool {
+        self.atoms.iter().all(|entry| entry.exponent == 0)
+    }
+
+    /// Call `f(atom, self.exponent(atom), other.exponent(atom))` once for
+    /// each atom that appears in either product, by merging both vectors.
+    fn merge<'a, F>(&'a self, other: &'a Self, mut f: F)
+    where
+        F: FnMut(&'a A, i32, i32),
+    {
+        let (left, right) = (&self.atoms, &other.atoms);
+        let (mut i, mut j) = (0, 0);
+        while i < left.len() || j < right.len() {
+            let key = match (left.get(i), right.get(j)) {
+                (Some(l), Some(r)) => cmp::min(l.key, r.key),
+                (Some(l), None) => l.key,
+                (None, Some(r)) => r.key,
+                (None, None) => unreachable!(),
+            };
+            // The atoms with this key, usually at most one on each side.
+            let left_end = i + left[i..].iter().take_while(|l| l.key == key).count();
+            let right_end = j + right[j..].iter().take_while(|r| r.key == key).count();
+            let (left_run, right_run) = (&left[i..left_end], &right[j..right_end]);
+            for l in left_run {
+                let number = right_run
+                    .iter()
+                    .find(|r| r.atom == l.atom)
+                    .map(|r| r.exponent)
+                    .unwrap_or(0);
+                f(&l.atom, l.exponent, number);
+            }
+            for r in right_run {
+                if !left_run.iter().any(|l| l.atom == r.atom) {
+                    f(&r.atom, 0, r.exponent);
+                }
+            }
+            i = left_end;
+            j = right_end;
+        }
+    }
+
+    /// Display this product, e.g. `m * s^-1`, using `name` to
+    /// display individual atoms.
+    ///
+    /// Atoms with an exponent of 0 are skipped. Atoms are sorted by name,
+    /// so that the result does not depend on how atoms are stored. A
+    /// dimensionless product returns `""`.
+    pub fn describe<F>(&self, mut name: F) -> String
+    where
+        F: FnMut(&A) -> String,
+    {
+        let mut atoms: Vec<(String, i32)> = self
+            .iter()
+            .filter(|&(_, number)| number != 0)
+            .map(|(atom, number)| (name(atom), number))
+            .collect();
+        atoms.sort();
+        atoms
+            .into_iter()
+            .map(|(name, number)| {
+                if number == 1 {
+                    name
+                } else {
+                    format!("{}^{}", name, number)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" * ")
+    }
+}
+
+impl<A, O> Default for Product<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Two products are equal if their atoms have the same exponents,
+/// regardless of their origins.
+impl<A, O> PartialEq for Product<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let mut equal = true;
+        self.merge(other, |_, left, right| equal &= left == right);
+        equal
+    }
+}
+impl<A, O> Eq for Product<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+}
+
+/// A unit-of-measure level unification `left == right`.
+#[derive(Clone, Debug)]
+pub struct Unification<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    pub left: Product<A, O>,
+    pub right: Product<A, O>,
+}
+
+impl<A, O> Unification<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self {
+            left: Product::new(),
+            right: Product::new(),
+        }
+    }
+
+    /// Add an atom to the left (if `left` is `true`) or right (otherwise)
+    /// side of the unification, either in positive or negative position.
+    pub fn add(&mut self, atom: A, origin: O, left: bool, positive: bool) {
+        let side = if left {
+            &mut self.left
+        } else {
+            &mut self.right
+        };
+        side.add(atom, origin, positive);
+    }
+
+    /// Add a unit to the left (if `left` is `true`) or right (otherwise)
+    /// side of the unification, either in positive or negative position.
+    ///
+    /// Function `view` is used to determine the shape of `term` and, recursively,
+    /// of its subterms. The first error it returns stops the walk.
+    pub fn add_term<T, E, F>(
+        &mut self,
+        term: T,
+        left: bool,
+        positive: bool,
+        view: &mut F,
+    ) -> Result<(), E>
+    where
+        F: FnMut(T) -> Result<Term<T, A, O>, E>,
+    {
+        match view(term)? {
+            Term::Mul(items) => {
+                for item in items {
+                    self.add_term(item, left, positive, view)?;
+                }
+            }
+            Term::Inv(item) => {
+                self.add_term(item, left, !positive, view)?;
+            }
+            Term::Dimensionless => {
+                // Nothing to do.
+            }
+            Term::Atom(atom, origin) => {
+                self.add(atom, origin, left, positive);
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove everything that has multiplicity 0.
+    pub fn simplify(&mut self) {
+        self.left.simplify();
+        self.right.simplify();
+    }
+
+    /// `true` if both sides represent the same unit.
+    pub fn is_solved(&self) -> bool {
+        self.left == self.right
+    }
+
+    /// Describe what the right side is missing and has in excess
+    /// compared to the left side.
+    ///
+    /// The order of atoms is not specified.
+    pub fn diff<'a>(&'a self) -> Diff<'a, A> {
+        let mut diff = Diff {
+            missing: vec![],
+            extra: vec![],
+        };
+        self.left.merge(&self.right, |atom, left, right| {
+            if left != right {
+                if left != 0 {
+                    diff.missing.push((atom, left));
+                }
+                if right != 0 {
+                    diff.extra.push((atom, right));
+                }
+            }
+        });
+        diff
+    }
+}
+
+/// The difference between both sides of a unification, as computed by
+/// `Unification::diff`.
+///
+/// An atom that appears on both sides with distinct exponents is both
+/// missing and extra.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Diff<'a, A: 'a> {
+    /// Atoms of the left side that do not appear with the same exponent
+    /// on the right side.
+    pub missing: Vec<(&'a A, i32)>,
+
+    /// Atoms of the right side that do not appear with the same exponent
+    /// on the left side.
+    pub extra: Vec<(&'a A, i32)>,
+}
+
+impl<A, O> Default for Unification<A, O>
+where
+    A: Eq + Hash,
+    O: Eq + Hash,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
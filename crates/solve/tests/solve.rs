@@ -0,0 +1,257 @@
+extern crate yaiouom_solve;
+
+use yaiouom_solve::*;
+
+#[test]
+fn same_atoms_in_any_order() {
+    // m * s == s * m
+    let mut unification = Unification::new();
+    unification.add("m", 1, true, true);
+    unification.add("s", 2, true, true);
+    unification.add("s", 3, false, true);
+    unification.add("m", 4, false, true);
+    unification.simplify();
+    assert!(unification.is_solved());
+}
+
+#[test]
+fn cancellation() {
+    // m * s * s^-1 == m
+    let mut unification = Unification::new();
+    unification.add("m", 1, true, true);
+    unification.add("s", 2, true, true);
+    unification.add("s", 3, true, false);
+    unification.add("m", 4, false, true);
+    assert_eq!(unification.left.exponent(&"s"), 0);
+    unification.simplify();
+    assert!(unification.is_solved());
+    assert_eq!(unification.left.origins(&"s"), None);
+}
+
+#[test]
+fn mismatch() {
+    // km * s^-1 != m * s^-1
+    let mut unification = Unification::new();
+    unification.add("km", 1, true, true);
+    unification.add("s", 2, true, false);
+    unification.add("m", 3, false, true);
+    unification.add("s", 4, false, false);
+    unification.simplify();
+    assert!(!unification.is_solved());
+    assert_eq!(unification.left.describe(|x| x.to_string()), "km * s^-1");
+}
+
+#[test]
+fn origins_do_not_matter() {
+    let mut left: Product<&str, u32> = Product::new();
+    left.add("m", 1, true);
+    let mut right = Product::new();
+    right.add("m", 2, true);
+    assert!(left == right);
+    assert_eq!(left.origins(&"m").unwrap().len(), 1);
+}
+
+#[test]
+fn describe() {
+    let mut product: Product<&str, ()> = Product::new();
+    assert_eq!(product.describe(|x| x.to_string()), "");
+    assert!(product.is_dimensionless());
+
+    product.add("s", (), false);
+    product.add("s", (), false);
+    assert_eq!(product.describe(|x| x.to_string()), "s^-2");
+    assert!(!product.is_dimensionless());
+
+    product.add("m", (), true);
+    assert_eq!(product.describe(|x| x.to_string()), "m * s^-2");
+
+    // Regardless of the order in which atoms were added.
+    product.add("a", (), true);
+    assert_eq!(product.describe(|x| x.to_string()), "a * m * s^-2");
+}
+
+/// A toy representation of units, standing in for `Ty<'tcx>`.
+#[derive(Clone)]
+enum Unit {
+    Base(&'static str),
+    Param(&'static str),
+    Mul(Box<Unit>, Box<Unit>),
+    Inv(Box<Unit>),
+    Dimensionless,
+    Error,
+}
+use Unit::*;
+
+fn mul(a: Unit, b: Unit) -> Unit {
+    Mul(Box::new(a), Box::new(b))
+}
+fn inv(a: Unit) -> Unit {
+    Inv(Box::new(a))
+}
+
+/// Atoms are either base units or type parameters, which are handled
+/// identically by the solver, but must not be confused with each other.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum Atom {
+    Base(&'static str),
+    Param(&'static str),
+}
+
+fn view(unit: Unit) -> Result<Term<Unit, Atom, ()>, ()> {
+    match unit {
+        Base(name) => Ok(Term::Atom(Atom::Base(name), ())),
+        Param(name) => Ok(Term::Atom(Atom::Param(name), ())),
+        Mul(a, b) => Ok(Term::Mul(vec![*a, *b])),
+        Inv(a) => Ok(Term::Inv(*a)),
+        Dimensionless => Ok(Term::Dimensionless),
+        Error => Err(()),
+    }
+}
+
+fn unify(left: Unit, right: Unit) -> Result<bool, ()> {
+    let mut unification = Unification::new();
+    unification.add_term(left, true, true, &mut view)?;
+    unification.add_term(right, false, true, &mut view)?;
+    unification.simplify();
+    Ok(unification.is_solved())
+}
+
+#[test]
+fn term_commutativity() {
+    let m = || Base("m");
+    let s = || Base("s");
+    assert_eq!(unify(mul(m(), inv(s())), mul(inv(s()), m())), Ok(true));
+    assert_eq!(unify(mul(m(), inv(s())), mul(s(), inv(m()))), Ok(false));
+}
+
+#[test]
+fn term_cancellation() {
+    let m = || Base("m");
+    let s = || Base("s");
+    // m * s * s^-1 == m
+    assert_eq!(unify(mul(m(), mul(s(), inv(s()))), m()), Ok(true));
+    // m * m^-1 == Dimensionless
+    assert_eq!(unify(mul(m(), inv(m())), Dimensionless), Ok(true));
+    // m * m^-1 != m
+    assert_eq!(unify(mul(m(), inv(m())), m()), Ok(false));
+}
+
+#[test]
+fn term_generics() {
+    let a = || Param("A");
+    let b = || Param("B");
+    // A * B^-1 * B == A
+    assert_eq!(unify(mul(mul(a(), inv(b())), b()), a()), Ok(true));
+    // A != B, even though both are parameters.
+    assert_eq!(unify(a(), b()), Ok(false));
+    // A type parameter is not a base unit of the same name.
+    assert_eq!(unify(a(), Base("A")), Ok(false));
+    // W * m * W^-1 == m
+    let w = || Param("W");
+    assert_eq!(unify(mul(w(), mul(Base("m"), inv(w()))), Base("m")), Ok(true));
+}
+
+#[test]
+fn term_nested_combinators() {
+    let m = || Base("m");
+    let s = || Base("s");
+    // (m^-1)^-1 == m
+    assert_eq!(unify(inv(inv(m())), m()), Ok(true));
+    // (m * s)^-1 == m^-1 * s^-1
+    assert_eq!(unify(inv(mul(m(), s())), mul(inv(s()), inv(m()))), Ok(true));
+    // ((m * m) * m) == (m * (m * m))
+    assert_eq!(
+        unify(mul(mul(m(), m()), m()), mul(m(), mul(m(), m()))),
+        Ok(true)
+    );
+    // m * m != m
+    assert_eq!(unify(mul(m(), m()), m()), Ok(false));
+}
+
+#[test]
+fn term_dimensionless() {
+    let m = || Base("m");
+    assert_eq!(unify(Dimensionless, Dimensionless), Ok(true));
+    assert_eq!(unify(inv(Dimensionless), Dimensionless), Ok(true));
+    assert_eq!(unify(mul(Dimensionless, m()), m()), Ok(true));
+    assert_eq!(unify(mul(m(), inv(Dimensionless)), m()), Ok(true));
+    assert_eq!(unify(Dimensionless, m()), Ok(false));
+}
+
+#[test]
+fn term_errors() {
+    assert_eq!(unify(mul(Base("m"), Error), Base("m")), Err(()));
+    assert_eq!(unify(Base("m"), inv(Error)), Err(()));
+}
+
+#[test]
+fn term_exponents() {
+    let mut unification = Unification::new();
+    let s = || Base("s");
+    let term = mul(Base("m"), inv(mul(s(), s())));
+    assert_eq!(unification.add_term(term, true, true, &mut view), Ok(()));
+    assert_eq!(unification.left.exponent(&Atom::Base("m")), 1);
+    assert_eq!(unification.left.exponent(&Atom::Base("s")), -2);
+    assert_eq!(unification.right.exponent(&Atom::Base("m")), 0);
+
+    // Same term, on the right side, in negative position.
+    let term = mul(Base("m"), inv(mul(s(), s())));
+    assert_eq!(unification.add_term(term, false, false, &mut view), Ok(()));
+    assert_eq!(unification.right.exponent(&Atom::Base("m")), -1);
+    assert_eq!(unification.right.exponent(&Atom::Base("s")), 2);
+}
+
+#[test]
+fn diff() {
+    // expected m * s^-1, found m^2
+    let mut unification: Unification<&str, ()> = Unification::new();
+    unification.add("m", (), true, true);
+    unification.add("s", (), true, false);
+    unification.add("m", (), false, true);
+    unification.add("m", (), false, true);
+    unification.simplify();
+    let mut diff = unification.diff();
+    diff.missing.sort();
+    assert_eq!(diff.missing, vec![(&"m", 1), (&"s", -1)]);
+    assert_eq!(diff.extra, vec![(&"m", 2)]);
+
+    let mut unification: Unification<&str, ()> = Unification::new();
+    unification.add("m", (), true, true);
+    unification.add("m", (), false, true);
+    let diff = unification.diff();
+    assert!(diff.missing.is_empty());
+    assert!(diff.extra.is_empty());
+}
+
+/// An atom whose hash does not distinguish its values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Colliding(u32);
+impl std::hash::Hash for Colliding {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+#[test]
+fn colliding_atoms() {
+    // a * b * c^-1 == b * a * c^-1, != a * c^-1
+    let mut unification = Unification::new();
+    for &(atom, left, positive) in &[
+        (0, true, true),
+        (1, true, true),
+        (2, true, false),
+        (1, false, true),
+        (2, false, false),
+        (0, false, true),
+    ] {
+        unification.add(Colliding(atom), (), left, positive);
+    }
+    assert!(unification.is_solved());
+    assert_eq!(unification.left.exponent(&Colliding(2)), -1);
+    assert_eq!(unification.left.exponent(&Colliding(3)), 0);
+
+    unification.add(Colliding(1), (), false, false);
+    unification.simplify();
+    assert!(!unification.is_solved());
+    let diff = unification.diff();
+    assert_eq!(diff.missing, vec![(&Colliding(1), 1)]);
+    assert!(diff.extra.is_empty());
+}
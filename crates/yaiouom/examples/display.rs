@@ -0,0 +1,61 @@
+//! A benchmark of displaying measures, as in logging-heavy code, through
+//! `RuntimeUnit` and through `Unit::display`, which does not allocate.
+//!
+//! Run with `cargo run --release --example display`.
+
+extern crate yaiouom;
+
+use std::hint::black_box;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+/// Number of log lines written by each approach.
+const ROUNDS: usize = 1_000_000;
+
+type Speed = Mul<Meter, Inv<Second>>;
+
+/// Write `ROUNDS` log lines with `log` and return the time it took.
+fn bench<F: FnMut(&mut Vec<u8>, f64)>(mut log: F) -> Duration {
+    // A preallocated line, so that only formatting may allocate.
+    let mut line = Vec::with_capacity(256);
+    let start = Instant::now();
+    for round in 0..ROUNDS {
+        line.clear();
+        log(&mut line, black_box(round as f64));
+        black_box(&line);
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let runtime = bench(|line, value| {
+        let distance: Measure<f64, Meter> = Measure::new(value);
+        let speed: Measure<f64, Speed> = Measure::new(value);
+        writeln!(
+            line,
+            "distance: {} {}, speed: {} {}",
+            distance.as_ref(),
+            distance.as_runtime(),
+            speed.as_ref(),
+            speed.as_runtime()
+        )
+        .unwrap();
+    });
+    let display = bench(|line, value| {
+        let distance: Measure<f64, Meter> = Measure::new(value);
+        let speed: Measure<f64, Speed> = Measure::new(value);
+        writeln!(line, "distance: {}, speed: {}", distance, speed).unwrap();
+    });
+    for &(name, elapsed) in &[("RuntimeUnit", runtime), ("Unit::display", display)] {
+        println!(
+            "{}: {} log lines in {:?} ({:?} per line)",
+            name,
+            ROUNDS,
+            elapsed,
+            elapsed / ROUNDS as u32
+        );
+    }
+}
@@ -0,0 +1,170 @@
+//! Recording all the changes of unit of a program, e.g. for regulatory audits.
+//!
+//! With feature `audit`, each call to `unify`, `checked_unify`, `try_unify`
+//! and `reinterpret_with` is reported to the hook installed with `set_hook`,
+//! if any. Without feature `audit`, this module does not exist and these calls
+//! cost nothing more than usual.
+//!
+//! ```
+//! use std::sync::{Arc, Mutex};
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::audit::*;
+//!
+//! let log = Arc::new(Mutex::new(vec![]));
+//! let hook_log = log.clone();
+//! set_hook(Box::new(move |conversion: &Conversion| {
+//!     hook_log.lock().unwrap().push(conversion.to_string());
+//! }));
+//!
+//! let speed = Meter::new(10.) / Second::new(2.);
+//! let _: Measure<f64, Mul<Meter, Inv<Second>>> = speed.unify();
+//! let _ = take_hook();
+//!
+//! let log = log.lock().unwrap();
+//! assert_eq!(log.len(), 1);
+//! assert!(log[0].starts_with("unify of `f64` from `m * s^-1` to `m * s^-1` at "));
+//! ```
+//!
+//! Changes of unit do not change values, so hooks do not receive them: they
+//! would require a `Debug` bound on all measures. Record values along with
+//! the location of the conversion if needed.
+//!
+//! The hook is global, i.e. shared by all threads.
+
+use std;
+use std::panic::Location;
+use std::sync::RwLock;
+
+use unit::*;
+
+/// The function that changed the unit of a measure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConversionKind {
+    /// `Measure::unify`, `Measure::checked_unify` or `Measure::unify_trusted`.
+    Unify,
+
+    /// `Measure::try_unify`.
+    TryUnify,
+
+    /// `Measure::reinterpret_with`, with its justification.
+    Reinterpret(&'static str),
+}
+
+/// A change of the unit of a measure, as reported to an `AuditHook`.
+#[derive(Clone, Debug)]
+pub struct Conversion {
+    kind: ConversionKind,
+    source: RuntimeUnit,
+    target: RuntimeUnit,
+    value_type: &'static str,
+    location: &'static Location<'static>,
+}
+impl Conversion {
+    /// The function that changed the unit.
+    pub fn kind(&self) -> ConversionKind {
+        self.kind
+    }
+
+    /// The unit before the change.
+    pub fn source(&self) -> &RuntimeUnit {
+        &self.source
+    }
+
+    /// The unit after the change, or the unit requested, if `try_unify`
+    /// failed or if `unify` is about to panic.
+    pub fn target(&self) -> &RuntimeUnit {
+        &self.target
+    }
+
+    /// `true` if the source and target units are the same, which is
+    /// always the case of a successful `unify` or `try_unify`.
+    pub fn units_match(&self) -> bool {
+        self.source == self.target
+    }
+
+    /// The name of the type of the value, e.g. `"f64"`.
+    pub fn value_type(&self) -> &'static str {
+        self.value_type
+    }
+
+    /// The location of the call to `unify`, `checked_unify`, `try_unify` or
+    /// `reinterpret_with`.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+}
+impl std::fmt::Display for Conversion {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        let kind = match self.kind {
+            ConversionKind::Unify => "unify",
+            ConversionKind::TryUnify => "try_unify",
+            ConversionKind::Reinterpret(_) => "reinterpret_with",
+        };
+        write!(
+            fmt,
+            "{} of `{}` from `{}` to `{}` at {}",
+            kind, self.value_type, self.source, self.target, self.location
+        )?;
+        if let ConversionKind::Reinterpret(justification) = self.kind {
+            write!(fmt, ": {}", justification)?;
+        }
+        Ok(())
+    }
+}
+
+/// A hook called for each change of unit.
+///
+/// Implemented by closures.
+pub trait AuditHook: Send + Sync {
+    fn conversion(&self, conversion: &Conversion);
+}
+impl<F> AuditHook for F
+where
+    F: Fn(&Conversion) + Send + Sync,
+{
+    fn conversion(&self, conversion: &Conversion) {
+        self(conversion)
+    }
+}
+
+static HOOK: RwLock<Option<Box<dyn AuditHook>>> = RwLock::new(None);
+
+/// Install `hook`, replacing the previous hook, if any.
+pub fn set_hook(hook: Box<dyn AuditHook>) {
+    let mut current = match HOOK.write() {
+        Ok(current) => current,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *current = Some(hook);
+}
+
+/// Uninstall the current hook, returning it.
+pub fn take_hook() -> Option<Box<dyn AuditHook>> {
+    let mut current = match HOOK.write() {
+        Ok(current) => current,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    current.take()
+}
+
+/// Report a change of a measure of `T` from unit `U` to unit `V` to the
+/// hook, if any.
+#[track_caller]
+pub(crate) fn record<T, U: Unit, V: Unit>(kind: ConversionKind) {
+    let location = Location::caller();
+    let hook = match HOOK.read() {
+        Ok(hook) => hook,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some(ref hook) = *hook {
+        hook.conversion(&Conversion {
+            kind,
+            source: U::as_runtime(),
+            target: V::as_runtime(),
+            value_type: std::any::type_name::<T>(),
+            location,
+        });
+    }
+}
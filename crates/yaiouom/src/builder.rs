@@ -0,0 +1,48 @@
+//! Builders for structs of measures, e.g. configurations.
+//!
+//! Use `#[derive(UnitBuilder)]` (feature `derive`) on a struct with named
+//! fields to generate a builder whose setters only accept values with the
+//! unit of the field, or values that convert into it with `Into` (e.g. a
+//! number, for a dimensionless field).
+//!
+//! ```ignore
+//! #[derive(UnitBuilder)]
+//! struct Config {
+//!     timeout: Measure<u64, Second>,
+//!     retries: Measure<u32, Dimensionless>,
+//! }
+//!
+//! let config = Config::builder()
+//!     .timeout(Second::new(5))
+//!     .retries(3)
+//!     .build()?;
+//!
+//! // Error: expected `Measure<u64, Second>`, found `Measure<u64, Meter>`.
+//! let config = Config::builder().timeout(Meter::new(5));
+//! ```
+//!
+//! yaiouom does not perform conversions between units (e.g. from
+//! milliseconds to seconds), so neither do the setters.
+
+/// The error returned by the `build` method of a builder generated by
+/// `#[derive(UnitBuilder)]`, if a field was not set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingField {
+    name: &'static str,
+}
+impl MissingField {
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+
+    /// The name of the field that was not set.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl std::fmt::Display for MissingField {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "Missing value for field `{}`", self.name)
+    }
+}
+impl std::error::Error for MissingField {}
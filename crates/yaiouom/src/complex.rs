@@ -0,0 +1,135 @@
+//! Measures with complex values, e.g. phasors and impedances for the
+//! analysis of AC circuits, with crate `num-complex`.
+//!
+//! All the operators of `Measure` apply to `Measure<Complex<T>, U>`, as for
+//! real values. This module adds the polar form of complex measures, with
+//! phases in `Radian`, as well as multiplication and division on the left
+//! by a complex scalar.
+//!
+//! ```
+//! extern crate num_complex;
+//! extern crate yaiouom;
+//!
+//! use num_complex::Complex;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! type Volt = Mul<Mul<Kg, Mul<Meter, Meter>>, Inv<Mul<Mul<Second, Mul<Second, Second>>, Ampere>>>;
+//! type Ohm = Mul<Volt, Inv<Ampere>>;
+//!
+//! # fn main() {
+//! // A resistor of 100 Ohm in series with a capacitor of 100 Ohm of
+//! // reactance, under 230 V.
+//! let voltage: Measure<Complex<f64>, Volt> = Measure::new(Complex::new(230., 0.));
+//! let resistor: Measure<Complex<f64>, Ohm> = Measure::new(Complex::new(100., 0.));
+//! let capacitor: Measure<Complex<f64>, Ohm> = Measure::new(Complex::new(0., -100.));
+//! let impedance = resistor + capacitor;
+//!
+//! let current: Measure<Complex<f64>, Ampere> = (voltage / impedance).unify();
+//! let magnitude: Measure<f64, Ampere> = current.abs();
+//! assert!((magnitude.as_ref() - 230. / 20000f64.sqrt()).abs() < 1e-9);
+//! // The current leads the voltage by 45 degrees.
+//! assert!((current.arg().as_ref() - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+//!
+//! // Back from the polar form.
+//! let same = Measure::from_polar(magnitude, current.arg());
+//! assert!((same - current).abs() < Measure::new(1e-9));
+//!
+//! // The apparent power, `V * conj(I)`, whose real part is the active power.
+//! let power = voltage * current.conj();
+//! assert!((power.re().as_ref() - 264.5).abs() < 1e-9);
+//! # }
+//! ```
+//!
+//! Complex scalars multiply and divide measures on either side:
+//!
+//! ```
+//! extern crate num_complex;
+//! extern crate num_traits;
+//! extern crate yaiouom;
+//!
+//! use num_complex::Complex;
+//! use num_traits::Zero;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! # fn main() {
+//! let j = Complex::new(0., 1.);
+//! let phasor: Measure<Complex<f64>, Ampere> = Measure::new(Complex::new(1., 0.));
+//!
+//! assert_eq!(j * phasor, phasor * j);
+//! assert_eq!(-(j * (j * phasor)), phasor);
+//! assert_eq!(j / phasor, Inv::<Ampere>::new(j));
+//! assert_eq!(phasor / j, Measure::new(-j));
+//!
+//! let phasors = vec![phasor, j * phasor, -phasor];
+//! let sum: Measure<Complex<f64>, Ampere> = phasors.into_iter().sum();
+//! assert_eq!(sum, j * phasor);
+//! assert!(Measure::<Complex<f64>, Ampere>::zero().is_zero());
+//! # }
+//! ```
+
+use std::ops::{Div, Mul};
+
+use num_complex::Complex;
+use num_traits::float::Float;
+
+use si::Radian;
+use unit::*;
+
+impl<T: Float, U: Unit> Measure<Complex<T>, U> {
+    /// The complex measure with magnitude `magnitude` and phase `phase`.
+    pub fn from_polar(magnitude: Measure<T, U>, phase: Measure<T, Radian>) -> Self {
+        Measure::new(Complex::from_polar(magnitude.value, phase.value))
+    }
+
+    /// The real part.
+    pub fn re(self) -> Measure<T, U> {
+        Measure::new(self.value.re)
+    }
+
+    /// The imaginary part.
+    pub fn im(self) -> Measure<T, U> {
+        Measure::new(self.value.im)
+    }
+
+    /// The magnitude, e.g. the amplitude of a phasor.
+    pub fn abs(self) -> Measure<T, U> {
+        Measure::new(self.value.norm())
+    }
+
+    /// The phase, in `(-pi, pi]`.
+    pub fn arg(self) -> Measure<T, Radian> {
+        Measure::new(self.value.arg())
+    }
+
+    /// The complex conjugate.
+    pub fn conj(self) -> Self {
+        Measure::new(self.value.conj())
+    }
+}
+
+/// Multiply a measure by a complex scalar on the left, e.g. the `j` of
+/// `j * omega * inductance`.
+impl<T, U: Unit> Mul<Measure<Complex<T>, U>> for Complex<T>
+where
+    Complex<T>: Mul<Output = Complex<T>>,
+{
+    type Output = Measure<Complex<T>, U>;
+    fn mul(self, rhs: Measure<Complex<T>, U>) -> Self::Output {
+        Measure::new(self * rhs.value)
+    }
+}
+
+/// Divide a complex scalar by a measure, e.g. to compute an admittance.
+impl<T, U: Unit> Div<Measure<Complex<T>, U>> for Complex<T>
+where
+    Complex<T>: Div<Output = Complex<T>>,
+{
+    type Output = Measure<Complex<T>, Inv<U>>;
+    fn div(self, rhs: Measure<Complex<T>, U>) -> Self::Output {
+        Measure::new(self / rhs.value)
+    }
+}
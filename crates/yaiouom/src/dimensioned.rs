@@ -0,0 +1,160 @@
+//! Interoperability with crate `dimensioned`, e.g. to let libraries built
+//! on either crate work together.
+//!
+//! Base units of `dimensioned::si` convert from and to the corresponding
+//! units of `yaiouom::si` with `From`, e.g. `dimensioned::si::Meter<f64>`
+//! and `Measure<f64, Meter>`. These conversions are checked at compile time.
+//!
+//! ```
+//! extern crate dimensioned;
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! # fn main() {
+//! let length = 3. * dimensioned::si::M;
+//! let measure: Measure<f64, Meter> = length.into();
+//! assert_eq!(measure, Meter::new(3.));
+//!
+//! // `Measure::into` converts between value representations, so use `from`.
+//! let length = dimensioned::si::Meter::from(measure);
+//! assert_eq!(length, 3. * dimensioned::si::M);
+//! # }
+//! ```
+//!
+//! Other units, e.g. velocities, convert with `try_from_dimensioned` and
+//! `try_into_dimensioned`, which compare units at runtime, or into a
+//! `DynMeasure`.
+//!
+//! ```
+//! extern crate dimensioned;
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::dimensioned::*;
+//!
+//! # fn main() {
+//! let velocity = 5. * dimensioned::si::M / dimensioned::si::S;
+//!
+//! let speed: Measure<f64, Mul<Meter, Inv<Second>>> = try_from_dimensioned(velocity).unwrap();
+//! assert_eq!(speed.as_ref(), &5.);
+//! assert!(try_from_dimensioned::<_, _, Mul<Meter, Second>>(velocity).is_err());
+//!
+//! let velocity: dimensioned::si::MeterPerSecond<f64> = try_into_dimensioned(speed).unwrap();
+//! assert_eq!(velocity, 5. * dimensioned::si::M / dimensioned::si::S);
+//!
+//! assert_eq!(DynMeasure::from(velocity).unit().to_string(), "m * s^-1");
+//! # }
+//! ```
+//!
+//! Only `dimensioned::si` is supported: the other unit systems of
+//! `dimensioned` (e.g. `cgs`) have no equivalent in `yaiouom::si`.
+
+use dimensioned_crate::array::ToGA;
+use dimensioned_crate::si::SI;
+
+use dynamic::*;
+use si::*;
+use unit::*;
+
+/// Add `B^exponent` to `repr`.
+fn add_base<B: BaseUnit>(repr: &mut RuntimeUnit, exponent: isize) {
+    for _ in 0..exponent.abs() {
+        B::add_to_runtime(repr, exponent > 0).unwrap();
+    }
+}
+
+/// The unit of `dimensioned::si` units `U`, e.g. the `U` of
+/// `dimensioned::si::SI<f64, U>`.
+pub fn runtime_unit<U>() -> RuntimeUnit
+where
+    U: ToGA,
+    U::Output: AsRef<[isize]>,
+{
+    let exponents = U::to_ga();
+    let exponents = exponents.as_ref();
+    assert_eq!(exponents.len(), 7, "Not a unit of `dimensioned::si`");
+
+    // The order of base units in `dimensioned::si`.
+    let mut repr = Dimensionless::as_runtime();
+    add_base::<Meter>(&mut repr, exponents[0]);
+    add_base::<Kg>(&mut repr, exponents[1]);
+    add_base::<Second>(&mut repr, exponents[2]);
+    add_base::<Ampere>(&mut repr, exponents[3]);
+    add_base::<Kelvin>(&mut repr, exponents[4]);
+    add_base::<Candela>(&mut repr, exponents[5]);
+    add_base::<Mole>(&mut repr, exponents[6]);
+    repr
+}
+
+/// Convert a `dimensioned` value to a measure, if its units match unit `U`.
+///
+/// # Performance note
+///
+/// This function is fine for debugging, but should not be used in a tight loop.
+pub fn try_from_dimensioned<V, D, U>(value: SI<V, D>) -> Result<Measure<V, U>, UnitMismatch>
+where
+    D: ToGA,
+    D::Output: AsRef<[isize]>,
+    U: Unit,
+{
+    let found = runtime_unit::<D>();
+    let expected = U::as_runtime();
+    if found != expected {
+        return Err(UnitMismatch::new(expected, found));
+    }
+    Ok(Measure::new(value.value_unsafe))
+}
+
+/// Convert a measure to a `dimensioned` value, if unit `U` matches its units.
+///
+/// # Performance note
+///
+/// This function is fine for debugging, but should not be used in a tight loop.
+pub fn try_into_dimensioned<V, D, U>(measure: Measure<V, U>) -> Result<SI<V, D>, UnitMismatch>
+where
+    D: ToGA,
+    D::Output: AsRef<[isize]>,
+    U: Unit,
+{
+    let found = U::as_runtime();
+    let expected = runtime_unit::<D>();
+    if found != expected {
+        return Err(UnitMismatch::new(expected, found));
+    }
+    Ok(SI::new(measure.value))
+}
+
+impl<V, D> From<SI<V, D>> for DynMeasure<V>
+where
+    D: ToGA,
+    D::Output: AsRef<[isize]>,
+{
+    fn from(value: SI<V, D>) -> Self {
+        DynMeasure::new(value.value_unsafe, runtime_unit::<D>())
+    }
+}
+
+macro_rules! impl_base_units {
+    ($($dimensioned:ident => $unit:ty),*) => {
+        $(
+            impl<V> From<::dimensioned_crate::si::$dimensioned<V>> for Measure<V, $unit> {
+                fn from(value: ::dimensioned_crate::si::$dimensioned<V>) -> Self {
+                    Measure::new(value.value_unsafe)
+                }
+            }
+            impl<V> From<Measure<V, $unit>> for ::dimensioned_crate::si::$dimensioned<V> {
+                fn from(measure: Measure<V, $unit>) -> Self {
+                    SI::new(measure.value)
+                }
+            }
+        )*
+    }
+}
+
+impl_base_units! {
+    Meter => Meter, Kilogram => Kg, Second => Second, Ampere => Ampere,
+    Kelvin => Kelvin, Candela => Candela, Mole => Mole
+}
@@ -0,0 +1,135 @@
+//! Units of dosing, e.g. for the rates of infusion pumps.
+//!
+//! Doses are expressed in mg of drug per kg of body weight, which SI would
+//! simplify into a dimensionless ratio, losing the very information that
+//! must be checked. This module therefore defines its own base units,
+//! `Milligram` of drug, `BodyKg` of body weight, `Milliliter` of solution
+//! and `Hour`. They are distinct from those of module `si`, and the crate
+//! does not convert between them.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::dosing::*;
+//!
+//! // 0.5 mg/kg/h for a patient of 70 kg, from a solution of 2 mg/mL.
+//! let dose_rate: Measure<f64, DoseRate> = 0.5 * MG / (BODY_KG * H);
+//! let weight = 70. * BODY_KG;
+//! let concentration: Measure<f64, Concentration> = 2. * MG / ML;
+//!
+//! let pump = infusion_rate(dose_rate, weight, concentration);
+//! assert_eq!(pump, Measure::new(17.5));
+//! assert_eq!(pump.to_tagged().1, "mL * h^-1");
+//!
+//! // Check the pump rate against the prescription.
+//! assert_eq!(dose_rate_of_infusion(pump, concentration, weight), dose_rate);
+//! ```
+//!
+//! Mixing up drug mass and body weight does not type-check:
+//!
+//! ```compile_fail
+//! use yaiouom::*;
+//! use yaiouom::dosing::*;
+//!
+//! let dose_rate: Measure<f64, DoseRate> = 0.5 * MG / (BODY_KG * H);
+//! let weight = 70. * MG;
+//! let _ = drug_rate(dose_rate, weight);
+//! ```
+
+use num_traits::float::Float;
+
+use unit::*;
+
+/// Base unit of the mass of a drug.
+pub struct Milligram;
+impl BaseUnit for Milligram {
+    const NAMESPACE: &'static str = "yaiouom::dosing";
+    const NAME: &'static str = "mg";
+    const FULL_NAME: &'static str = "milligram";
+    const QUANTITY: &'static str = "mass of drug";
+}
+
+/// Base unit of body weight.
+pub struct BodyKg;
+impl BaseUnit for BodyKg {
+    const NAMESPACE: &'static str = "yaiouom::dosing";
+    const NAME: &'static str = "kg_body";
+    const FULL_NAME: &'static str = "kilogram of body weight";
+    const QUANTITY: &'static str = "body weight";
+}
+
+/// Base unit of the volume of a solution.
+pub struct Milliliter;
+impl BaseUnit for Milliliter {
+    const NAMESPACE: &'static str = "yaiouom::dosing";
+    const NAME: &'static str = "mL";
+    const FULL_NAME: &'static str = "millilitre";
+    const QUANTITY: &'static str = "volume of solution";
+}
+
+/// Base unit of time for dosing.
+pub struct Hour;
+impl BaseUnit for Hour {
+    const NAMESPACE: &'static str = "yaiouom::dosing";
+    const NAME: &'static str = "h";
+    const FULL_NAME: &'static str = "hour";
+    const QUANTITY: &'static str = "time";
+}
+
+/// One `Milligram`, e.g. to write `3. * MG`.
+pub const MG: Measure<f64, Milligram> = Measure::new(1.);
+/// One `BodyKg`, e.g. to write `70. * BODY_KG`.
+pub const BODY_KG: Measure<f64, BodyKg> = Measure::new(1.);
+/// One `Milliliter`, e.g. to write `3. * ML`.
+pub const ML: Measure<f64, Milliliter> = Measure::new(1.);
+/// One `Hour`, e.g. to write `3. * H`.
+pub const H: Measure<f64, Hour> = Measure::new(1.);
+
+/// mg * kg_body^-1 * h^-1.
+pub type DoseRate = Mul<Milligram, Inv<Mul<BodyKg, Hour>>>;
+
+/// mg * h^-1.
+pub type DrugRate = Mul<Milligram, Inv<Hour>>;
+
+/// mg * mL^-1.
+pub type Concentration = Mul<Milligram, Inv<Milliliter>>;
+
+/// mL * h^-1.
+pub type InfusionRate = Mul<Milliliter, Inv<Hour>>;
+
+/// The rate at which a patient of body weight `weight` should receive the
+/// drug, for a prescription of `dose_rate`.
+pub fn drug_rate<T: Float>(
+    dose_rate: Measure<T, DoseRate>,
+    weight: Measure<T, BodyKg>,
+) -> Measure<T, DrugRate> {
+    (dose_rate * weight).unify()
+}
+
+/// The rate of an infusion pump delivering `dose_rate` to a patient of body
+/// weight `weight`, from a solution of `concentration`.
+pub fn infusion_rate<T: Float>(
+    dose_rate: Measure<T, DoseRate>,
+    weight: Measure<T, BodyKg>,
+    concentration: Measure<T, Concentration>,
+) -> Measure<T, InfusionRate> {
+    (drug_rate(dose_rate, weight) / concentration).unify()
+}
+
+/// The dose rate delivered by an infusion pump running at `infusion_rate`
+/// to a patient of body weight `weight`, from a solution of
+/// `concentration`, i.e. the converse of `infusion_rate`.
+pub fn dose_rate_of_infusion<T: Float>(
+    infusion_rate: Measure<T, InfusionRate>,
+    concentration: Measure<T, Concentration>,
+    weight: Measure<T, BodyKg>,
+) -> Measure<T, DoseRate> {
+    (infusion_rate * concentration / weight).unify()
+}
+
+/// The total amount of drug delivered at `drug_rate` during `duration`.
+pub fn dose<T: Float>(
+    drug_rate: Measure<T, DrugRate>,
+    duration: Measure<T, Hour>,
+) -> Measure<T, Milligram> {
+    (drug_rate * duration).unify()
+}
@@ -0,0 +1,311 @@
+//! Values whose unit is only known at runtime.
+use unit::*;
+
+use std;
+use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+
+/// A unit as a trait object, e.g. a unit chosen at runtime by a plugin.
+///
+/// Implemented by all base units. Other units, e.g. `Mul<Meter, Inv<Second>>`,
+/// may be wrapped in a `StaticUnit`.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let units: Vec<Box<dyn DynUnit>> = vec![
+///     Box::new(Meter),
+///     Box::new(StaticUnit::<Mul<Meter, Inv<Second>>>::new()),
+/// ];
+/// assert_eq!(units[1].name(), "m * s^-1");
+///
+/// let speed = DynMeasure::with_dyn_unit(5., &*units[1]);
+/// assert_eq!(speed.unit(), &Mul::<Meter, Inv<Second>>::as_runtime());
+///
+/// // Back to a static unit.
+/// assert!(units[0].is::<Meter>());
+/// let distance: Measure<f64, Meter> = units[0].downcast(3.).unwrap();
+/// assert_eq!(distance, Meter::new(3.));
+/// assert!(units[0].downcast::<_, Second>(3.).is_err());
+/// ```
+pub trait DynUnit: Any {
+    /// The runtime representation of this unit, e.g. to compare it with
+    /// other units.
+    fn runtime_unit(&self) -> RuntimeUnit;
+
+    /// A human-readable name, e.g. `"m * s^-1"`.
+    fn name(&self) -> String {
+        self.runtime_unit().to_string()
+    }
+
+    /// The `TypeId` of the static unit represented by `self`, if any.
+    ///
+    /// Implementations for types which are not themselves units should
+    /// return `None`.
+    fn unit_type_id(&self) -> Option<TypeId> {
+        None
+    }
+}
+
+impl<U: BaseUnit> DynUnit for U {
+    fn runtime_unit(&self) -> RuntimeUnit {
+        U::as_runtime()
+    }
+    fn unit_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<U>())
+    }
+}
+
+impl dyn DynUnit {
+    /// `true` if `self` represents the static unit `U`.
+    ///
+    /// Units are compared by `TypeId`, so `Mul<Meter, Second>` and
+    /// `Mul<Second, Meter>` are distinct.
+    pub fn is<U: Unit + 'static>(&self) -> bool {
+        self.unit_type_id() == Some(TypeId::of::<U>())
+    }
+
+    /// Build a measure of `value` with the static unit `U`, if `self`
+    /// represents `U`.
+    ///
+    /// Otherwise, return `value` unchanged.
+    pub fn downcast<T, U: Unit + 'static>(&self, value: T) -> Result<Measure<T, U>, T> {
+        if self.is::<U>() {
+            Ok(Measure::new(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+/// Any unit, as a `DynUnit`.
+pub struct StaticUnit<U: Unit> {
+    unit: PhantomData<U>,
+}
+impl<U: Unit> StaticUnit<U> {
+    pub fn new() -> Self {
+        StaticUnit { unit: PhantomData }
+    }
+}
+impl<U: Unit> Default for StaticUnit<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<U: Unit + 'static> DynUnit for StaticUnit<U> {
+    fn runtime_unit(&self) -> RuntimeUnit {
+        U::as_runtime()
+    }
+    fn unit_type_id(&self) -> Option<TypeId> {
+        Some(TypeId::of::<U>())
+    }
+}
+
+/// A value with a unit, where the unit is only known at runtime.
+///
+/// This is useful e.g. to store measures with distinct units in a single
+/// collection. Of course, the type system cannot check anything about
+/// such values, so prefer `Measure` whenever possible.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let measures = vec![
+///     DynMeasure::from(Meter::new(1.)),
+///     DynMeasure::from(Second::new(2.)),
+/// ];
+/// assert_eq!(measures[0].unit().to_string(), "m");
+/// assert_eq!(measures[1].unit().to_string(), "s");
+///
+/// let duration: Measure<f64, Second> = measures[1].clone().into_static().unwrap();
+/// assert_eq!(duration, Second::new(2.));
+/// assert!(measures[0].clone().into_static::<Second>().is_err());
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct DynMeasure<T> {
+    value: T,
+    unit: RuntimeUnit,
+}
+
+impl<T> DynMeasure<T> {
+    pub fn new(value: T, unit: RuntimeUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// A value with a unit chosen at runtime.
+    pub fn with_dyn_unit(value: T, unit: &dyn DynUnit) -> Self {
+        Self::new(value, unit.runtime_unit())
+    }
+
+    /// The value, without its unit.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// The unit of this value.
+    pub fn unit(&self) -> &RuntimeUnit {
+        &self.unit
+    }
+
+    /// Convert back to a `Measure`, if the unit matches `U`.
+    ///
+    /// Otherwise, return `self` unchanged.
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    pub fn into_static<U: Unit>(self) -> Result<Measure<T, U>, Self> {
+        if self.unit == U::as_runtime() {
+            Ok(Measure::new(self.value))
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T> DynMeasure<T> {
+    /// Add two values, if they have the same unit.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let distance = DynMeasure::from(Meter::new(1.));
+    /// let sum = distance.clone().try_add(DynMeasure::from(Meter::new(2.))).unwrap();
+    /// assert_eq!(sum, DynMeasure::from(Meter::new(3.)));
+    ///
+    /// let err = distance.try_add(DynMeasure::from(Second::new(2.))).unwrap_err();
+    /// assert_eq!(err.to_string(), "Unit mismatch: expected `s`, found `m` (missing: s; extra: m)");
+    /// ```
+    pub fn try_add(self, rhs: Self) -> Result<DynMeasure<T::Output>, UnitMismatch>
+    where
+        T: std::ops::Add,
+    {
+        if self.unit != rhs.unit {
+            return Err(UnitMismatch::new(rhs.unit, self.unit));
+        }
+        Ok(DynMeasure::new(self.value + rhs.value, self.unit))
+    }
+
+    /// Subtract two values, if they have the same unit.
+    pub fn try_sub(self, rhs: Self) -> Result<DynMeasure<T::Output>, UnitMismatch>
+    where
+        T: std::ops::Sub,
+    {
+        if self.unit != rhs.unit {
+            return Err(UnitMismatch::new(rhs.unit, self.unit));
+        }
+        Ok(DynMeasure::new(self.value - rhs.value, self.unit))
+    }
+
+    /// Multiply two values, if the exponents of the product do not
+    /// overflow.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let distance = DynMeasure::from(Meter::new(2.));
+    /// let area = distance.clone().try_mul(distance).unwrap();
+    /// assert_eq!(area, DynMeasure::from(Mul::<Meter, Meter>::new(4.)));
+    ///
+    /// // m^16384 * m^16384 does not fit in an `i16`.
+    /// let mut huge = DynMeasure::from(Meter::new(1.));
+    /// for _ in 0..14 {
+    ///     huge = huge.clone().try_mul(huge).unwrap();
+    /// }
+    /// let err = huge.clone().try_mul(huge).unwrap_err();
+    /// assert_eq!(err.to_string(), "Exponent of `m` exceeds 32767");
+    /// ```
+    pub fn try_mul(mut self, rhs: Self) -> Result<DynMeasure<T::Output>, ExponentOverflow>
+    where
+        T: std::ops::Mul,
+    {
+        self.unit.combine(&rhs.unit, true)?;
+        Ok(DynMeasure::new(self.value * rhs.value, self.unit))
+    }
+
+    /// Divide two values, if the exponents of the quotient do not
+    /// overflow.
+    pub fn try_div(mut self, rhs: Self) -> Result<DynMeasure<T::Output>, ExponentOverflow>
+    where
+        T: std::ops::Div,
+    {
+        self.unit.combine(&rhs.unit, false)?;
+        Ok(DynMeasure::new(self.value / rhs.value, self.unit))
+    }
+}
+
+/// Multiply two values, multiplying their units.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let area = DynMeasure::from(Meter::new(2.)) * DynMeasure::from(Meter::new(3.));
+/// assert_eq!(area, DynMeasure::from(Mul::<Meter, Meter>::new(6.)));
+/// ```
+///
+/// # Panics
+///
+/// If an exponent of the product overflows, as `Unit::as_runtime`. Use
+/// `try_mul` to handle this case, e.g. with units chosen by a plugin.
+impl<T> std::ops::Mul<Self> for DynMeasure<T>
+where
+    T: std::ops::Mul,
+{
+    type Output = DynMeasure<T::Output>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        match self.try_mul(rhs) {
+            Ok(mul) => mul,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+/// Divide two values, dividing their units.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let speed = DynMeasure::from(Meter::new(6.)) / DynMeasure::from(Second::new(3.));
+/// assert_eq!(speed, DynMeasure::from(Mul::<Meter, Inv<Second>>::new(2.)));
+/// ```
+///
+/// # Panics
+///
+/// If an exponent of the quotient overflows, as `Unit::as_runtime`. Use
+/// `try_div` to handle this case, e.g. with units chosen by a plugin.
+impl<T> std::ops::Div<Self> for DynMeasure<T>
+where
+    T: std::ops::Div,
+{
+    type Output = DynMeasure<T::Output>;
+    fn div(self, rhs: Self) -> Self::Output {
+        match self.try_div(rhs) {
+            Ok(div) => div,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+impl<T, U: Unit> From<Measure<T, U>> for DynMeasure<T> {
+    fn from(measure: Measure<T, U>) -> Self {
+        Self {
+            value: measure.value,
+            unit: U::as_runtime(),
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for DynMeasure<T>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "{:?}{}", self.value, self.unit.to_canonical_string())
+    }
+}
@@ -0,0 +1,76 @@
+//! Dimensionless numbers of fluid dynamics.
+//!
+//! Each number is computed from measures in SI units, with a `unify` that
+//! yaiouom-checker verifies when it checks this crate, so a mistake in a
+//! formula is caught at compile time rather than in the results of a
+//! simulation.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::fluids::*;
+//!
+//! // Water flowing at 2 m/s in a pipe with a diameter of 5 cm.
+//! let density: Measure<f64, Density> = Measure::new(1000.);
+//! let viscosity: Measure<f64, DynamicViscosity> = Measure::new(1e-3);
+//! let velocity: Measure<f64, Speed> = Measure::new(2.);
+//!
+//! let re = reynolds(density, velocity, 0.05 * M, viscosity);
+//! assert!((re.as_ref() - 1e5).abs() < 1e-6);
+//!
+//! let ma = mach(Measure::new(170.), Measure::new(340.));
+//! assert_eq!(ma, Dimensionless::new(0.5));
+//!
+//! let fr = froude(Measure::new(3.), Measure::new(9.), 1. * M);
+//! assert_eq!(fr, Dimensionless::new(1.));
+//! ```
+
+use num_traits::float::Float;
+
+use geometry::Volume;
+use si::*;
+use unit::*;
+
+/// m * s^-1.
+pub type Speed = Mul<Meter, Inv<Second>>;
+
+/// m * s^-2.
+pub type Acceleration = Mul<Speed, Inv<Second>>;
+
+/// kg * m^-3.
+pub type Density = Mul<Kg, Inv<Volume<Meter>>>;
+
+/// kg * m^-1 * s^-1, i.e. Pa * s.
+pub type DynamicViscosity = Mul<Kg, Inv<Mul<Meter, Second>>>;
+
+/// The Reynolds number, the ratio of inertial forces to viscous forces,
+/// for a fluid of density `density` and dynamic viscosity `viscosity`
+/// flowing at `velocity` along characteristic length `length`.
+pub fn reynolds<T: Float>(
+    density: Measure<T, Density>,
+    velocity: Measure<T, Speed>,
+    length: Measure<T, Meter>,
+    viscosity: Measure<T, DynamicViscosity>,
+) -> Measure<T, Dimensionless> {
+    (density * velocity * length / viscosity).unify()
+}
+
+/// The Mach number, the ratio of `velocity` to the speed of sound in the
+/// medium.
+pub fn mach<T: Float>(
+    velocity: Measure<T, Speed>,
+    speed_of_sound: Measure<T, Speed>,
+) -> Measure<T, Dimensionless> {
+    (velocity / speed_of_sound).unify()
+}
+
+/// The Froude number, the ratio of inertial forces to gravity, for a flow
+/// at `velocity` along characteristic length `length`.
+pub fn froude<T: Float>(
+    velocity: Measure<T, Speed>,
+    gravity: Measure<T, Acceleration>,
+    length: Measure<T, Meter>,
+) -> Measure<T, Dimensionless> {
+    let wave_speed: Measure<T, Mul<Speed, Speed>> = (gravity * length).unify();
+    (velocity / wave_speed.sqrt()).unify()
+}
@@ -0,0 +1,494 @@
+//! Formulas over measures whose units are only known at runtime, e.g.
+//! formulas typed by the users of a spreadsheet over named columns.
+//!
+//! A formula is made of numbers, which are dimensionless, variables,
+//! `+`, `-`, `*`, `/`, parentheses and integer powers, e.g. `x^2` or
+//! `t^-1`. It may start with the name of its result, as in
+//! `speed = distance / time`. Units are checked as the formula is
+//! evaluated, and errors point to the part of the formula at fault.
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::formula::*;
+//!
+//! let mut columns = HashMap::new();
+//! columns.insert("distance".to_string(), DynMeasure::from(Meter::new(10.)));
+//! columns.insert("time".to_string(), DynMeasure::from(Second::new(2.)));
+//!
+//! let formula = Formula::parse("speed = distance / time").unwrap();
+//! assert_eq!(formula.target(), Some("speed"));
+//! let speed = formula.evaluate(&columns).unwrap();
+//! assert_eq!(speed, DynMeasure::from(Mul::<Meter, Inv<Second>>::new(5.)));
+//!
+//! let err = evaluate("distance + 2 * time", &columns).unwrap_err();
+//! assert_eq!(err.span(), 0..19);
+//! assert_eq!(err.to_string(), "Cannot add `m` and `s` (at 0..19)");
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use dynamic::DynMeasure;
+use unit::*;
+
+/// An error in a formula, at a range of bytes of its source.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FormulaError {
+    /// The formula is not well-formed, e.g. `distance /`.
+    Syntax {
+        span: Range<usize>,
+        expected: &'static str,
+    },
+
+    /// A variable is not defined.
+    UnknownVariable { span: Range<usize>, name: String },
+
+    /// The operands of `+` or `-` do not have the same unit.
+    Mismatch {
+        span: Range<usize>,
+        operator: char,
+        left: RuntimeUnit,
+        right: RuntimeUnit,
+    },
+
+    /// An exponent of a unit does not fit in an `i16`.
+    Overflow {
+        span: Range<usize>,
+        overflow: ExponentOverflow,
+    },
+}
+
+impl FormulaError {
+    /// The bytes of the formula at fault.
+    pub fn span(&self) -> Range<usize> {
+        match *self {
+            FormulaError::Syntax { ref span, .. }
+            | FormulaError::UnknownVariable { ref span, .. }
+            | FormulaError::Mismatch { ref span, .. }
+            | FormulaError::Overflow { ref span, .. } => span.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for FormulaError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match *self {
+            FormulaError::Syntax { expected, .. } => write!(fmt, "Expected {}", expected)?,
+            FormulaError::UnknownVariable { ref name, .. } => {
+                write!(fmt, "Unknown variable `{}`", name)?
+            }
+            FormulaError::Mismatch {
+                operator: '+',
+                ref left,
+                ref right,
+                ..
+            } => write!(fmt, "Cannot add `{}` and `{}`", left, right)?,
+            FormulaError::Mismatch {
+                ref left,
+                ref right,
+                ..
+            } => write!(fmt, "Cannot subtract `{}` from `{}`", right, left)?,
+            FormulaError::Overflow { ref overflow, .. } => write!(fmt, "{}", overflow)?,
+        }
+        let span = self.span();
+        write!(fmt, " (at {}..{})", span.start, span.end)
+    }
+}
+
+impl std::error::Error for FormulaError {}
+
+enum Expr {
+    Number(f64),
+    Variable(String),
+    Neg(Box<Node>),
+    /// `left operator right`, with `operator` one of `+`, `-`, `*`, `/`.
+    Binary(Box<Node>, char, Box<Node>),
+    Pow(Box<Node>, i16),
+}
+
+struct Node {
+    expr: Expr,
+    span: Range<usize>,
+}
+
+/// A recursive descent parser, with the usual precedence: `^`, then unary
+/// `-`, then `*` and `/`, then `+` and `-`.
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.source[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.source[self.position..].chars().next()
+    }
+
+    /// Consume `c`, if it is the next character.
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error<T>(&mut self, expected: &'static str) -> Result<T, FormulaError> {
+        self.skip_whitespace();
+        let len = self.source[self.position..]
+            .chars()
+            .next()
+            .map(char::len_utf8)
+            .unwrap_or(0);
+        Err(FormulaError::Syntax {
+            span: self.position..self.position + len,
+            expected,
+        })
+    }
+
+    /// Consume the longest prefix whose characters satisfy `accept`.
+    fn take_while<F: Fn(usize, char) -> bool>(&mut self, accept: F) -> Range<usize> {
+        let start = self.position;
+        let len = self.source[start..]
+            .char_indices()
+            .find(|&(index, c)| !accept(index, c))
+            .map(|(index, _)| index)
+            .unwrap_or(self.source.len() - start);
+        self.position += len;
+        start..self.position
+    }
+
+    fn identifier(&mut self) -> Option<Range<usize>> {
+        match self.peek() {
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                Some(self.take_while(|_, c| c.is_alphanumeric() || c == '_'))
+            }
+            _ => None,
+        }
+    }
+
+    fn sum(&mut self) -> Result<Node, FormulaError> {
+        let mut left = self.product()?;
+        loop {
+            let operator = match self.peek() {
+                Some(c) if c == '+' || c == '-' => c,
+                _ => return Ok(left),
+            };
+            self.eat(operator);
+            let right = self.product()?;
+            left = binary(left, operator, right);
+        }
+    }
+
+    fn product(&mut self) -> Result<Node, FormulaError> {
+        let mut left = self.negation()?;
+        loop {
+            let operator = match self.peek() {
+                Some(c) if c == '*' || c == '/' => c,
+                _ => return Ok(left),
+            };
+            self.eat(operator);
+            let right = self.negation()?;
+            left = binary(left, operator, right);
+        }
+    }
+
+    fn negation(&mut self) -> Result<Node, FormulaError> {
+        self.skip_whitespace();
+        let start = self.position;
+        if self.eat('-') {
+            let operand = self.negation()?;
+            let span = start..operand.span.end;
+            return Ok(Node {
+                expr: Expr::Neg(Box::new(operand)),
+                span,
+            });
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Result<Node, FormulaError> {
+        let base = self.atom()?;
+        if !self.eat('^') {
+            return Ok(base);
+        }
+        self.skip_whitespace();
+        let start = self.position;
+        self.eat('-');
+        let digits = self.take_while(|_, c| c.is_ascii_digit());
+        let exponent = match self.source[start..digits.end].parse() {
+            Ok(exponent) => exponent,
+            Err(_) => {
+                self.position = start;
+                return self.error("an integer exponent");
+            }
+        };
+        let span = base.span.start..digits.end;
+        Ok(Node {
+            expr: Expr::Pow(Box::new(base), exponent),
+            span,
+        })
+    }
+
+    fn atom(&mut self) -> Result<Node, FormulaError> {
+        self.skip_whitespace();
+        let start = self.position;
+        if self.eat('(') {
+            let inner = self.sum()?;
+            if !self.eat(')') {
+                return self.error("`)`");
+            }
+            return Ok(Node {
+                expr: inner.expr,
+                span: start..self.position,
+            });
+        }
+        if let Some(span) = self.identifier() {
+            return Ok(Node {
+                expr: Expr::Variable(self.source[span.clone()].to_string()),
+                span,
+            });
+        }
+        // A number, e.g. `2`, `0.5` or `1e-3`.
+        let span = self.take_while(|index, c| {
+            c.is_ascii_digit() || c == '.' || (index > 0 && (c == 'e' || c == 'E'))
+        });
+        let span = if !span.is_empty()
+            && self.source[..span.end].ends_with(['e', 'E'])
+            && self.source[span.end..].starts_with(['-', '+'])
+        {
+            self.position += 1;
+            let exponent = self.take_while(|_, c| c.is_ascii_digit());
+            span.start..exponent.end
+        } else {
+            span
+        };
+        match self.source[span.clone()].parse() {
+            Ok(value) => Ok(Node {
+                expr: Expr::Number(value),
+                span,
+            }),
+            Err(_) => {
+                self.position = start;
+                self.error("a number, a variable or `(`")
+            }
+        }
+    }
+
+    /// An optional `name =`, then an expression.
+    fn formula(&mut self) -> Result<Formula, FormulaError> {
+        let start = self.position;
+        let target = match self.identifier() {
+            Some(name) if self.eat('=') => Some(self.source[name].to_string()),
+            _ => {
+                self.position = start;
+                None
+            }
+        };
+        let root = self.sum()?;
+        if self.peek().is_some() {
+            return self.error("an operator");
+        }
+        Ok(Formula { target, root })
+    }
+}
+
+fn binary(left: Node, operator: char, right: Node) -> Node {
+    let span = left.span.start..right.span.end;
+    Node {
+        expr: Expr::Binary(Box::new(left), operator, Box::new(right)),
+        span,
+    }
+}
+
+/// What evaluating a formula computes: measures, or only their units.
+trait Operand: Clone {
+    fn number(value: f64) -> Self;
+    fn unit(&self) -> &RuntimeUnit;
+    fn neg(self) -> Self;
+
+    /// `self + rhs` if `positive`, `self - rhs` otherwise, with the same unit.
+    fn add(self, rhs: Self, positive: bool) -> Self;
+
+    /// `self * rhs` if `positive`, `self / rhs` otherwise.
+    fn mul(self, rhs: Self, positive: bool) -> Result<Self, ExponentOverflow>;
+}
+
+impl Operand for DynMeasure<f64> {
+    fn number(value: f64) -> Self {
+        DynMeasure::new(value, Dimensionless::as_runtime())
+    }
+    fn unit(&self) -> &RuntimeUnit {
+        DynMeasure::unit(self)
+    }
+    fn neg(self) -> Self {
+        DynMeasure::new(-*self.value(), self.unit().clone())
+    }
+    fn add(self, rhs: Self, positive: bool) -> Self {
+        let value = if positive {
+            self.value() + rhs.value()
+        } else {
+            self.value() - rhs.value()
+        };
+        DynMeasure::new(value, self.unit().clone())
+    }
+    fn mul(self, rhs: Self, positive: bool) -> Result<Self, ExponentOverflow> {
+        let mut unit = self.unit().clone();
+        unit.combine(rhs.unit(), positive)?;
+        let value = if positive {
+            self.value() * rhs.value()
+        } else {
+            self.value() / rhs.value()
+        };
+        Ok(DynMeasure::new(value, unit))
+    }
+}
+
+impl Operand for RuntimeUnit {
+    fn number(_: f64) -> Self {
+        Dimensionless::as_runtime()
+    }
+    fn unit(&self) -> &RuntimeUnit {
+        self
+    }
+    fn neg(self) -> Self {
+        self
+    }
+    fn add(self, _: Self, _: bool) -> Self {
+        self
+    }
+    fn mul(mut self, rhs: Self, positive: bool) -> Result<Self, ExponentOverflow> {
+        self.combine(&rhs, positive)?;
+        Ok(self)
+    }
+}
+
+fn eval<V: Operand>(node: &Node, variables: &HashMap<String, V>) -> Result<V, FormulaError> {
+    let overflow = |overflow| FormulaError::Overflow {
+        span: node.span.clone(),
+        overflow,
+    };
+    match node.expr {
+        Expr::Number(value) => Ok(V::number(value)),
+        Expr::Variable(ref name) => {
+            variables
+                .get(name)
+                .cloned()
+                .ok_or_else(|| FormulaError::UnknownVariable {
+                    span: node.span.clone(),
+                    name: name.clone(),
+                })
+        }
+        Expr::Neg(ref operand) => Ok(eval(operand, variables)?.neg()),
+        Expr::Binary(ref left, operator, ref right) => {
+            let left = eval(left, variables)?;
+            let right = eval(right, variables)?;
+            match operator {
+                '+' | '-' => {
+                    if left.unit() != right.unit() {
+                        return Err(FormulaError::Mismatch {
+                            span: node.span.clone(),
+                            operator,
+                            left: left.unit().clone(),
+                            right: right.unit().clone(),
+                        });
+                    }
+                    Ok(left.add(right, operator == '+'))
+                }
+                _ => left.mul(right, operator == '*').map_err(overflow),
+            }
+        }
+        Expr::Pow(ref base, exponent) => {
+            let base = eval(base, variables)?;
+            let mut result = V::number(1.);
+            for _ in 0..exponent.unsigned_abs() {
+                result = result.mul(base.clone(), exponent > 0).map_err(overflow)?;
+            }
+            Ok(result)
+        }
+    }
+}
+
+/// A parsed formula, e.g. to evaluate it once per row of a spreadsheet.
+pub struct Formula {
+    target: Option<String>,
+    root: Node,
+}
+
+impl Formula {
+    pub fn parse(source: &str) -> Result<Self, FormulaError> {
+        Parser {
+            source,
+            position: 0,
+        }
+        .formula()
+    }
+
+    /// The name of the result, e.g. `speed` in `speed = distance / time`.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// Evaluate with the measures of `variables`.
+    pub fn evaluate(
+        &self,
+        variables: &HashMap<String, DynMeasure<f64>>,
+    ) -> Result<DynMeasure<f64>, FormulaError> {
+        eval(&self.root, variables)
+    }
+
+    /// The unit of the result, if the variables have `units`, e.g. to check
+    /// a formula as soon as it is typed.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    /// use yaiouom::formula::*;
+    ///
+    /// let mut units = HashMap::new();
+    /// units.insert("side".to_string(), Meter::as_runtime());
+    ///
+    /// let formula = Formula::parse("side^2 / 2").unwrap();
+    /// assert_eq!(formula.unit(&units).unwrap(), Mul::<Meter, Meter>::as_runtime());
+    ///
+    /// let err = Formula::parse("side - side^2").unwrap().unit(&units).unwrap_err();
+    /// assert_eq!(err.to_string(), "Cannot subtract `m^2` from `m` (at 0..13)");
+    /// ```
+    pub fn unit(&self, units: &HashMap<String, RuntimeUnit>) -> Result<RuntimeUnit, FormulaError> {
+        eval(&self.root, units)
+    }
+}
+
+/// Parse and evaluate `formula` with the measures of `variables`.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use yaiouom::*;
+/// use yaiouom::formula::*;
+///
+/// let variables = HashMap::new();
+/// assert_eq!(evaluate("-(1 + 2) * 2^-1", &variables).unwrap(), DynMeasure::from(Dimensionless::new(-1.5)));
+///
+/// let err = evaluate("2 * (3 + x)", &variables).unwrap_err();
+/// assert_eq!(err.to_string(), "Unknown variable `x` (at 9..10)");
+///
+/// let err = evaluate("2 * (3 +", &variables).unwrap_err();
+/// assert_eq!(err.to_string(), "Expected a number, a variable or `(` (at 8..8)");
+/// ```
+pub fn evaluate(
+    formula: &str,
+    variables: &HashMap<String, DynMeasure<f64>>,
+) -> Result<DynMeasure<f64>, FormulaError> {
+    Formula::parse(formula)?.evaluate(variables)
+}
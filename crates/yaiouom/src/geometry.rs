@@ -0,0 +1,86 @@
+//! Areas and volumes of common shapes.
+//!
+//! Functions are generic over the unit of length `L`, so they work just as
+//! well with `Meter` as with any user-defined base unit.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::geometry::*;
+//!
+//! let area: Measure<f64, Area<Meter>> = circle_area(Meter::new(2.));
+//! assert_eq!(area.as_ref(), &(4. * std::f64::consts::PI));
+//! assert_eq!(circle_radius(area), Meter::new(2.));
+//!
+//! let volume: Measure<f64, Volume<Meter>> = cylinder_volume(Meter::new(1.), Meter::new(3.));
+//! assert_eq!(volume.as_ref(), &(3. * std::f64::consts::PI));
+//! ```
+
+use num_traits::float::{Float, FloatConst};
+
+use unit::*;
+
+/// The unit of area for unit of length `L`, e.g. m^2.
+pub type Area<L> = Mul<L, L>;
+
+/// The unit of volume for unit of length `L`, e.g. m^3.
+pub type Volume<L> = Mul<L, Mul<L, L>>;
+
+/// `T`'s representation of small integers.
+fn constant<T: Float>(value: u8) -> T {
+    T::from(value).unwrap()
+}
+
+pub fn rectangle_area<T: Float, L: Unit>(
+    width: Measure<T, L>,
+    height: Measure<T, L>,
+) -> Measure<T, Area<L>> {
+    width * height
+}
+
+pub fn cuboid_volume<T: Float, L: Unit>(
+    width: Measure<T, L>,
+    height: Measure<T, L>,
+    depth: Measure<T, L>,
+) -> Measure<T, Volume<L>> {
+    width * (height * depth)
+}
+
+pub fn circle_circumference<T: Float + FloatConst, L: Unit>(
+    radius: Measure<T, L>,
+) -> Measure<T, L> {
+    radius * (constant::<T>(2) * T::PI())
+}
+
+pub fn circle_area<T: Float + FloatConst, L: Unit>(radius: Measure<T, L>) -> Measure<T, Area<L>> {
+    radius * radius * T::PI()
+}
+
+/// The radius of a circle of area `area`.
+pub fn circle_radius<T: Float + FloatConst, L: Unit>(area: Measure<T, Area<L>>) -> Measure<T, L> {
+    (area / T::PI()).sqrt()
+}
+
+pub fn sphere_area<T: Float + FloatConst, L: Unit>(radius: Measure<T, L>) -> Measure<T, Area<L>> {
+    circle_area(radius) * constant::<T>(4)
+}
+
+pub fn sphere_volume<T: Float + FloatConst, L: Unit>(
+    radius: Measure<T, L>,
+) -> Measure<T, Volume<L>> {
+    radius * (radius * radius) * (constant::<T>(4) / constant::<T>(3) * T::PI())
+}
+
+pub fn cylinder_volume<T: Float + FloatConst, L: Unit>(
+    radius: Measure<T, L>,
+    height: Measure<T, L>,
+) -> Measure<T, Volume<L>> {
+    height * circle_area(radius)
+}
+
+pub fn cone_volume<T: Float + FloatConst, L: Unit>(
+    radius: Measure<T, L>,
+    height: Measure<T, L>,
+) -> Measure<T, Volume<L>> {
+    cylinder_volume(radius, height) / constant::<T>(3)
+}
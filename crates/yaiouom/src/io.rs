@@ -0,0 +1,292 @@
+//! Writing structs of measures to CSV, and reading them back.
+//!
+//! Use `#[derive(UnitReflect, UnitRecord)]` (feature `derive`) on a struct
+//! whose fields are measures (or structs which themselves derive both), then
+//! [`write_csv`](fn.write_csv.html) and [`read_csv`](fn.read_csv.html).
+//!
+//! Each column header holds the name of a field and its unit, e.g.
+//! `speed (m * s^-1)`. When reading, the headers are checked against the
+//! units expected by the struct, so that a file written with a unit is not
+//! accidentally read with another.
+//!
+//! This format is meant for numbers: values are neither quoted nor escaped,
+//! so they may not contain commas or newlines.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::io::*;
+//!
+//! let distances = vec![Meter::new(1.5), Meter::new(2.)];
+//! let mut file = vec![];
+//! write_csv(&mut file, &distances).unwrap();
+//! assert_eq!(std::str::from_utf8(&file).unwrap(), "value (m)\n1.5\n2\n");
+//!
+//! let read: Vec<Measure<f64, Meter>> = read_csv(&file[..]).unwrap();
+//! assert_eq!(read, distances);
+//!
+//! // Reading with the wrong unit fails.
+//! let err = read_csv::<_, Measure<f64, Second>>(&file[..]).unwrap_err();
+//! assert_eq!(err.to_string(), "Column `value`: expected unit `s`, found `m`");
+//! ```
+
+use std::io::{BufRead, Write};
+
+use reflect::*;
+use unit::*;
+
+/// A type made of measures, which may be written to and read from
+/// a list of values, one per field of its `UnitSchema`.
+///
+/// Implemented by `Measure` and by `#[derive(UnitRecord)]`.
+pub trait UnitRecord: UnitReflect + Sized {
+    /// Append the values of the fields of `self` to `record`.
+    fn write_record(&self, record: &mut Vec<String>);
+
+    /// Build a value from the values of its fields, consuming them from `record`.
+    fn read_record(record: &mut std::slice::Iter<&str>) -> Result<Self, InvalidValue>;
+}
+
+impl<T, U: Unit> UnitRecord for Measure<T, U>
+where
+    T: std::fmt::Display + std::str::FromStr,
+{
+    fn write_record(&self, record: &mut Vec<String>) {
+        record.push(self.as_ref().to_string());
+    }
+
+    fn read_record(record: &mut std::slice::Iter<&str>) -> Result<Self, InvalidValue> {
+        let value = record.next().ok_or(InvalidValue::Missing)?;
+        value
+            .trim()
+            .parse()
+            .map(Measure::new)
+            .map_err(|_| InvalidValue::Unparseable(value.to_string()))
+    }
+}
+
+/// A value that could not be read by `UnitRecord::read_record`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvalidValue {
+    /// There are fewer values than fields.
+    Missing,
+
+    /// The value could not be parsed.
+    Unparseable(String),
+}
+
+/// An error while reading a CSV file with `read_csv`.
+#[derive(Debug)]
+pub enum CsvError {
+    /// The underlying reader failed.
+    Io(std::io::Error),
+
+    /// The file is empty, so there are no headers.
+    MissingHeader,
+
+    /// The headers do not match the fields of the struct.
+    Columns {
+        expected: Vec<String>,
+        found: Vec<String>,
+    },
+
+    /// A column has the expected name, but not the expected unit.
+    Unit {
+        column: String,
+        expected: String,
+        found: String,
+    },
+
+    /// A value could not be read. Lines are numbered from 1, including headers.
+    Value {
+        line: usize,
+        column: String,
+        value: InvalidValue,
+    },
+
+    /// A line has more values than there are columns.
+    TooManyValues { line: usize },
+}
+
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match *self {
+            CsvError::Io(ref err) => write!(fmt, "{}", err),
+            CsvError::MissingHeader => write!(fmt, "Missing header"),
+            CsvError::Columns {
+                ref expected,
+                ref found,
+            } => write!(
+                fmt,
+                "Expected columns `{}`, found `{}`",
+                expected.join(","),
+                found.join(",")
+            ),
+            CsvError::Unit {
+                ref column,
+                ref expected,
+                ref found,
+            } => write!(
+                fmt,
+                "Column `{}`: expected unit `{}`, found `{}`",
+                column, expected, found
+            ),
+            CsvError::Value {
+                line,
+                ref column,
+                value: InvalidValue::Missing,
+            } => write!(fmt, "Line {}: missing value for column `{}`", line, column),
+            CsvError::Value {
+                line,
+                ref column,
+                value: InvalidValue::Unparseable(ref value),
+            } => write!(
+                fmt,
+                "Line {}: invalid value `{}` for column `{}`",
+                line, value, column
+            ),
+            CsvError::TooManyValues { line } => write!(fmt, "Line {}: too many values", line),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<std::io::Error> for CsvError {
+    fn from(err: std::io::Error) -> Self {
+        CsvError::Io(err)
+    }
+}
+
+/// The name of a column, for a field of the schema.
+fn column_name(path: &str) -> &str {
+    if path.is_empty() {
+        // A bare `Measure`.
+        "value"
+    } else {
+        path
+    }
+}
+
+/// Split a header such as `speed (m * s^-1)` into its name and its unit.
+fn parse_header(header: &str) -> (&str, &str) {
+    let header = header.trim();
+    if header.ends_with(')') {
+        if let Some(index) = header.rfind(" (") {
+            return (&header[..index], &header[index + 2..header.len() - 1]);
+        }
+    }
+    (header, "")
+}
+
+/// Write `rows` to `writer` as CSV, with a header naming each field and its unit.
+///
+/// # Errors
+///
+/// Fails if the writer fails, or with `std::io::ErrorKind::InvalidData`
+/// if a value contains a comma or a newline.
+pub fn write_csv<'a, W, T, I>(mut writer: W, rows: I) -> std::io::Result<()>
+where
+    W: Write,
+    T: UnitRecord + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    let headers: Vec<_> = T::unit_schema()
+        .fields()
+        .iter()
+        .map(|(path, unit)| {
+            let name = column_name(path);
+            let unit = unit.to_string();
+            if unit.is_empty() {
+                name.to_string()
+            } else {
+                format!("{} ({})", name, unit)
+            }
+        })
+        .collect();
+    writeln!(writer, "{}", headers.join(","))?;
+    let mut record = vec![];
+    for row in rows {
+        record.clear();
+        row.write_record(&mut record);
+        if let Some(value) = record
+            .iter()
+            .find(|value| value.contains(',') || value.contains('\n'))
+        {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Cannot write value `{}` to CSV", value),
+            ));
+        }
+        writeln!(writer, "{}", record.join(","))?;
+    }
+    Ok(())
+}
+
+/// Read rows written by `write_csv`, checking that the columns have the
+/// expected names and units.
+///
+/// Empty lines are skipped.
+pub fn read_csv<R, T>(reader: R) -> Result<Vec<T>, CsvError>
+where
+    R: BufRead,
+    T: UnitRecord,
+{
+    let schema = T::unit_schema();
+    let mut lines = reader.lines();
+    let header = match lines.next() {
+        None => return Err(CsvError::MissingHeader),
+        Some(header) => header?,
+    };
+
+    // Check the headers.
+    let expected: Vec<_> = schema
+        .fields()
+        .iter()
+        .map(|(path, _)| column_name(path).to_string())
+        .collect();
+    let headers: Vec<_> = header.split(',').map(parse_header).collect();
+    let found: Vec<_> = headers.iter().map(|&(name, _)| name.to_string()).collect();
+    if expected != found {
+        return Err(CsvError::Columns { expected, found });
+    }
+    for (&(name, found), (_, expected)) in headers.iter().zip(schema.fields()) {
+        if !symbol_matches(found, expected) {
+            return Err(CsvError::Unit {
+                column: name.to_string(),
+                expected: expected.to_string(),
+                found: found.to_string(),
+            });
+        }
+    }
+
+    // Read the values.
+    let mut rows = vec![];
+    for (index, line) in lines.enumerate() {
+        let line = line?;
+        let number = index + 2;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Vec<_> = line.split(',').collect();
+        let mut values = record.iter();
+        let row = T::read_record(&mut values).map_err(|value| {
+            let column = match value {
+                // All the values have been consumed.
+                InvalidValue::Missing => record.len(),
+                // The offending value is the last one consumed.
+                InvalidValue::Unparseable(_) => record.len() - values.len() - 1,
+            };
+            CsvError::Value {
+                line: number,
+                column: expected[column].clone(),
+                value,
+            }
+        })?;
+        if values.next().is_some() {
+            return Err(CsvError::TooManyValues { line: number });
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
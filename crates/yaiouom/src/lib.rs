@@ -1,4 +1,4 @@
-#![feature(rustc_attrs)]
+#![cfg_attr(feature = "checker", feature(rustc_attrs))]
 
 //! Units of measure.
 //!
@@ -43,6 +43,11 @@
 //! The linter guarantees that you'll never hit such dynamic
 //! panics.
 //!
+//! By default, this crate builds with stable Rust and does not
+//! need the checker. The checker itself requires a nightly rustc,
+//! and enables feature `checker` of this crate, which contains the
+//! attributes used by the checker to recognize units of measure.
+//!
 //! > You really should use the companion linter :) Also, please see
 //! > the documentation of [`unify`](struct.Measure.html#method.unify).
 //!
@@ -60,6 +65,25 @@
 //! of values. A value with a unit is a `Measure<T, U: Unit>`, where
 //! `T` can be any kind of number or number-like value.
 //!
+//! With feature `debug-math`, a panic during arithmetic on measures, e.g.
+//! an integer overflow, is reported at the location of the operation, with
+//! the units involved:
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! let result = std::panic::catch_unwind(|| Meter::new(i32::MAX) + Meter::new(1));
+//! # #[cfg(feature = "debug-math")]
+//! assert_eq!(
+//!     result.unwrap_err().downcast_ref::<String>().unwrap(),
+//!     "attempt to add with overflow, in `Measure<i32, m> + Measure<i32, m>`"
+//! );
+//! ```
+//!
+//! As usual, integer overflows are only detected if overflow checks are
+//! enabled, e.g. in debug builds.
+//!
 //!
 //!
 //! # Limitations
@@ -83,10 +107,40 @@
 //! the difference between two dates in seconds is a duration in seconds.
 //! The difference between two ºC temperatures is a value that may be
 //! multiplied or divided. We do not attempt to differentiate between
-//! these things.
+//! these things, although traits `AddAcross` and `SubAcross` let you
+//! add or subtract measures with distinct units, e.g. a date and a duration.
 
+#[cfg(feature = "dimensioned")]
+extern crate dimensioned as dimensioned_crate;
 extern crate itertools;
+#[cfg(feature = "nalgebra")]
+extern crate nalgebra;
+#[cfg(feature = "num-complex")]
+extern crate num_complex;
 extern crate num_traits;
+#[cfg(feature = "pyo3")]
+extern crate pyo3;
+#[cfg(feature = "rand")]
+extern crate rand as rand_crate;
+#[cfg(feature = "rand")]
+extern crate rand_distr;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "uom")]
+extern crate uom as uom_crate;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+#[cfg(feature = "derive")]
+extern crate yaiouom_derive;
+
+#[cfg(all(feature = "derive", feature = "serde"))]
+pub use yaiouom_derive::UnitSerialize;
+#[cfg(feature = "derive")]
+pub use yaiouom_derive::{
+    auto_unify, trusted, HasUnit, UnitBuilder, UnitDebug, UnitRecord, UnitReflect,
+};
 
 /// Seal mechanism, to ensure that we cannot implement private traits
 /// from outside this module.
@@ -98,4 +152,96 @@ mod private {
 mod unit;
 pub use unit::*;
 
+mod dynamic;
+pub use dynamic::*;
+
+mod map;
+pub use map::*;
+
+mod symbol;
+pub use symbol::{MAX_SYMBOL_DIMENSIONS, MAX_SYMBOL_LEN};
+
+/// Register base units, so that `registry::all_units` lists them.
+///
+/// Without feature `registry`, this macro does nothing, so libraries may
+/// register their units unconditionally and leave the choice of the
+/// feature to the program.
+///
+/// ```
+/// #[macro_use]
+/// extern crate yaiouom;
+///
+/// pub struct Euro;
+/// impl yaiouom::BaseUnit for Euro {
+///     const NAMESPACE: &'static str = "finance";
+///     const NAME: &'static str = "EUR";
+/// }
+/// pub struct Dollar;
+/// impl yaiouom::BaseUnit for Dollar {
+///     const NAMESPACE: &'static str = "finance";
+///     const NAME: &'static str = "USD";
+/// }
+/// register_units!(Euro, Dollar);
+/// # fn main() {}
+/// ```
+#[cfg(feature = "registry")]
+#[macro_export]
+macro_rules! register_units {
+    ($($unit:ty),* $(,)*) => {
+        $(
+            const _: () = {
+                #[$crate::registry::__linkme::distributed_slice($crate::registry::UNITS)]
+                #[linkme(crate = $crate::registry::__linkme)]
+                static UNIT: $crate::registry::RegisteredUnit =
+                    $crate::registry::RegisteredUnit::of::<$unit>();
+            };
+        )*
+    };
+}
+
+/// Register base units, so that `registry::all_units` lists them.
+///
+/// Without feature `registry`, this macro does nothing, so libraries may
+/// register their units unconditionally and leave the choice of the
+/// feature to the program.
+#[cfg(not(feature = "registry"))]
+#[macro_export]
+macro_rules! register_units {
+    ($($unit:ty),* $(,)*) => {};
+}
+
+#[cfg(feature = "tracing")]
+mod trace;
+
+#[cfg(feature = "audit")]
+pub mod audit;
+pub mod builder;
+#[cfg(feature = "num-complex")]
+pub mod complex;
+#[cfg(feature = "dimensioned")]
+pub mod dimensioned;
+pub mod dosing;
+pub mod fluids;
+pub mod formula;
+pub mod geometry;
+pub mod io;
+pub mod logics;
+pub mod matrix;
+pub mod pretty;
+#[cfg(feature = "pyo3")]
+pub mod python;
+#[cfg(feature = "rand")]
+pub mod rand;
+pub mod reflect;
+#[cfg(feature = "registry")]
+pub mod registry;
+pub mod rotation;
+pub mod sensor;
 pub mod si;
+#[cfg(feature = "tests")]
+pub mod testing;
+#[cfg(feature = "uom")]
+pub mod uom;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
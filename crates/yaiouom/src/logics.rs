@@ -0,0 +1,201 @@
+//! Embedded logics: proofs that two units of measure are equal.
+//!
+//! The Rust type system does not know that, for instance,
+//! `Mul<A, B> == Mul<B, A>`. The companion linter does, which is what
+//! makes [`unify`](../struct.Measure.html#method.unify) safe. If you
+//! cannot use the linter, you may instead write down why two units are
+//! equal, using the combinators of this module. Each combinator is an
+//! axiom of abelian groups (commutativity, associativity, neutral element,
+//! inverse) or a congruence rule, so a proof built from them is checked
+//! by the Rust type system alone.
+//!
+//! Proofs are used with [`rewrite`](../struct.Measure.html#method.rewrite).
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::logics::*;
+//!
+//! // A function that only accepts proofs that `A == B`.
+//! fn check<A: Unit, B: Unit, P: Proof<Left = A, Right = B>>() {}
+//!
+//! // m * s == s * m
+//! check::<Mul<Meter, Second>, Mul<Second, Meter>, PComm<Meter, Second>>();
+//!
+//! // (m * s) * s^-1 == m * (s * s^-1)
+//! check::<
+//!     Mul<Mul<Meter, Second>, Inv<Second>>,
+//!     Mul<Meter, Mul<Second, Inv<Second>>>,
+//!     PAssoc<Meter, Second, Inv<Second>>
+//! >();
+//!
+//! // m * (s * s^-1) == m * Dimensionless
+//! check::<
+//!     Mul<Meter, Mul<Second, Inv<Second>>>,
+//!     Mul<Meter, Dimensionless>,
+//!     PMul<PId<Meter>, PInverse<Second>>
+//! >();
+//! ```
+//!
+//! Proofs cannot be forged:
+//!
+//! ```compile_fail
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::logics::*;
+//!
+//! fn check<A: Unit, B: Unit, P: Proof<Left = A, Right = B>>() {}
+//!
+//! // m * s != m * m
+//! check::<Mul<Meter, Second>, Mul<Meter, Meter>, PComm<Meter, Second>>();
+//! ```
+
+use std::marker::PhantomData;
+
+use unit::*;
+
+/// Seal mechanism, to ensure that proofs may only be built
+/// with the combinators of this module.
+mod private {
+    pub trait Sealed {}
+}
+
+/// A proof that units `Left` and `Right` are equal.
+///
+/// This trait is sealed: it is implemented only by the combinators
+/// of this module.
+pub trait Proof: private::Sealed {
+    type Left: Unit;
+    type Right: Unit;
+}
+
+/// `A == A`.
+pub struct PId<A: Unit> {
+    unit: PhantomData<A>,
+}
+impl<A: Unit> private::Sealed for PId<A> {}
+impl<A: Unit> Proof for PId<A> {
+    type Left = A;
+    type Right = A;
+}
+
+/// Commutativity: `A * B == B * A`.
+pub struct PComm<A: Unit, B: Unit> {
+    left: PhantomData<A>,
+    right: PhantomData<B>,
+}
+impl<A: Unit, B: Unit> private::Sealed for PComm<A, B> {}
+impl<A: Unit, B: Unit> Proof for PComm<A, B> {
+    type Left = Mul<A, B>;
+    type Right = Mul<B, A>;
+}
+
+/// Associativity: `(A * B) * C == A * (B * C)`.
+pub struct PAssoc<A: Unit, B: Unit, C: Unit> {
+    left: PhantomData<A>,
+    middle: PhantomData<B>,
+    right: PhantomData<C>,
+}
+impl<A: Unit, B: Unit, C: Unit> private::Sealed for PAssoc<A, B, C> {}
+impl<A: Unit, B: Unit, C: Unit> Proof for PAssoc<A, B, C> {
+    type Left = Mul<Mul<A, B>, C>;
+    type Right = Mul<A, Mul<B, C>>;
+}
+
+/// Neutral element: `A * Dimensionless == A`.
+pub struct PDimensionless<A: Unit> {
+    unit: PhantomData<A>,
+}
+impl<A: Unit> private::Sealed for PDimensionless<A> {}
+impl<A: Unit> Proof for PDimensionless<A> {
+    type Left = Mul<A, Dimensionless>;
+    type Right = A;
+}
+
+/// Inverse: `A * A^-1 == Dimensionless`.
+pub struct PInverse<A: Unit> {
+    unit: PhantomData<A>,
+}
+impl<A: Unit> private::Sealed for PInverse<A> {}
+impl<A: Unit> Proof for PInverse<A> {
+    type Left = Mul<A, Inv<A>>;
+    type Right = Dimensionless;
+}
+
+/// Inverse of a product: `(A * B)^-1 == A^-1 * B^-1`.
+///
+/// This, as well as `PInvInv` and `PInvDimensionless`, may be derived
+/// from the axioms, albeit tediously. They are provided as shortcuts.
+pub struct PInvMul<A: Unit, B: Unit> {
+    left: PhantomData<A>,
+    right: PhantomData<B>,
+}
+impl<A: Unit, B: Unit> private::Sealed for PInvMul<A, B> {}
+impl<A: Unit, B: Unit> Proof for PInvMul<A, B> {
+    type Left = Inv<Mul<A, B>>;
+    type Right = Mul<Inv<A>, Inv<B>>;
+}
+
+/// Inverse of an inverse: `(A^-1)^-1 == A`.
+pub struct PInvInv<A: Unit> {
+    unit: PhantomData<A>,
+}
+impl<A: Unit> private::Sealed for PInvInv<A> {}
+impl<A: Unit> Proof for PInvInv<A> {
+    type Left = Inv<Inv<A>>;
+    type Right = A;
+}
+
+/// Inverse of the neutral element: `Dimensionless^-1 == Dimensionless`.
+pub struct PInvDimensionless;
+impl private::Sealed for PInvDimensionless {}
+impl Proof for PInvDimensionless {
+    type Left = Inv<Dimensionless>;
+    type Right = Dimensionless;
+}
+
+/// Congruence of products: if `P` proves `A == B` and `Q` proves `C == D`,
+/// then `A * C == B * D`.
+pub struct PMul<P: Proof, Q: Proof> {
+    left: PhantomData<P>,
+    right: PhantomData<Q>,
+}
+impl<P: Proof, Q: Proof> private::Sealed for PMul<P, Q> {}
+impl<P: Proof, Q: Proof> Proof for PMul<P, Q> {
+    type Left = Mul<P::Left, Q::Left>;
+    type Right = Mul<P::Right, Q::Right>;
+}
+
+/// Congruence of inverses: if `P` proves `A == B`, then `A^-1 == B^-1`.
+pub struct PInv<P: Proof> {
+    inner: PhantomData<P>,
+}
+impl<P: Proof> private::Sealed for PInv<P> {}
+impl<P: Proof> Proof for PInv<P> {
+    type Left = Inv<P::Left>;
+    type Right = Inv<P::Right>;
+}
+
+/// Symmetry: if `P` proves `A == B`, then `B == A`.
+pub struct PSym<P: Proof> {
+    inner: PhantomData<P>,
+}
+impl<P: Proof> private::Sealed for PSym<P> {}
+impl<P: Proof> Proof for PSym<P> {
+    type Left = P::Right;
+    type Right = P::Left;
+}
+
+/// Transitivity: if `P` proves `A == B` and `Q` proves `B == C`,
+/// then `A == C`.
+///
+/// This is the mechanism used to compose several steps into a single proof.
+pub struct PThen<P: Proof, Q: Proof<Left = P::Right>> {
+    first: PhantomData<P>,
+    then: PhantomData<Q>,
+}
+impl<P: Proof, Q: Proof<Left = P::Right>> private::Sealed for PThen<P, Q> {}
+impl<P: Proof, Q: Proof<Left = P::Right>> Proof for PThen<P, Q> {
+    type Left = P::Left;
+    type Right = Q::Right;
+}
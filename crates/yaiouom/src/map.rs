@@ -0,0 +1,137 @@
+//! Collections indexed by units.
+use dynamic::{DynMeasure, DynUnit};
+use unit::*;
+
+use std;
+use std::any::TypeId;
+use std::collections::HashMap;
+
+/// A map holding at most one value per unit.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let mut config = UnitMap::new();
+/// config.insert(Meter::new(3.0));
+/// config.insert(Second::new(10.0));
+///
+/// assert_eq!(config.get::<Meter>(), Some(Meter::new(3.0)));
+/// assert_eq!(config.get::<Kg>(), None);
+///
+/// let mut units: Vec<_> = config.iter()
+///     .map(|measure| measure.unit().to_string())
+///     .collect();
+/// units.sort();
+/// assert_eq!(units, vec!["m", "s"]);
+/// ```
+///
+/// Note that values are indexed by the type of their unit. Since
+/// `Mul<Meter, Second>` and `Mul<Second, Meter>` are distinct types,
+/// they are also distinct keys.
+pub struct UnitMap<T> {
+    values: HashMap<TypeId, (RuntimeUnit, T)>,
+}
+
+impl<T> UnitMap<T> {
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Insert a value, returning the previous value with the same unit, if any.
+    pub fn insert<U: Unit + 'static>(&mut self, measure: Measure<T, U>) -> Option<Measure<T, U>> {
+        self.values
+            .insert(TypeId::of::<U>(), (U::as_runtime(), measure.value))
+            .map(|(_, value)| Measure::new(value))
+    }
+
+    /// Insert a value with a unit chosen at runtime, returning the previous
+    /// value with the same unit, if any.
+    ///
+    /// The value may then be read back with `get`:
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let unit: Box<dyn DynUnit> = Box::new(Meter);
+    /// let mut config = UnitMap::new();
+    /// config.insert_dyn(&*unit, 3.0).unwrap();
+    /// assert_eq!(config.get::<Meter>(), Some(Meter::new(3.0)));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// If `unit` does not represent a static unit (see
+    /// `DynUnit::unit_type_id`), `value` is returned unchanged.
+    pub fn insert_dyn(&mut self, unit: &dyn DynUnit, value: T) -> Result<Option<T>, T> {
+        match unit.unit_type_id() {
+            Some(id) => Ok(self
+                .values
+                .insert(id, (unit.runtime_unit(), value))
+                .map(|(_, value)| value)),
+            None => Err(value),
+        }
+    }
+
+    /// Get a copy of the value with unit `U`, if any.
+    pub fn get<U: Unit + 'static>(&self) -> Option<Measure<T, U>>
+    where
+        T: Clone,
+    {
+        self.values
+            .get(&TypeId::of::<U>())
+            .map(|(_, value)| Measure::new(value.clone()))
+    }
+
+    /// Remove the value with unit `U`, if any.
+    pub fn remove<U: Unit + 'static>(&mut self) -> Option<Measure<T, U>> {
+        self.values
+            .remove(&TypeId::of::<U>())
+            .map(|(_, value)| Measure::new(value))
+    }
+
+    /// `true` if there is a value with unit `U`.
+    pub fn contains<U: Unit + 'static>(&self) -> bool {
+        self.values.contains_key(&TypeId::of::<U>())
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Iterate through the values, in an unspecified order.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item = DynMeasure<&'a T>> + 'a {
+        self.values
+            .values()
+            .map(|(unit, value)| DynMeasure::new(value, unit.clone()))
+    }
+}
+
+impl<T> Default for UnitMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> IntoIterator for UnitMap<T> {
+    type Item = DynMeasure<T>;
+    type IntoIter = std::iter::Map<
+        std::collections::hash_map::IntoIter<TypeId, (RuntimeUnit, T)>,
+        fn((TypeId, (RuntimeUnit, T))) -> DynMeasure<T>,
+    >;
+
+    /// Iterate through the values, in an unspecified order.
+    fn into_iter(self) -> Self::IntoIter {
+        fn to_dyn<T>((_, (unit, value)): (TypeId, (RuntimeUnit, T))) -> DynMeasure<T> {
+            DynMeasure::new(value, unit)
+        }
+        self.values.into_iter().map(to_dyn)
+    }
+}
@@ -0,0 +1,418 @@
+//! Vectors and matrices whose entries have distinct units, e.g. the state
+//! vectors of control theory.
+//!
+//! The units of a `UnitVector` are a tuple of units, e.g. a position and
+//! a speed for `UnitVector<f64, (Meter, Mul<Meter, Inv<Second>>)>`.
+//!
+//! A `UnitMatrix<T, R, C>` maps a `UnitVector<T, C>` to a `UnitVector<T, R>`,
+//! so the entry at row `i` and column `j` has unit `R_i / C_j`. Entries are
+//! read and written with their unit, and products only build if the units of
+//! both sides match, so the unit of each entry of a product is correct by
+//! construction.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::matrix::*;
+//!
+//! type State = (Meter, Mul<Meter, Inv<Second>>);
+//!
+//! // A body moving at 2 m/s.
+//! let state: UnitVector<f64, State> = (Meter::new(1.), Measure::new(2.)).into();
+//!
+//! // Its position after 3 s.
+//! let dt: Measure<f64, Second> = Second::new(3.);
+//! let transition: UnitMatrix<f64, State, State> = UnitMatrix::zero()
+//!     .set::<I0, I0>(Measure::new(1.))
+//!     .set::<I0, I1>(dt.unify())
+//!     .set::<I1, I1>(Measure::new(1.));
+//!
+//! let state = transition * state;
+//! assert_eq!(state.get::<I0>(), Meter::new(7.));
+//! assert_eq!(state.get::<I1>().as_ref(), &2.);
+//! ```
+//!
+//! Vectors and matrices have at most 4 rows and 4 columns.
+
+use std::marker::PhantomData;
+
+use num_traits::identities::Zero;
+
+use unit::*;
+
+/// A type-level index in a `UnitList`, from `I0` to `I3`.
+pub trait Position {
+    const INDEX: usize;
+}
+
+pub struct I0;
+pub struct I1;
+pub struct I2;
+pub struct I3;
+impl Position for I0 {
+    const INDEX: usize = 0;
+}
+impl Position for I1 {
+    const INDEX: usize = 1;
+}
+impl Position for I2 {
+    const INDEX: usize = 2;
+}
+impl Position for I3 {
+    const INDEX: usize = 3;
+}
+
+/// A tuple of 1 to 4 units, e.g. `(Meter, Second)`.
+pub trait UnitList {
+    const LEN: usize;
+
+    /// Add the unit at position `index` to `repr`, in positive or negative
+    /// position, as `Unit::add_to_runtime`.
+    fn add_to_runtime(
+        index: usize,
+        repr: &mut RuntimeUnit,
+        positive: bool,
+    ) -> Result<(), ExponentOverflow>;
+
+    /// The unit at position `index`.
+    fn as_runtime(index: usize) -> RuntimeUnit {
+        let mut repr = Dimensionless::as_runtime();
+        Self::add_to_runtime(index, &mut repr, true).unwrap();
+        repr
+    }
+}
+
+/// The unit at position `I` of a tuple of units.
+pub trait UnitAt<I: Position>: UnitList {
+    type Unit: Unit;
+}
+
+macro_rules! impl_unit_list {
+    ($(($($unit:ident: $index:tt),*) => $len:expr;)*) => {
+        $(
+            impl<$($unit: Unit),*> UnitList for ($($unit,)*) {
+                const LEN: usize = $len;
+                fn add_to_runtime(
+                    index: usize,
+                    repr: &mut RuntimeUnit,
+                    positive: bool,
+                ) -> Result<(), ExponentOverflow> {
+                    match index {
+                        $($index => $unit::add_to_runtime(repr, positive),)*
+                        _ => panic!("Index {} out of bounds for {} units", index, $len),
+                    }
+                }
+            }
+        )*
+    }
+}
+
+impl_unit_list! {
+    (A: 0) => 1;
+    (A: 0, B: 1) => 2;
+    (A: 0, B: 1, C: 2) => 3;
+    (A: 0, B: 1, C: 2, D: 3) => 4;
+}
+
+macro_rules! impl_unit_at {
+    ($(($($unit:ident),*): $index:ident => $at:ident;)*) => {
+        $(
+            impl<$($unit: Unit),*> UnitAt<$index> for ($($unit,)*) {
+                type Unit = $at;
+            }
+        )*
+    }
+}
+
+impl_unit_at! {
+    (A): I0 => A;
+    (A, B): I0 => A;
+    (A, B): I1 => B;
+    (A, B, C): I0 => A;
+    (A, B, C): I1 => B;
+    (A, B, C): I2 => C;
+    (A, B, C, D): I0 => A;
+    (A, B, C, D): I1 => B;
+    (A, B, C, D): I2 => C;
+    (A, B, C, D): I3 => D;
+}
+
+/// A vector of values of type `T`, whose units are the tuple `U`.
+pub struct UnitVector<T, U: UnitList> {
+    values: Vec<T>,
+    units: PhantomData<U>,
+}
+
+impl<T, U: UnitList> UnitVector<T, U> {
+    /// A vector whose entries are all zero.
+    pub fn zero() -> Self
+    where
+        T: Zero + Clone,
+    {
+        UnitVector {
+            values: vec![T::zero(); U::LEN],
+            units: PhantomData,
+        }
+    }
+
+    /// The entry at position `I`.
+    pub fn get<I: Position>(&self) -> Measure<T, <U as UnitAt<I>>::Unit>
+    where
+        U: UnitAt<I>,
+        T: Clone,
+    {
+        Measure::new(self.values[I::INDEX].clone())
+    }
+
+    /// Replace the entry at position `I`.
+    pub fn set<I: Position>(mut self, value: Measure<T, <U as UnitAt<I>>::Unit>) -> Self
+    where
+        U: UnitAt<I>,
+    {
+        self.values[I::INDEX] = value.value;
+        self
+    }
+
+    /// The values of the entries, without their units.
+    pub fn as_values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+macro_rules! impl_vector_from_tuple {
+    ($(($($unit:ident: $index:tt),*);)*) => {
+        $(
+            impl<T, $($unit: Unit),*> From<($(Measure<T, $unit>,)*)> for UnitVector<T, ($($unit,)*)> {
+                fn from(measures: ($(Measure<T, $unit>,)*)) -> Self {
+                    UnitVector {
+                        values: vec![$(measures.$index.value),*],
+                        units: PhantomData,
+                    }
+                }
+            }
+        )*
+    }
+}
+
+impl_vector_from_tuple! {
+    (A: 0);
+    (A: 0, B: 1);
+    (A: 0, B: 1, C: 2);
+    (A: 0, B: 1, C: 2, D: 3);
+}
+
+impl<T, U: UnitList> Clone for UnitVector<T, U>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        UnitVector {
+            values: self.values.clone(),
+            units: PhantomData,
+        }
+    }
+}
+
+impl<T, U: UnitList> PartialEq for UnitVector<T, U>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<T, U: UnitList> std::fmt::Debug for UnitVector<T, U>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "[")?;
+        for (index, value) in self.values.iter().enumerate() {
+            if index != 0 {
+                write!(fmt, ", ")?;
+            }
+            write!(fmt, "{:?}{}", value, U::as_runtime(index))?;
+        }
+        write!(fmt, "]")
+    }
+}
+
+/// Vectors with the same units may be added entry-wise.
+impl<T, U: UnitList> std::ops::Add<Self> for UnitVector<T, U>
+where
+    T: std::ops::Add,
+{
+    type Output = UnitVector<T::Output, U>;
+    fn add(self, rhs: Self) -> Self::Output {
+        UnitVector {
+            values: self
+                .values
+                .into_iter()
+                .zip(rhs.values)
+                .map(|(a, b)| a + b)
+                .collect(),
+            units: PhantomData,
+        }
+    }
+}
+
+/// The unit of the entry at row `I` and column `J` of a matrix with row units
+/// `R` and column units `C`.
+pub type EntryUnit<R, C, I, J> = Mul<<R as UnitAt<I>>::Unit, Inv<<C as UnitAt<J>>::Unit>>;
+
+/// A matrix of values of type `T`, which maps vectors with units `C` to
+/// vectors with units `R`.
+///
+/// Values are stored in column-major order.
+pub struct UnitMatrix<T, R: UnitList, C: UnitList> {
+    values: Vec<T>,
+    units: PhantomData<(R, C)>,
+}
+
+impl<T, R: UnitList, C: UnitList> UnitMatrix<T, R, C> {
+    /// A matrix whose entries are all zero.
+    pub fn zero() -> Self
+    where
+        T: Zero + Clone,
+    {
+        UnitMatrix {
+            values: vec![T::zero(); R::LEN * C::LEN],
+            units: PhantomData,
+        }
+    }
+
+    /// The entry at row `I` and column `J`.
+    pub fn get<I: Position, J: Position>(&self) -> Measure<T, EntryUnit<R, C, I, J>>
+    where
+        R: UnitAt<I>,
+        C: UnitAt<J>,
+        T: Clone,
+    {
+        Measure::new(self.values[J::INDEX * R::LEN + I::INDEX].clone())
+    }
+
+    /// Replace the entry at row `I` and column `J`.
+    pub fn set<I: Position, J: Position>(mut self, value: Measure<T, EntryUnit<R, C, I, J>>) -> Self
+    where
+        R: UnitAt<I>,
+        C: UnitAt<J>,
+    {
+        self.values[J::INDEX * R::LEN + I::INDEX] = value.value;
+        self
+    }
+
+    /// The values of the entries, without their units, in column-major order.
+    pub fn as_values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T, R: UnitList, C: UnitList> Clone for UnitMatrix<T, R, C>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        UnitMatrix {
+            values: self.values.clone(),
+            units: PhantomData,
+        }
+    }
+}
+
+impl<T, R: UnitList, C: UnitList> PartialEq for UnitMatrix<T, R, C>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<T, R: UnitList, C: UnitList> std::fmt::Debug for UnitMatrix<T, R, C>
+where
+    T: std::fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "[")?;
+        for i in 0..R::LEN {
+            if i != 0 {
+                write!(fmt, "; ")?;
+            }
+            for j in 0..C::LEN {
+                if j != 0 {
+                    write!(fmt, ", ")?;
+                }
+                let mut unit = R::as_runtime(i);
+                C::add_to_runtime(j, &mut unit, false).unwrap();
+                write!(fmt, "{:?}{}", self.values[j * R::LEN + i], unit)?;
+            }
+        }
+        write!(fmt, "]")
+    }
+}
+
+/// Apply a matrix to a vector whose units are its column units.
+impl<T, R: UnitList, C: UnitList> std::ops::Mul<UnitVector<T, C>> for UnitMatrix<T, R, C>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T>,
+{
+    type Output = UnitVector<T, R>;
+    fn mul(self, rhs: UnitVector<T, C>) -> UnitVector<T, R> {
+        let mut values = vec![T::zero(); R::LEN];
+        for (j, x) in rhs.values.iter().enumerate() {
+            for (i, value) in values.iter_mut().enumerate() {
+                *value = value.clone() + self.values[j * R::LEN + i].clone() * x.clone();
+            }
+        }
+        UnitVector {
+            values,
+            units: PhantomData,
+        }
+    }
+}
+
+/// Compose two matrices, if the column units of the left one are the row
+/// units of the right one.
+impl<T, R: UnitList, K: UnitList, C: UnitList> std::ops::Mul<UnitMatrix<T, K, C>>
+    for UnitMatrix<T, R, K>
+where
+    T: Zero + Clone + std::ops::Mul<Output = T>,
+{
+    type Output = UnitMatrix<T, R, C>;
+    fn mul(self, rhs: UnitMatrix<T, K, C>) -> UnitMatrix<T, R, C> {
+        let mut values = vec![T::zero(); R::LEN * C::LEN];
+        for j in 0..C::LEN {
+            for k in 0..K::LEN {
+                let x = &rhs.values[j * K::LEN + k];
+                for i in 0..R::LEN {
+                    let value = &mut values[j * R::LEN + i];
+                    *value = value.clone() + self.values[k * R::LEN + i].clone() * x.clone();
+                }
+            }
+        }
+        UnitMatrix {
+            values,
+            units: PhantomData,
+        }
+    }
+}
+
+/// Matrices with the same units may be added entry-wise.
+impl<T, R: UnitList, C: UnitList> std::ops::Add<Self> for UnitMatrix<T, R, C>
+where
+    T: std::ops::Add,
+{
+    type Output = UnitMatrix<T::Output, R, C>;
+    fn add(self, rhs: Self) -> Self::Output {
+        UnitMatrix {
+            values: self
+                .values
+                .into_iter()
+                .zip(rhs.values)
+                .map(|(a, b)| a + b)
+                .collect(),
+            units: PhantomData,
+        }
+    }
+}
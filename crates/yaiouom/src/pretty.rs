@@ -0,0 +1,198 @@
+//! Displaying structs of measures along with their units.
+//!
+//! Use `#[derive(UnitDebug)]` (feature `derive`) on a struct whose fields
+//! are measures (or structs which themselves derive `UnitDebug`) to obtain
+//! a `Debug` implementation that shows each field with its unit, as well as
+//! an implementation of `UnitFields`, which [`table`](fn.table.html) uses
+//! to print the struct as a table.
+//!
+//! Similarly, `#[derive(UnitSerialize)]` (features `derive` and `serde`)
+//! implements `serde::Serialize`, with each measure serialized as
+//! `{ "value": ..., "unit": "..." }`.
+
+use unit::*;
+
+/// One line of a [`table`](fn.table.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitField {
+    /// The path to the field, e.g. `position.x`. Empty for a `Measure`.
+    pub path: String,
+
+    /// The value of the field, formatted with `Debug`.
+    pub value: String,
+
+    /// The unit of the field.
+    pub unit: RuntimeUnit,
+}
+
+/// A value made of measures, which may be listed along with their units.
+///
+/// Implemented by `Measure` and by `#[derive(UnitDebug)]`.
+///
+/// # Performance note
+///
+/// This trait is fine for debugging, but should not be used in a tight loop.
+pub trait UnitFields {
+    /// The measures contained in this value.
+    fn unit_fields(&self) -> Vec<UnitField>;
+}
+
+impl<T, U: Unit> UnitFields for Measure<T, U>
+where
+    T: std::fmt::Debug,
+{
+    fn unit_fields(&self) -> Vec<UnitField> {
+        vec![UnitField {
+            path: String::new(),
+            value: format!("{:?}", self.as_ref()),
+            unit: U::as_runtime(),
+        }]
+    }
+}
+
+/// A wrapper used by the code generated by `#[derive(UnitDebug)]` and
+/// `#[derive(UnitSerialize)]` to display a field along with its unit.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+/// use yaiouom::pretty::WithUnit;
+///
+/// let speed = Meter::new(3.5) / Second::new(1.);
+/// assert_eq!(format!("{:?}", WithUnit(&speed)), "3.5 m * s^-1");
+/// assert_eq!(format!("{:?}", WithUnit(&Dimensionless::new(2))), "2");
+/// ```
+pub struct WithUnit<'a, F: 'a + ?Sized>(pub &'a F);
+
+impl<'a, F> std::fmt::Debug for WithUnit<'a, F>
+where
+    F: std::fmt::Debug + UnitFields + ?Sized,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        let mut fields = self.0.unit_fields();
+        if fields.len() != 1 || !fields[0].path.is_empty() {
+            // A struct of measures, which knows how to display itself.
+            return self.0.fmt(fmt);
+        }
+        let field = fields.pop().unwrap();
+        let unit = field.unit.to_string();
+        if unit.is_empty() {
+            write!(fmt, "{}", field.value)
+        } else {
+            write!(fmt, "{} {}", field.value, unit)
+        }
+    }
+}
+
+/// Format the measures contained in a value as a table, one line per field.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+/// use yaiouom::pretty::*;
+///
+/// struct State {
+///     position: Measure<f64, Meter>,
+///     speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+/// }
+/// impl UnitFields for State {
+///     fn unit_fields(&self) -> Vec<UnitField> {
+///         let mut fields = self.position.unit_fields();
+///         fields[0].path = "position".to_string();
+///         let mut speed = self.speed.unit_fields();
+///         speed[0].path = "speed".to_string();
+///         fields.extend(speed);
+///         fields
+///     }
+/// }
+///
+/// let state = State {
+///     position: Meter::new(1.5),
+///     speed: Meter::new(10.) / Second::new(1.),
+/// };
+/// assert_eq!(table(&state), "\
+/// field    | value | unit
+/// position | 1.5   | m
+/// speed    | 10.0  | m * s^-1
+/// ");
+/// ```
+pub fn table<F: UnitFields + ?Sized>(value: &F) -> String {
+    let header = UnitField {
+        path: "field".to_string(),
+        value: "value".to_string(),
+        unit: Dimensionless::as_runtime(),
+    };
+    let fields = value.unit_fields();
+    let width = |column: &dyn Fn(&UnitField) -> usize| {
+        fields
+            .iter()
+            .map(column)
+            .chain(Some(column(&header)))
+            .max()
+            .unwrap()
+    };
+    let path_width = width(&|field| field.path.chars().count());
+    let value_width = width(&|field| field.value.chars().count());
+
+    let mut result = format!(
+        "{:path$} | {:value$} | unit\n",
+        header.path,
+        header.value,
+        path = path_width,
+        value = value_width
+    );
+    for field in &fields {
+        let line = format!(
+            "{:path$} | {:value$} | {}",
+            field.path,
+            field.value,
+            field.unit,
+            path = path_width,
+            value = value_width
+        );
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+    result
+}
+
+/// A value made of measures, which may be serialized along with their units.
+///
+/// Implemented by `Measure`, as `{ "value": ..., "unit": "..." }`, and by
+/// `#[derive(UnitSerialize)]`.
+#[cfg(feature = "serde")]
+pub trait UnitSerialize {
+    /// Serialize this value, along with its units.
+    fn serialize_with_unit<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+#[cfg(feature = "serde")]
+impl<T, U: Unit> UnitSerialize for Measure<T, U>
+where
+    T: serde::Serialize,
+{
+    fn serialize_with_unit<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Measure", 2)?;
+        state.serialize_field("value", self.as_ref())?;
+        state.serialize_field("unit", &U::as_runtime().to_string())?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'a, F> serde::Serialize for WithUnit<'a, F>
+where
+    F: UnitSerialize + ?Sized,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_with_unit(serializer)
+    }
+}
+
+/// Used by the code generated by `#[derive(UnitSerialize)]`.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub mod __serde {
+    pub use serde::{ser, Serialize, Serializer};
+}
@@ -0,0 +1,100 @@
+//! Exposing measures to Python with crate `pyo3`, e.g. for scripting
+//! bridges.
+//!
+//! Measures cross the boundary as instances of Python class `Measure`
+//! (`PyMeasure` in Rust), with attributes `value` and `unit`, the latter
+//! being the symbol of the unit, as in `Measure::to_tagged`. Functions
+//! exposed with `#[pyfunction]` may take and return `Measure<f64, U>`
+//! directly: the units of arguments are checked, and a mismatch raises a
+//! `ValueError`.
+//!
+//! The macros of `pyo3` require edition 2018 or later in the calling crate.
+//!
+//! ```edition2018
+//! extern crate pyo3;
+//! extern crate yaiouom;
+//!
+//! use pyo3::exceptions::PyValueError;
+//! use pyo3::prelude::*;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::python::*;
+//!
+//! #[pyfunction]
+//! fn speed(distance: Measure<f64, Meter>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
+//!     distance / duration
+//! }
+//!
+//! # fn main() {
+//! pyo3::prepare_freethreaded_python();
+//! Python::with_gil(|py| {
+//!     let speed = wrap_pyfunction_bound!(speed, py).unwrap();
+//!
+//!     let result = speed.call1((PyMeasure::new(10., "m".to_string()), PyMeasure::from(Second::new(2.)))).unwrap();
+//!     let result: PyMeasure = result.extract().unwrap();
+//!     assert_eq!(result, PyMeasure::new(5., "m * s^-1".to_string()));
+//!
+//!     let err = speed.call1((PyMeasure::from(Second::new(10.)), PyMeasure::from(Second::new(2.)))).unwrap_err();
+//!     assert!(err.is_instance_of::<PyValueError>(py));
+//! });
+//! # }
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use unit::*;
+
+/// A measure whose unit is only known at runtime, as seen from Python.
+#[pyclass(name = "Measure", frozen)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PyMeasure {
+    #[pyo3(get)]
+    pub value: f64,
+
+    /// The symbol of the unit, e.g. `m * s^-1`.
+    #[pyo3(get)]
+    pub unit: String,
+}
+
+#[pymethods]
+impl PyMeasure {
+    #[new]
+    pub fn new(value: f64, unit: String) -> Self {
+        Self { value, unit }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Measure({:?}, {:?})", self.value, self.unit)
+    }
+}
+
+impl PyMeasure {
+    /// Convert into a measure, if `unit` is the symbol of `U`.
+    pub fn into_measure<U: Unit>(self) -> Result<Measure<f64, U>, TagMismatch> {
+        Measure::from_tagged((self.value, &self.unit))
+    }
+}
+
+impl<U: Unit> From<Measure<f64, U>> for PyMeasure {
+    fn from(measure: Measure<f64, U>) -> Self {
+        let (value, unit) = measure.to_tagged();
+        Self::new(value, unit.to_string())
+    }
+}
+
+impl<U: Unit> IntoPy<PyObject> for Measure<f64, U> {
+    fn into_py(self, py: Python) -> PyObject {
+        PyMeasure::from(self).into_py(py)
+    }
+}
+
+impl<'py, U: Unit> FromPyObject<'py> for Measure<f64, U> {
+    fn extract_bound(object: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let measure: PyMeasure = object.extract()?;
+        measure
+            .into_measure()
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+}
@@ -0,0 +1,255 @@
+//! Random measures, e.g. to add noise to the inputs of a simulation.
+//!
+//! These distributions wrap those of crates `rand` and `rand_distr`, with
+//! parameters that carry units, so that noise cannot be drawn in a unit
+//! and added in another.
+//!
+//! ```
+//! extern crate rand;
+//! extern crate yaiouom;
+//!
+//! use rand::SeedableRng;
+//! use rand::distributions::Distribution;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::rand::*;
+//!
+//! # fn main() {
+//! let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+//!
+//! let noise = Normal::noise(Meter::new(0.01)).unwrap();
+//! let altitude = Meter::new(100.) + noise.sample(&mut rng);
+//! let error: f64 = *(altitude - Meter::new(100.)).as_ref();
+//! assert!(error.abs() < 1.);
+//!
+//! let delay = Uniform::new(Second::new(1.), Second::new(2.));
+//! let delay = delay.sample(&mut rng);
+//! assert!(delay >= Second::new(1.) && delay < Second::new(2.));
+//! # }
+//! ```
+//!
+//! Parameters in the wrong unit do not type-check:
+//!
+//! ```compile_fail
+//! extern crate rand;
+//! extern crate yaiouom;
+//!
+//! use rand::distributions::Distribution;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::rand::*;
+//!
+//! # fn main() {
+//! let noise = Normal::noise(Second::new(0.01)).unwrap();
+//! let altitude = Meter::new(100.) + noise.sample(&mut rand::thread_rng());
+//! # }
+//! ```
+
+use std::marker::PhantomData;
+
+use num_traits::Float;
+use rand_crate::distributions::uniform::SampleUniform;
+use rand_crate::distributions::Distribution;
+use rand_crate::Rng;
+use rand_distr::{Exp1, ExpError, NormalError, StandardNormal};
+
+use unit::*;
+
+/// The normal (Gaussian) distribution of measures in unit `U`.
+pub struct Normal<T, U: Unit>
+where
+    T: Float,
+    StandardNormal: Distribution<T>,
+{
+    inner: rand_distr::Normal<T>,
+    unit: PhantomData<U>,
+}
+impl<T, U: Unit> Normal<T, U>
+where
+    T: Float,
+    StandardNormal: Distribution<T>,
+{
+    /// The normal distribution with mean `mean` and standard deviation
+    /// `std_dev`.
+    ///
+    /// Fails if `std_dev` is negative or not finite.
+    pub fn new(mean: Measure<T, U>, std_dev: Measure<T, U>) -> Result<Self, NormalError> {
+        Ok(Self {
+            inner: rand_distr::Normal::new(mean.value, std_dev.value)?,
+            unit: PhantomData,
+        })
+    }
+
+    /// The normal distribution with mean 0 and standard deviation `sigma`,
+    /// i.e. noise to add to a measure.
+    pub fn noise(sigma: Measure<T, U>) -> Result<Self, NormalError> {
+        Self::new(Measure::new(T::zero()), sigma)
+    }
+
+    pub fn mean(&self) -> Measure<T, U> {
+        Measure::new(self.inner.mean())
+    }
+
+    pub fn std_dev(&self) -> Measure<T, U> {
+        Measure::new(self.inner.std_dev())
+    }
+}
+impl<T, U: Unit> Distribution<Measure<T, U>> for Normal<T, U>
+where
+    T: Float,
+    StandardNormal: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Measure<T, U> {
+        Measure::new(self.inner.sample(rng))
+    }
+}
+impl<T, U: Unit> Clone for Normal<T, U>
+where
+    T: Float,
+    StandardNormal: Distribution<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner,
+            unit: PhantomData,
+        }
+    }
+}
+
+/// The uniform distribution of measures in unit `U`.
+pub struct Uniform<T, U: Unit>
+where
+    T: SampleUniform,
+{
+    inner: rand_crate::distributions::Uniform<T>,
+    unit: PhantomData<U>,
+}
+impl<T, U: Unit> Uniform<T, U>
+where
+    T: SampleUniform,
+{
+    /// The uniform distribution in `[low, high)`.
+    ///
+    /// # Panics
+    ///
+    /// If `low >= high`.
+    pub fn new(low: Measure<T, U>, high: Measure<T, U>) -> Self {
+        Self {
+            inner: rand_crate::distributions::Uniform::new(low.value, high.value),
+            unit: PhantomData,
+        }
+    }
+
+    /// The uniform distribution in `[low, high]`.
+    ///
+    /// # Panics
+    ///
+    /// If `low > high`.
+    pub fn new_inclusive(low: Measure<T, U>, high: Measure<T, U>) -> Self {
+        Self {
+            inner: rand_crate::distributions::Uniform::new_inclusive(low.value, high.value),
+            unit: PhantomData,
+        }
+    }
+}
+impl<T, U: Unit> Distribution<Measure<T, U>> for Uniform<T, U>
+where
+    T: SampleUniform,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Measure<T, U> {
+        Measure::new(self.inner.sample(rng))
+    }
+}
+impl<T, U: Unit> Clone for Uniform<T, U>
+where
+    T: SampleUniform + Clone,
+    T::Sampler: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            unit: PhantomData,
+        }
+    }
+}
+
+/// The exponential distribution of measures in unit `U`, e.g. the delays
+/// between events that occur at a constant rate.
+///
+/// ```
+/// extern crate rand;
+/// extern crate yaiouom;
+///
+/// use rand::distributions::Distribution;
+///
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+/// use yaiouom::rand::*;
+///
+/// # fn main() {
+/// // Two arrivals per second.
+/// let delays = Exponential::new(Inv::<Second>::new(2.)).unwrap();
+/// assert_eq!(delays.mean(), Second::new(0.5));
+///
+/// let delay: Measure<f64, Second> = delays.sample(&mut rand::thread_rng());
+/// assert!(delay >= Second::new(0.));
+/// # }
+/// ```
+pub struct Exponential<T, U: Unit>
+where
+    T: Float,
+    Exp1: Distribution<T>,
+{
+    inner: rand_distr::Exp<T>,
+    lambda: T,
+    unit: PhantomData<U>,
+}
+impl<T, U: Unit> Exponential<T, U>
+where
+    T: Float,
+    Exp1: Distribution<T>,
+{
+    /// The exponential distribution with rate `lambda`, i.e. with mean
+    /// `1 / lambda`.
+    ///
+    /// Fails if `lambda` is negative or NaN.
+    pub fn new(lambda: Measure<T, Inv<U>>) -> Result<Self, ExpError> {
+        Ok(Self {
+            inner: rand_distr::Exp::new(lambda.value)?,
+            lambda: lambda.value,
+            unit: PhantomData,
+        })
+    }
+
+    pub fn lambda(&self) -> Measure<T, Inv<U>> {
+        Measure::new(self.lambda)
+    }
+
+    pub fn mean(&self) -> Measure<T, U> {
+        Measure::new(T::one() / self.lambda)
+    }
+}
+impl<T, U: Unit> Distribution<Measure<T, U>> for Exponential<T, U>
+where
+    T: Float,
+    Exp1: Distribution<T>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Measure<T, U> {
+        Measure::new(self.inner.sample(rng))
+    }
+}
+impl<T, U: Unit> Clone for Exponential<T, U>
+where
+    T: Float,
+    Exp1: Distribution<T>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner,
+            lambda: self.lambda,
+            unit: PhantomData,
+        }
+    }
+}
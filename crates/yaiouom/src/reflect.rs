@@ -0,0 +1,117 @@
+//! Inspecting the units of a struct of measures at runtime.
+//!
+//! Use `#[derive(UnitReflect)]` (feature `derive`) on a struct whose fields
+//! are measures (or structs which themselves derive `UnitReflect`), then
+//! `UnitSchema::of::<MyStruct>()` to list its fields and their units. This
+//! lets generic tools (writers, validators, user interfaces, ...) consume
+//! any such struct.
+
+use unit::*;
+
+/// A type made of measures, whose units may be inspected at runtime.
+///
+/// Implemented by `Measure` and by `#[derive(UnitReflect)]`.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+/// use yaiouom::reflect::*;
+///
+/// struct State {
+///     position: Measure<f64, Meter>,
+///     speed: Measure<f64, Mul<Meter, Inv<Second>>>,
+/// }
+/// impl UnitReflect for State {
+///     fn unit_schema() -> UnitSchema {
+///         let mut schema = UnitSchema::new();
+///         schema.push_nested("position", UnitSchema::of::<Measure<f64, Meter>>());
+///         schema.push_nested("speed", UnitSchema::of::<Measure<f64, Mul<Meter, Inv<Second>>>>());
+///         schema
+///     }
+/// }
+///
+/// let schema = UnitSchema::of::<State>();
+/// assert_eq!(schema.len(), 2);
+/// assert_eq!(schema.fields()[0].0, "position");
+/// assert_eq!(schema.unit("speed").unwrap().to_string(), "m * s^-1");
+/// assert!(schema.unit("acceleration").is_none());
+/// ```
+pub trait UnitReflect {
+    /// The fields of `Self` and their units.
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    fn unit_schema() -> UnitSchema;
+}
+
+impl<T, U: Unit> UnitReflect for Measure<T, U> {
+    fn unit_schema() -> UnitSchema {
+        UnitSchema {
+            fields: vec![(String::new(), U::as_runtime())],
+        }
+    }
+}
+
+/// The fields of a type made of measures, in order of declaration,
+/// paired with their units.
+///
+/// Fields of nested structs are flattened, with paths such as
+/// `position.x`. A `Measure` has a single field, with an empty path.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitSchema {
+    fields: Vec<(String, RuntimeUnit)>,
+}
+
+impl UnitSchema {
+    /// An empty schema, for use when implementing `UnitReflect`.
+    pub fn new() -> Self {
+        UnitSchema { fields: vec![] }
+    }
+
+    /// The schema of `T`.
+    pub fn of<T: UnitReflect + ?Sized>() -> Self {
+        T::unit_schema()
+    }
+
+    /// Append the fields of `schema` as fields of `name`.
+    pub fn push_nested(&mut self, name: &str, schema: UnitSchema) {
+        for (path, unit) in schema.fields {
+            let path = if path.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", name, path)
+            };
+            self.fields.push((path, unit));
+        }
+    }
+
+    /// The fields, in order of declaration.
+    pub fn fields(&self) -> &[(String, RuntimeUnit)] {
+        &self.fields
+    }
+
+    /// The unit of field `path`, if there is such a field.
+    pub fn unit(&self, path: &str) -> Option<&RuntimeUnit> {
+        self.fields
+            .iter()
+            .find(|(name, _)| name == path)
+            .map(|(_, unit)| unit)
+    }
+
+    /// The number of fields.
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// `true` if there are no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+}
+
+impl Default for UnitSchema {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,277 @@
+//! Listing at runtime the base units linked into the program, e.g. to fill
+//! the dropdowns of a user interface.
+//!
+//! Crates register their base units with `register_units!`. With feature
+//! `registry`, `all_units` lists all the units registered by all the crates
+//! linked into the program, including the units of modules `si` and
+//! `dosing`. Registration relies on the linker, with crate `linkme`, so it
+//! needs no initialization code.
+//!
+//! `collisions` lists the registered units that are considered identical
+//! at runtime although they are distinct types, see `BaseUnit::NAMESPACE`.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::registry::*;
+//!
+//! pub struct Euro;
+//! impl BaseUnit for Euro {
+//!     const NAMESPACE: &'static str = "finance";
+//!     const NAME: &'static str = "EUR";
+//!     const FULL_NAME: &'static str = "euro";
+//!     const QUANTITY: &'static str = "money";
+//! }
+//! register_units!(Euro);
+//!
+//! # fn main() {
+//! let units = all_units();
+//! let euro = units.iter().find(|unit| unit.name() == "EUR").unwrap();
+//! assert_eq!(euro.namespace(), "finance");
+//! assert_eq!(euro.info().full_name(), "euro");
+//! assert_eq!(euro.unit(), Euro::as_runtime());
+//!
+//! assert!(units.iter().any(|unit| unit.unit() == yaiouom::si::Meter::as_runtime()));
+//! # }
+//! ```
+
+use dynamic::DynUnit;
+use unit::*;
+use {dosing, si};
+
+use std::any::TypeId;
+
+#[doc(hidden)]
+pub extern crate linkme as __linkme;
+use self::__linkme::distributed_slice;
+
+/// A base unit registered with `register_units!`.
+#[derive(Clone, Copy)]
+pub struct RegisteredUnit {
+    info: BaseUnitInfo,
+    unit: fn() -> RuntimeUnit,
+    path: fn() -> &'static str,
+    type_id: fn() -> TypeId,
+}
+impl RegisteredUnit {
+    #[doc(hidden)]
+    pub const fn of<U: BaseUnit>() -> Self {
+        RegisteredUnit {
+            info: BaseUnitInfo::of::<U>(),
+            unit: <U as Unit>::as_runtime,
+            path: std::any::type_name::<U>,
+            type_id: TypeId::of::<U>,
+        }
+    }
+
+    /// The `BaseUnit::NAME` of the unit, e.g. `"m"`.
+    pub fn name(&self) -> &'static str {
+        self.info.name()
+    }
+
+    /// The `BaseUnit::NAMESPACE` of the unit, e.g. `"yaiouom::si"`.
+    pub fn namespace(&self) -> &'static str {
+        self.info.namespace()
+    }
+
+    /// The `BaseUnit::DISAMBIGUATOR` of the unit.
+    pub fn disambiguator(&self) -> u32 {
+        self.info.disambiguator()
+    }
+
+    /// The metadata of the unit, e.g. its full name, to label the entries
+    /// of a dropdown.
+    pub fn info(&self) -> BaseUnitInfo {
+        self.info
+    }
+
+    /// The unit, e.g. to build a `DynMeasure` once the user has picked it.
+    pub fn unit(&self) -> RuntimeUnit {
+        (self.unit)()
+    }
+
+    /// The path of the type of the unit, e.g. `"yaiouom::si::Meter"`, as
+    /// given by `std::any::type_name`.
+    pub fn path(&self) -> &'static str {
+        (self.path)()
+    }
+}
+impl std::fmt::Debug for RegisteredUnit {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        fmt.debug_struct("RegisteredUnit")
+            .field("info", &self.info)
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+#[doc(hidden)]
+#[distributed_slice]
+#[linkme(crate = self::__linkme)]
+pub static UNITS: [RegisteredUnit];
+
+/// All the registered base units, sorted by namespace, then name.
+///
+/// A unit registered several times, e.g. by two versions of a crate, is
+/// listed once.
+pub fn all_units() -> Vec<RegisteredUnit> {
+    let mut units: Vec<RegisteredUnit> = UNITS.to_vec();
+    units.sort_by_key(|unit| unit.info);
+    units.dedup_by_key(|unit| unit.info);
+    units
+}
+
+/// A registered unit is a `DynUnit`, e.g. to build a `DynMeasure` from
+/// the unit that a user has picked.
+///
+/// ```
+/// #[macro_use]
+/// extern crate yaiouom;
+///
+/// use yaiouom::*;
+/// use yaiouom::registry::*;
+///
+/// pub struct Euro;
+/// impl BaseUnit for Euro {
+///     const NAMESPACE: &'static str = "finance";
+///     const NAME: &'static str = "EUR";
+/// }
+/// register_units!(Euro);
+///
+/// # fn main() {
+/// let euro = all_units().into_iter().find(|unit| unit.name() == "EUR").unwrap();
+/// let price = DynMeasure::with_dyn_unit(5., &euro);
+/// assert_eq!(price.clone().into_static::<Euro>().unwrap(), Euro::new(5.));
+///
+/// // Back to the registered unit, and to the static unit.
+/// let unit: &dyn DynUnit = &euro;
+/// assert_eq!(lookup(unit).unwrap().namespace(), "finance");
+/// assert_eq!(unit.downcast::<_, Euro>(*price.value()).unwrap(), Euro::new(5.));
+///
+/// assert_eq!(lookup(&yaiouom::si::Meter).unwrap().name(), "m");
+/// assert!(lookup(&StaticUnit::<Mul<Euro, Euro>>::new()).is_none());
+/// # }
+/// ```
+impl DynUnit for RegisteredUnit {
+    fn runtime_unit(&self) -> RuntimeUnit {
+        self.unit()
+    }
+    fn unit_type_id(&self) -> Option<TypeId> {
+        Some((self.type_id)())
+    }
+}
+
+/// The registered base unit represented by `unit`, if any.
+///
+/// Units are compared by `TypeId`, as in `DynUnit::is`, so this finds the
+/// unit registered for `unit`'s own type, e.g. `Meter` for
+/// `StaticUnit<Meter>`, but nothing for `StaticUnit<Mul<Meter, Meter>>`.
+pub fn lookup(unit: &dyn DynUnit) -> Option<RegisteredUnit> {
+    let type_id = unit.unit_type_id()?;
+    UNITS
+        .iter()
+        .find(|registered| (registered.type_id)() == type_id)
+        .cloned()
+}
+
+/// Two registered base units with the same identity but distinct paths,
+/// see `collisions`.
+#[derive(Clone, Copy, Debug)]
+pub struct Collision {
+    first: RegisteredUnit,
+    second: RegisteredUnit,
+}
+impl Collision {
+    /// The units, sorted by path.
+    pub fn units(&self) -> (RegisteredUnit, RegisteredUnit) {
+        (self.first, self.second)
+    }
+}
+impl std::fmt::Display for Collision {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "Base units `{}` and `{}` have the same identity ({}), so they are \
+             considered identical at runtime. Consider setting their `NAMESPACE`.",
+            self.first.path(),
+            self.second.path(),
+            self.first.unit()
+        )
+    }
+}
+
+/// The registered base units that share the same identity, i.e. the same
+/// namespace, name and disambiguator, but have distinct paths.
+///
+/// Units with the same path are typically the same unit, defined in two
+/// versions of a crate, so they are not collisions. This is not checked
+/// when converting units at runtime, to keep conversions fast: call it
+/// e.g. once at startup, or in a test.
+///
+/// ```
+/// #[macro_use]
+/// extern crate yaiouom;
+///
+/// use yaiouom::*;
+/// use yaiouom::registry::*;
+///
+/// mod us {
+///     pub struct Dollar;
+///     impl yaiouom::BaseUnit for Dollar {
+///         const NAME: &'static str = "$";
+///     }
+/// }
+/// mod canada {
+///     pub struct Dollar;
+///     impl yaiouom::BaseUnit for Dollar {
+///         const NAME: &'static str = "$";
+///     }
+/// }
+/// register_units!(us::Dollar, canada::Dollar);
+///
+/// # fn main() {
+/// let collisions = collisions();
+/// assert_eq!(collisions.len(), 1);
+/// let (canada, us) = collisions[0].units();
+/// assert!(canada.path().ends_with("canada::Dollar"));
+/// assert!(us.path().ends_with("us::Dollar"));
+/// # }
+/// ```
+pub fn collisions() -> Vec<Collision> {
+    let mut units: Vec<(RegisteredUnit, &'static str)> =
+        UNITS.iter().map(|unit| (*unit, unit.path())).collect();
+    units.sort_by_key(|&(unit, path)| (unit.info, path));
+    units.dedup_by_key(|&mut (unit, path)| (unit.info, path));
+    let mut collisions = vec![];
+    // Units with the same identity are now adjacent: pair the first unit
+    // of each run with each of the others.
+    let mut start = 0;
+    for i in 1..units.len() {
+        if units[i].0.info != units[start].0.info {
+            start = i;
+            continue;
+        }
+        collisions.push(Collision {
+            first: units[start].0,
+            second: units[i].0,
+        });
+    }
+    collisions
+}
+
+register_units!(
+    si::Second,
+    si::Meter,
+    si::Kg,
+    si::Ampere,
+    si::Kelvin,
+    si::Mole,
+    si::Candela,
+    si::Radian,
+    dosing::Milligram,
+    dosing::BodyKg,
+    dosing::Milliliter,
+    dosing::Hour
+);
@@ -0,0 +1,113 @@
+//! Angular velocities and accelerations, and their integration over time.
+//!
+//! Angles are measured in `Radian`, a base unit of module `si`, so an
+//! angular velocity in rad/s cannot be mistaken for a frequency in s^-1,
+//! and integrating it over a duration in `Second` yields a `Radian`.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::rotation::*;
+//!
+//! // A wheel spinning up at 2 rad/s^2 for 3 s.
+//! let acceleration: Measure<f64, AngularAcceleration> = (2. * RAD / (S * S)).unify();
+//! let velocity = integrate_velocity(Measure::new(0.), acceleration, 3. * S);
+//! assert_eq!(velocity, Measure::new(6.));
+//!
+//! let angle = integrate_angle(1. * RAD, velocity, 0.5 * S);
+//! assert_eq!(angle, 4. * RAD);
+//! assert_eq!(angle_swept(velocity, 0.5 * S), 3. * RAD);
+//! ```
+//!
+//! Forgetting the duration does not type-check:
+//!
+//! ```compile_fail
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::rotation::*;
+//!
+//! let velocity: Measure<f64, AngularVelocity> = 6. * RAD / S;
+//! let _ = (1. * RAD) + velocity;
+//! ```
+//!
+//! With feature `nalgebra`, angular velocity vectors also rotate
+//! quaternions, see `integrate_orientation`.
+
+#[cfg(feature = "nalgebra")]
+use nalgebra::{RealField, UnitQuaternion, Vector3};
+use num_traits::float::Float;
+
+use si::*;
+use unit::*;
+
+/// rad * s^-1.
+pub type AngularVelocity = Mul<Radian, Inv<Second>>;
+
+/// rad * s^-2.
+pub type AngularAcceleration = Mul<AngularVelocity, Inv<Second>>;
+
+/// The angle swept at `velocity` during `dt`.
+pub fn angle_swept<T: Float>(
+    velocity: Measure<T, AngularVelocity>,
+    dt: Measure<T, Second>,
+) -> Measure<T, Radian> {
+    (velocity * dt).unify()
+}
+
+/// One step of integration of an angle, from `angle` at `velocity` during
+/// `dt`.
+pub fn integrate_angle<T: Float>(
+    angle: Measure<T, Radian>,
+    velocity: Measure<T, AngularVelocity>,
+    dt: Measure<T, Second>,
+) -> Measure<T, Radian> {
+    angle + angle_swept(velocity, dt)
+}
+
+/// One step of integration of an angular velocity, from `velocity` at
+/// `acceleration` during `dt`.
+pub fn integrate_velocity<T: Float>(
+    velocity: Measure<T, AngularVelocity>,
+    acceleration: Measure<T, AngularAcceleration>,
+    dt: Measure<T, Second>,
+) -> Measure<T, AngularVelocity> {
+    velocity + (acceleration * dt).unify()
+}
+
+/// One step of integration of an orientation, from `orientation` at
+/// angular velocity `velocity` during `dt`.
+///
+/// `velocity` is expressed in the body frame, as measured e.g. by a
+/// gyroscope, and its direction is the axis of rotation.
+///
+/// ```
+/// extern crate nalgebra;
+/// extern crate yaiouom;
+///
+/// use nalgebra::{UnitQuaternion, Vector3};
+///
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+/// use yaiouom::rotation::*;
+///
+/// # fn main() {
+/// // A quarter turn around the z axis, in 10 steps of 0.1 s.
+/// let velocity: Measure<Vector3<f64>, AngularVelocity> =
+///     Measure::new(Vector3::z() * std::f64::consts::FRAC_PI_2);
+/// let mut orientation = UnitQuaternion::identity();
+/// for _ in 0..10 {
+///     orientation = integrate_orientation(orientation, velocity, 0.1 * S);
+/// }
+///
+/// let x = orientation * Vector3::x();
+/// assert!((x - Vector3::y()).norm() < 1e-9);
+/// # }
+/// ```
+#[cfg(feature = "nalgebra")]
+pub fn integrate_orientation<T: RealField + Copy>(
+    orientation: UnitQuaternion<T>,
+    velocity: Measure<Vector3<T>, AngularVelocity>,
+    dt: Measure<T, Second>,
+) -> UnitQuaternion<T> {
+    orientation * UnitQuaternion::from_scaled_axis(velocity.value * dt.value)
+}
@@ -0,0 +1,174 @@
+//! Drivers of sensors whose readings are measures.
+//!
+//! A driver implements `Sensor`, with the unit of its readings. The
+//! adapters `scale`, `offset` and `low_pass` then compute calibrated or
+//! filtered readings, with the unit that results from the computation.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::sensor::*;
+//!
+//! /// The raw output of an analog-to-digital converter.
+//! pub struct Count;
+//! impl BaseUnit for Count {
+//!     const NAMESPACE: &'static str = "adc";
+//!     const NAME: &'static str = "count";
+//! }
+//!
+//! /// A thermometer behind an ADC, which reads 100 then 140.
+//! struct Adc(f32);
+//! impl Sensor for Adc {
+//!     type Output = Count;
+//!     fn read(&mut self) -> Measure<f32, Count> {
+//!         self.0 += 40.;
+//!         Measure::new(self.0 - 40.)
+//!     }
+//! }
+//!
+//! let gain: Measure<f32, Mul<Kelvin, Inv<Count>>> = Measure::new(0.5);
+//! let mut thermometer = Adc(100.)
+//!     .scale(gain)
+//!     .offset(Measure::new(223.))
+//!     .low_pass(0.5);
+//!
+//! let temperature: Measure<f32, Kelvin> = thermometer.read().unify();
+//! assert_eq!(temperature, Measure::new(273.));
+//! // Halfway between 273 K and the raw 293 K.
+//! let temperature: Measure<f32, Kelvin> = thermometer.read().unify();
+//! assert_eq!(temperature, Measure::new(283.));
+//! ```
+//!
+//! Offsets have the unit of the readings they adjust:
+//!
+//! ```compile_fail
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::sensor::*;
+//!
+//! struct Thermometer;
+//! impl Sensor for Thermometer {
+//!     type Output = Kelvin;
+//!     fn read(&mut self) -> Measure<f32, Kelvin> {
+//!         Measure::new(273.)
+//!     }
+//! }
+//!
+//! let _ = Thermometer.offset(Measure::<f32, Second>::new(1.));
+//! ```
+
+use unit::*;
+
+/// A sensor, whose readings are measures in unit `Output`.
+pub trait Sensor {
+    /// The unit of the readings.
+    type Output: Unit;
+
+    /// Read the current value.
+    fn read(&mut self) -> Measure<f32, Self::Output>;
+
+    /// Multiply the readings by `factor`, e.g. the gain of a calibration.
+    fn scale<V: Unit>(self, factor: Measure<f32, V>) -> Scale<Self, V>
+    where
+        Self: Sized,
+    {
+        Scale {
+            sensor: self,
+            factor,
+        }
+    }
+
+    /// Add `offset` to the readings, e.g. the zero of a calibration.
+    fn offset(self, offset: Measure<f32, Self::Output>) -> Offset<Self>
+    where
+        Self: Sized,
+    {
+        Offset {
+            sensor: self,
+            offset,
+        }
+    }
+
+    /// Smooth the readings with an exponential moving average, each new
+    /// reading having weight `alpha`, in `(0, 1]`.
+    ///
+    /// The first reading is returned unchanged.
+    fn low_pass(self, alpha: f32) -> LowPass<Self>
+    where
+        Self: Sized,
+    {
+        LowPass {
+            sensor: self,
+            alpha,
+            state: None,
+        }
+    }
+}
+
+impl<S: Sensor + ?Sized> Sensor for &mut S {
+    type Output = S::Output;
+    fn read(&mut self) -> Measure<f32, Self::Output> {
+        (**self).read()
+    }
+}
+
+/// A sensor whose readings are multiplied by a factor, see `Sensor::scale`.
+pub struct Scale<S: Sensor, V: Unit> {
+    sensor: S,
+    factor: Measure<f32, V>,
+}
+impl<S: Sensor, V: Unit> Scale<S, V> {
+    /// The underlying sensor.
+    pub fn into_inner(self) -> S {
+        self.sensor
+    }
+}
+impl<S: Sensor, V: Unit> Sensor for Scale<S, V> {
+    type Output = Mul<S::Output, V>;
+    fn read(&mut self) -> Measure<f32, Self::Output> {
+        self.sensor.read() * self.factor
+    }
+}
+
+/// A sensor whose readings are offset, see `Sensor::offset`.
+pub struct Offset<S: Sensor> {
+    sensor: S,
+    offset: Measure<f32, S::Output>,
+}
+impl<S: Sensor> Offset<S> {
+    /// The underlying sensor.
+    pub fn into_inner(self) -> S {
+        self.sensor
+    }
+}
+impl<S: Sensor> Sensor for Offset<S> {
+    type Output = S::Output;
+    fn read(&mut self) -> Measure<f32, Self::Output> {
+        self.sensor.read() + self.offset
+    }
+}
+
+/// A sensor whose readings are smoothed, see `Sensor::low_pass`.
+pub struct LowPass<S: Sensor> {
+    sensor: S,
+    alpha: f32,
+    state: Option<Measure<f32, S::Output>>,
+}
+impl<S: Sensor> LowPass<S> {
+    /// The underlying sensor.
+    pub fn into_inner(self) -> S {
+        self.sensor
+    }
+}
+impl<S: Sensor> Sensor for LowPass<S> {
+    type Output = S::Output;
+    fn read(&mut self) -> Measure<f32, Self::Output> {
+        let reading = self.sensor.read();
+        let state = match self.state {
+            Some(state) => state + (reading - state) * self.alpha,
+            None => reading,
+        };
+        self.state = Some(state);
+        state
+    }
+}
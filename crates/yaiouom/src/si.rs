@@ -1,44 +1,133 @@
 //! International system of units.
-use unit::BaseUnit;
+//!
+//! Besides base units, this module defines value-level constants for each
+//! base unit and, as dimensionless measures, for the SI prefixes, to write
+//! measures as literals:
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! let period = 2. * S;
+//! assert_eq!(period, Second::new(2.));
+//!
+//! // The unit of `532. * NANO * M` is `Mul<Dimensionless, Meter>`.
+//! let wavelength: Measure<f64, Meter> = (532. * NANO * M).unify();
+//! assert_eq!(wavelength, Meter::new(532e-9));
+//!
+//! let speed: Measure<f64, Mul<Meter, Inv<Second>>> = (3. * KILO * M / S).unify();
+//! assert_eq!(speed.as_ref(), &3000.);
+//! ```
+//!
+//! As the product of a dimensionless measure and a `Measure<_, Meter>` has
+//! unit `Mul<Dimensionless, Meter>`, prefixed literals are unified with
+//! their unit, which yaiouom-checker accepts.
+use unit::{BaseUnit, Dimensionless, Measure};
 
 /// Base unit of time
 pub struct Second;
 impl BaseUnit for Second {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "s";
+    const FULL_NAME: &'static str = "second";
+    const QUANTITY: &'static str = "time";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit of length
 pub struct Meter;
 impl BaseUnit for Meter {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "m";
+    const FULL_NAME: &'static str = "metre";
+    const QUANTITY: &'static str = "length";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit of mass
 pub struct Kg;
 impl BaseUnit for Kg {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "kg";
+    const FULL_NAME: &'static str = "kilogram";
+    const QUANTITY: &'static str = "mass";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit of electrical current
 pub struct Ampere;
 impl BaseUnit for Ampere {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "A";
+    const FULL_NAME: &'static str = "ampere";
+    const QUANTITY: &'static str = "electric current";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit of temperature
 pub struct Kelvin;
 impl BaseUnit for Kelvin {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "K";
+    const FULL_NAME: &'static str = "kelvin";
+    const QUANTITY: &'static str = "thermodynamic temperature";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit for amount of substance
 pub struct Mole;
 impl BaseUnit for Mole {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "mol";
+    const FULL_NAME: &'static str = "mole";
+    const QUANTITY: &'static str = "amount of substance";
+    const SYSTEM: &'static str = "SI";
 }
 
 /// Base unit of luminous intensity
 pub struct Candela;
 impl BaseUnit for Candela {
+    const NAMESPACE: &'static str = "yaiouom::si";
     const NAME: &'static str = "cd";
+    const FULL_NAME: &'static str = "candela";
+    const QUANTITY: &'static str = "luminous intensity";
+    const SYSTEM: &'static str = "SI";
+}
+
+/// Unit of plane angle
+///
+/// SI defines the radian as dimensionless. It is a base unit here, so that an
+/// angle cannot be mistaken for a ratio.
+pub struct Radian;
+impl BaseUnit for Radian {
+    const NAMESPACE: &'static str = "yaiouom::si";
+    const NAME: &'static str = "rad";
+    const FULL_NAME: &'static str = "radian";
+    const QUANTITY: &'static str = "plane angle";
+    const SYSTEM: &'static str = "SI";
+}
+
+macro_rules! unit_constants {
+    ($($name:ident: $unit:ident),*) => {
+        $(
+            #[doc = concat!("One `", stringify!($unit), "`, e.g. to write `3. * ", stringify!($name), "`.")]
+            pub const $name: Measure<f64, $unit> = Measure::new(1.);
+        )*
+    }
+}
+unit_constants! {
+    S: Second, M: Meter, KG: Kg, A: Ampere, K: Kelvin, MOL: Mole, CD: Candela, RAD: Radian
+}
+
+macro_rules! prefixes {
+    ($($name:ident = $value:expr),*) => {
+        $(
+            #[doc = concat!("SI prefix, `", stringify!($value), "`, as a dimensionless measure.")]
+            pub const $name: Measure<f64, Dimensionless> = Measure::new($value);
+        )*
+    }
+}
+prefixes! {
+    NANO = 1e-9, MICRO = 1e-6, MILLI = 1e-3, CENTI = 1e-2, DECI = 1e-1,
+    DECA = 1e1, HECTO = 1e2, KILO = 1e3, MEGA = 1e6, GIGA = 1e9
 }
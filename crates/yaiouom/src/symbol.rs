@@ -0,0 +1,306 @@
+//! Compile-time computation of `Unit::SYMBOL`.
+//!
+//! `RuntimeUnit` relies on a `HashMap`, which cannot be built in a constant.
+//! Instead, units are represented here as fixed-capacity arrays of base
+//! units, sorted by namespace, name and disambiguator, then rendered into a
+//! fixed-capacity buffer. Exceeding either capacity is a compile-time error
+//! in the unit that uses `SYMBOL`, whereas `Display` falls back to
+//! `RuntimeUnit`, see `Symbol::FAST`.
+
+use std::marker::PhantomData;
+
+use unit::Unit;
+
+/// The maximal number of distinct base units in a unit that uses `SYMBOL`.
+pub const MAX_SYMBOL_DIMENSIONS: usize = 16;
+
+/// The maximal length of `SYMBOL`, in bytes.
+pub const MAX_SYMBOL_LEN: usize = 128;
+
+#[derive(Clone, Copy)]
+struct ConstDimension {
+    namespace: &'static str,
+    name: &'static str,
+    disambiguator: u32,
+}
+
+/// Compare two strings as `Ord` does, i.e. byte by byte.
+const fn compare_str(a: &str, b: &str) -> i32 {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut index = 0;
+    while index < a.len() && index < b.len() {
+        if a[index] != b[index] {
+            return if a[index] < b[index] { -1 } else { 1 };
+        }
+        index += 1;
+    }
+    if a.len() == b.len() {
+        0
+    } else if a.len() < b.len() {
+        -1
+    } else {
+        1
+    }
+}
+
+impl ConstDimension {
+    /// Compare by namespace, name and disambiguator, as `RuntimeUnit` does.
+    const fn compare(&self, other: &ConstDimension) -> i32 {
+        let by_namespace = compare_str(self.namespace, other.namespace);
+        if by_namespace != 0 {
+            return by_namespace;
+        }
+        let by_name = compare_str(self.name, other.name);
+        if by_name != 0 {
+            return by_name;
+        }
+        if self.disambiguator == other.disambiguator {
+            0
+        } else if self.disambiguator < other.disambiguator {
+            -1
+        } else {
+            1
+        }
+    }
+}
+
+/// A unit, as the sorted list of its base units and their exponents.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct Canonical {
+    dimensions: [(ConstDimension, i16); MAX_SYMBOL_DIMENSIONS],
+    len: usize,
+    /// Why this unit cannot be represented, if it cannot.
+    error: Option<&'static str>,
+}
+
+const NO_DIMENSION: (ConstDimension, i16) = (
+    ConstDimension {
+        namespace: "",
+        name: "",
+        disambiguator: 0,
+    },
+    0,
+);
+
+impl Canonical {
+    pub(crate) const DIMENSIONLESS: Canonical = Canonical {
+        dimensions: [NO_DIMENSION; MAX_SYMBOL_DIMENSIONS],
+        len: 0,
+        error: None,
+    };
+
+    pub(crate) const fn base(
+        namespace: &'static str,
+        name: &'static str,
+        disambiguator: u32,
+    ) -> Self {
+        let mut canonical = Self::DIMENSIONLESS;
+        canonical.dimensions[0] = (
+            ConstDimension {
+                namespace,
+                name,
+                disambiguator,
+            },
+            1,
+        );
+        canonical.len = 1;
+        canonical
+    }
+
+    /// Multiply `self` by `other`, if `positive` is `true`, or by its
+    /// inverse otherwise.
+    pub(crate) const fn mul(mut self, other: &Canonical, positive: bool) -> Self {
+        if self.error.is_some() {
+            return self;
+        }
+        if other.error.is_some() {
+            self.error = other.error;
+            return self;
+        }
+        let mut i = 0;
+        while i < other.len {
+            let (dimension, exponent) = other.dimensions[i];
+            let exponent = if positive { exponent } else { -exponent };
+
+            // Find where `dimension` is or should be.
+            let mut j = 0;
+            let mut order = 1;
+            while j < self.len {
+                order = dimension.compare(&self.dimensions[j].0);
+                if order <= 0 {
+                    break;
+                }
+                j += 1;
+            }
+            if j < self.len && order == 0 {
+                let sum = match self.dimensions[j].1.checked_add(exponent) {
+                    Some(sum) => sum,
+                    None => {
+                        self.error = Some("Exponent overflow in `Unit::SYMBOL`");
+                        return self;
+                    }
+                };
+                if sum == 0 {
+                    // Remove the dimension.
+                    let mut k = j;
+                    while k + 1 < self.len {
+                        self.dimensions[k] = self.dimensions[k + 1];
+                        k += 1;
+                    }
+                    self.len -= 1;
+                } else {
+                    self.dimensions[j].1 = sum;
+                }
+            } else {
+                if self.len == MAX_SYMBOL_DIMENSIONS {
+                    self.error = Some("Too many base units for `Unit::SYMBOL`");
+                    return self;
+                }
+                // Insert the dimension.
+                let mut k = self.len;
+                while k > j {
+                    self.dimensions[k] = self.dimensions[k - 1];
+                    k -= 1;
+                }
+                self.dimensions[j] = (dimension, exponent);
+                self.len += 1;
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Render as `RuntimeUnit`'s `Display` does, which is currently the
+    /// canonical format.
+    const fn render(&self) -> Rendered {
+        self.render_canonical()
+    }
+
+    /// Render as `RuntimeUnit::to_canonical_string` does. This format must
+    /// never change, even if `render` does.
+    const fn render_canonical(&self) -> Rendered {
+        let mut rendered = Rendered {
+            bytes: [0; MAX_SYMBOL_LEN],
+            len: 0,
+            error: self.error,
+        };
+        // First the positive exponents, then the negative ones.
+        let mut positives = true;
+        loop {
+            let mut i = 0;
+            while i < self.len {
+                let (dimension, exponent) = self.dimensions[i];
+                if (exponent > 0) == positives {
+                    if rendered.len != 0 {
+                        rendered = rendered.push(" * ");
+                    }
+                    rendered = rendered.push(dimension.name);
+                    if exponent != 1 {
+                        rendered = rendered.push("^").push_exponent(exponent);
+                    }
+                }
+                i += 1;
+            }
+            if !positives {
+                break;
+            }
+            positives = false;
+        }
+        rendered
+    }
+}
+
+struct Rendered {
+    bytes: [u8; MAX_SYMBOL_LEN],
+    len: usize,
+    /// Why this unit cannot be rendered, if it cannot.
+    error: Option<&'static str>,
+}
+impl Rendered {
+    const fn push(mut self, text: &str) -> Self {
+        let text = text.as_bytes();
+        if self.error.is_some() {
+            return self;
+        }
+        if self.len + text.len() > MAX_SYMBOL_LEN {
+            self.error = Some("Unit too long for `Unit::SYMBOL`");
+            return self;
+        }
+        let mut i = 0;
+        while i < text.len() {
+            self.bytes[self.len] = text[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    const fn push_exponent(self, exponent: i16) -> Self {
+        let mut digits = [0u8; 6];
+        let mut start = digits.len();
+        // Widen, as `-i16::MIN` does not fit in an `i16`.
+        let mut remaining = if exponent < 0 {
+            -(exponent as i32)
+        } else {
+            exponent as i32
+        };
+        while remaining != 0 {
+            start -= 1;
+            digits[start] = b'0' + (remaining % 10) as u8;
+            remaining /= 10;
+        }
+        if exponent < 0 {
+            start -= 1;
+            digits[start] = b'-';
+        }
+        let (_, digits) = digits.split_at(start);
+        match std::str::from_utf8(digits) {
+            Ok(digits) => self.push(digits),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// The rendered unit, unless it exceeds the capacities.
+    const fn try_as_str(&'static self) -> Option<&'static str> {
+        if self.error.is_some() {
+            return None;
+        }
+        let (bytes, _) = self.bytes.split_at(self.len);
+        match std::str::from_utf8(bytes) {
+            Ok(symbol) => Some(symbol),
+            // Only whole names are pushed.
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// The rendered unit, or a compile-time error if it exceeds the
+    /// capacities.
+    const fn as_str(&'static self) -> &'static str {
+        match (self.try_as_str(), self.error) {
+            (Some(symbol), _) => symbol,
+            (None, Some(error)) => panic!("{}", error),
+            (None, None) => unreachable!(),
+        }
+    }
+}
+
+/// A holder for the constants of unit `U`, which cannot be computed in
+/// `Unit` itself: a reference to the rendered unit must outlive its
+/// initializer, which only works at the top level of a constant.
+pub(crate) struct Symbol<U: ?Sized>(PhantomData<U>);
+impl<U: Unit + ?Sized> Symbol<U> {
+    const RENDERED: &'static Rendered = &U::CANONICAL.render();
+    pub(crate) const SYMBOL: &'static str = Self::RENDERED.as_str();
+
+    /// `SYMBOL`, or `None` for units that exceed its capacities, which
+    /// `Display` then renders through `RuntimeUnit`.
+    pub(crate) const FAST: Option<&'static str> = Self::RENDERED.try_as_str();
+
+    const RENDERED_CANONICAL: &'static Rendered = &U::CANONICAL.render_canonical();
+
+    /// The canonical string of `U`, or `None` for units that exceed the
+    /// capacities of `SYMBOL`, which `Debug` then renders through
+    /// `RuntimeUnit::to_canonical_string`.
+    pub(crate) const CANONICAL_STRING: Option<&'static str> = Self::RENDERED_CANONICAL.try_as_str();
+}
@@ -0,0 +1,47 @@
+//! Units for test fixtures, when the unit does not matter but some
+//! `U: Unit` is needed.
+//!
+//! Enabled by feature `tests`, typically as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! yaiouom = { version = "*", features = ["tests"] }
+//! ```
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::testing::*;
+//!
+//! fn total<U: Unit>(measures: &[Measure<f64, U>]) -> Measure<f64, U> {
+//!     measures.iter().cloned().sum()
+//! }
+//!
+//! assert_eq!(total(&any_measures(vec![1., 2.])), AnyUnit::new(3.));
+//!
+//! // Use `OtherUnit` when a test needs a second, distinct unit.
+//! assert!(AnyUnit::new(1.).try_unify::<OtherUnit>().is_err());
+//! ```
+
+use unit::*;
+
+/// A base unit standing for any unit, in tests.
+pub struct AnyUnit;
+impl BaseUnit for AnyUnit {
+    const NAMESPACE: &'static str = "yaiouom::testing";
+    const NAME: &'static str = "any";
+}
+
+/// A base unit distinct from `AnyUnit`, e.g. to test unit mismatches.
+pub struct OtherUnit;
+impl BaseUnit for OtherUnit {
+    const NAMESPACE: &'static str = "yaiouom::testing";
+    const NAME: &'static str = "other";
+}
+
+/// Measures with unit `AnyUnit`, one per value.
+pub fn any_measures<T, I>(values: I) -> Vec<Measure<T, AnyUnit>>
+where
+    I: IntoIterator<Item = T>,
+{
+    values.into_iter().map(Measure::new).collect()
+}
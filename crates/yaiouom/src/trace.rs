@@ -0,0 +1,57 @@
+//! Recording measures with `tracing`.
+//!
+//! Recording a `Measure` as a plain number would lose its unit. The methods
+//! of this module record either the value and its unit as two distinct
+//! fields, so that logs remain interpretable downstream, or both as a
+//! single field.
+//!
+//! ```
+//! #[macro_use]
+//! extern crate tracing;
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! fn main() {
+//!     let speed: Measure<f64, Mul<Meter, Inv<Second>>> = Measure::new(3.5);
+//!
+//!     // Two fields `speed.value = 3.5`, `speed.unit = "m * s^-1"`.
+//!     info!(speed.value = speed.trace_value(), speed.unit = speed.trace_unit(), "accelerating");
+//!
+//!     // A single field `speed = 3.5m * s^-1`.
+//!     info!(speed = speed.traced(), "accelerating");
+//! }
+//! ```
+use unit::*;
+
+use std;
+
+use tracing::field::{debug, display, DebugValue, DisplayValue};
+
+impl<T, U: Unit> Measure<T, U> {
+    /// The value of this measure, to be recorded as a `tracing` field.
+    pub fn trace_value(&self) -> DebugValue<&T>
+    where
+        T: std::fmt::Debug,
+    {
+        debug(&self.value)
+    }
+
+    /// The unit of this measure, to be recorded as a `tracing` field.
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    pub fn trace_unit(&self) -> DisplayValue<RuntimeUnit> {
+        display(U::as_runtime())
+    }
+
+    /// This measure, to be recorded as a single `tracing` field.
+    pub fn traced(&self) -> DebugValue<&Self>
+    where
+        T: std::fmt::Debug,
+    {
+        debug(self)
+    }
+}
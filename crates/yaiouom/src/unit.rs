@@ -2,22 +2,183 @@ use private;
 
 use std;
 use std::any::*;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::marker::PhantomData;
 
 use itertools::Itertools;
+use logics::Proof;
 use num_traits;
 
 /// A base unit of measure (e.g. meters, euros, ...)
+#[allow(unused_attributes)]
+#[cfg_attr(feature = "checker", rustc_yaiouom_base_unit)]
 pub trait BaseUnit: Any {
     /// The human-readable name of the unit, e.g. `"m"`
     /// `"s"`, `"EUR"`, etc.
     ///
     /// Used mainly for debugging purposes.
     const NAME: &'static str;
+
+    /// The namespace of the unit, e.g. `"yaiouom::si"`.
+    ///
+    /// At runtime, e.g. in the dynamic checks of `unify` or in
+    /// `RuntimeUnit`, base units are identified by their `NAMESPACE`,
+    /// `NAME` and `DISAMBIGUATOR`, rather than by their Rust type. This
+    /// lets a unit defined by two versions of the same crate be
+    /// recognized as a single unit.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    ///
+    /// mod v1 {
+    ///     pub struct Meter;
+    ///     impl yaiouom::BaseUnit for Meter {
+    ///         const NAMESPACE: &'static str = "geometry";
+    ///         const NAME: &'static str = "m";
+    ///     }
+    /// }
+    /// mod v2 {
+    ///     pub struct Meter;
+    ///     impl yaiouom::BaseUnit for Meter {
+    ///         const NAMESPACE: &'static str = "geometry";
+    ///         const NAME: &'static str = "m";
+    ///     }
+    /// }
+    /// assert_eq!(v1::Meter::as_runtime(), v2::Meter::as_runtime());
+    ///
+    /// // ... but not the Meter of the SI.
+    /// assert!(v1::Meter::as_runtime() != yaiouom::si::Meter::as_runtime());
+    /// ```
+    ///
+    /// With feature `registry`, `registry::collisions` lists the registered
+    /// base units with distinct paths that share the same identity.
+    const NAMESPACE: &'static str = "";
+
+    /// A number used to distinguish between base units that have the same
+    /// `NAMESPACE` and `NAME`.
+    const DISAMBIGUATOR: u32 = 0;
+
+    /// The full name of the unit, e.g. `"metre"`, or `""` if unspecified.
+    ///
+    /// This and the following metadata serve formatting, validation and
+    /// documentation, see `RuntimeUnit::base_units`. Unlike `NAMESPACE`,
+    /// `NAME` and `DISAMBIGUATOR`, they are not part of the identity of the
+    /// unit.
+    const FULL_NAME: &'static str = "";
+
+    /// The kind of quantity measured by the unit, e.g. `"length"`, or `""`
+    /// if unspecified.
+    const QUANTITY: &'static str = "";
+
+    /// The system of units of the unit, e.g. `"SI"`, or `""` if unspecified.
+    const SYSTEM: &'static str = "";
 }
 impl<T: BaseUnit> private::Sealed for T {}
 
+/// The runtime identity of a base unit, along with its metadata.
+///
+/// Only `namespace`, `name` and `disambiguator` are compared, hashed and
+/// ordered.
+#[derive(Clone, Copy, Debug)]
+struct Dimension {
+    namespace: &'static str,
+    name: &'static str,
+    disambiguator: u32,
+    full_name: &'static str,
+    quantity: &'static str,
+    system: &'static str,
+}
+impl Dimension {
+    const fn of<T: BaseUnit>() -> Self {
+        Dimension {
+            namespace: T::NAMESPACE,
+            name: T::NAME,
+            disambiguator: T::DISAMBIGUATOR,
+            full_name: T::FULL_NAME,
+            quantity: T::QUANTITY,
+            system: T::SYSTEM,
+        }
+    }
+
+    fn key(&self) -> (&'static str, &'static str, u32) {
+        (self.namespace, self.name, self.disambiguator)
+    }
+}
+impl PartialEq for Dimension {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+impl Eq for Dimension {}
+impl PartialOrd for Dimension {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Dimension {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+impl std::hash::Hash for Dimension {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state)
+    }
+}
+impl std::fmt::Display for Dimension {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "namespace {:?}, name {:?}, disambiguator {}",
+            self.namespace, self.name, self.disambiguator
+        )
+    }
+}
+
+/// A base unit and its metadata, as listed by `RuntimeUnit::base_units`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BaseUnitInfo {
+    dimension: Dimension,
+}
+impl BaseUnitInfo {
+    /// The information of base unit `T`.
+    pub const fn of<T: BaseUnit>() -> Self {
+        BaseUnitInfo {
+            dimension: Dimension::of::<T>(),
+        }
+    }
+
+    /// The `BaseUnit::NAME` of the unit, e.g. `"m"`.
+    pub fn name(&self) -> &'static str {
+        self.dimension.name
+    }
+
+    /// The `BaseUnit::NAMESPACE` of the unit, e.g. `"yaiouom::si"`.
+    pub fn namespace(&self) -> &'static str {
+        self.dimension.namespace
+    }
+
+    /// The `BaseUnit::DISAMBIGUATOR` of the unit.
+    pub fn disambiguator(&self) -> u32 {
+        self.dimension.disambiguator
+    }
+
+    /// The `BaseUnit::FULL_NAME` of the unit, e.g. `"metre"`.
+    pub fn full_name(&self) -> &'static str {
+        self.dimension.full_name
+    }
+
+    /// The `BaseUnit::QUANTITY` of the unit, e.g. `"length"`.
+    pub fn quantity(&self) -> &'static str {
+        self.dimension.quantity
+    }
+
+    /// The `BaseUnit::SYSTEM` of the unit, e.g. `"SI"`.
+    pub fn system(&self) -> &'static str {
+        self.dimension.system
+    }
+}
+
 /// A unit of measure.
 ///
 /// To implement a new Unit, use BaseUnit.
@@ -29,13 +190,230 @@ pub trait Unit: private::Sealed {
         Measure::new(value)
     }
 
+    /// A synonym for `new`, which reads better in some contexts.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let distances = vec![1., 2., 3.].into_iter().map(Meter::of);
+    /// let total: Measure<f64, Meter> = distances.sum();
+    /// assert_eq!(total, Meter::of(6.));
+    /// ```
+    fn of<T>(value: T) -> Measure<T, Self>
+    where
+        Self: Sized,
+    {
+        Measure::new(value)
+    }
+
+    /// The value `0` in this unit.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let origin = Meter::zero();
+    /// assert_eq!(origin, Meter::new(0.));
+    /// assert_eq!(Meter::new(2) + Meter::zero(), Meter::new(2));
+    /// ```
+    fn zero<T>() -> Measure<T, Self>
+    where
+        Self: Sized,
+        T: num_traits::Zero,
+    {
+        Measure::new(T::zero())
+    }
+
+    /// The value `1` in this unit.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let one_second = Second::one();
+    /// assert_eq!(one_second, Second::new(1.));
+    /// assert_eq!(Second::one::<u8>(), Second::new(1u8));
+    /// ```
+    fn one<T>() -> Measure<T, Self>
+    where
+        Self: Sized,
+        T: num_traits::One,
+    {
+        Measure::new(T::one())
+    }
+
+    /// View a reference to a value as a reference to a measure in this
+    /// unit, without copying.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// struct Config {
+    ///     timeout: u64,
+    /// }
+    /// impl Config {
+    ///     fn timeout(&self) -> &Measure<u64, Second> {
+    ///         Second::from_value_ref(&self.timeout)
+    ///     }
+    /// }
+    ///
+    /// let config = Config { timeout: 30 };
+    /// assert_eq!(config.timeout(), &Second::new(30));
+    /// ```
+    #[track_caller]
+    fn from_value_ref<T>(value: &T) -> &Measure<T, Self>
+    where
+        Self: Sized,
+    {
+        check_layout::<T, Self>();
+        // Safe, as `Measure<T, Self>` is a `repr(transparent)` wrapper of `T`.
+        unsafe { &*(value as *const T as *const Measure<T, Self>) }
+    }
+
+    /// View a mutable reference to a value as a mutable reference to a
+    /// measure in this unit, without copying.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let mut elapsed = 1.;
+    /// *Second::from_value_mut(&mut elapsed) = Second::new(2.);
+    /// assert_eq!(elapsed, 2.);
+    /// ```
+    #[track_caller]
+    fn from_value_mut<T>(value: &mut T) -> &mut Measure<T, Self>
+    where
+        Self: Sized,
+    {
+        check_layout::<T, Self>();
+        // Safe, as `Measure<T, Self>` is a `repr(transparent)` wrapper of `T`.
+        unsafe { &mut *(value as *mut T as *mut Measure<T, Self>) }
+    }
+
+    /// View a slice of values as a slice of measures in this unit,
+    /// without copying.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// // e.g. obtained from a library that does not know about units.
+    /// let raw: Vec<f64> = vec![1., 2., 3.];
+    ///
+    /// let distances: &[Measure<f64, Meter>] = Meter::from_slice(&raw);
+    /// let total: Measure<f64, Meter> = distances.iter().cloned().sum();
+    /// assert_eq!(total, Meter::new(6.));
+    /// ```
+    #[track_caller]
+    fn from_slice<T>(values: &[T]) -> &[Measure<T, Self>]
+    where
+        Self: Sized,
+    {
+        check_layout::<T, Self>();
+        // Safe, as `Measure<T, Self>` is a `repr(transparent)` wrapper of `T`.
+        unsafe {
+            std::slice::from_raw_parts(values.as_ptr() as *const Measure<T, Self>, values.len())
+        }
+    }
+
+    /// View a mutable slice of values as a mutable slice of measures in
+    /// this unit, without copying.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let mut raw = [1., 2., 3.];
+    /// for distance in Meter::from_slice_mut(&mut raw) {
+    ///     *distance = *distance * 2.;
+    /// }
+    /// assert_eq!(raw, [2., 4., 6.]);
+    /// ```
+    #[track_caller]
+    fn from_slice_mut<T>(values: &mut [T]) -> &mut [Measure<T, Self>]
+    where
+        Self: Sized,
+    {
+        check_layout::<T, Self>();
+        // Safe, as `Measure<T, Self>` is a `repr(transparent)` wrapper of `T`.
+        unsafe {
+            std::slice::from_raw_parts_mut(
+                values.as_mut_ptr() as *mut Measure<T, Self>,
+                values.len(),
+            )
+        }
+    }
+
     /// Return a runtime representation of this unit.
     /// This method is designed for indexing and debugging.
     /// Not particularly fast.
+    ///
+    /// # Panics
+    ///
+    /// If an exponent does not fit in an `i16`. This may only happen with
+    /// absurdly large units, typically produced by a runaway generic
+    /// composition. Use `try_as_runtime` to detect such units.
+    #[track_caller]
     fn as_runtime() -> RuntimeUnit {
+        match Self::try_as_runtime(i16::MAX) {
+            Ok(runtime) => runtime,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Return a runtime representation of this unit, provided that all its
+    /// exponents are within `[-limit, limit]`.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// type Cube<A> = Mul<A, Mul<A, A>>;
+    /// type Huge = Cube<Cube<Cube<Meter>>>; // m^27
+    ///
+    /// assert_eq!(Huge::try_as_runtime(27).unwrap().to_string(), "m^27");
+    ///
+    /// let err = Huge::try_as_runtime(16).unwrap_err();
+    /// assert_eq!(err.to_string(), "Exponent of `m` exceeds 16");
+    ///
+    /// // Only the final exponents are subject to `limit`.
+    /// assert!(Mul::<Huge, Inv<Huge>>::try_as_runtime(16).is_ok());
+    /// ```
+    fn try_as_runtime(limit: i16) -> Result<RuntimeUnit, ExponentOverflow> {
         let mut runtime = RuntimeUnit::new();
-        Self::add_to_runtime(&mut runtime, true);
-        runtime
+        Self::add_to_runtime(&mut runtime, true)?;
+        if let Some(name) = runtime
+            .dimensions
+            .iter()
+            .find(|&(_, n)| n.checked_abs().map(|n| n > limit).unwrap_or(true))
+            .map(|(dimension, _)| dimension.name.to_string())
+        {
+            return Err(ExponentOverflow { name, limit });
+        }
+        Ok(runtime)
+    }
+
+    /// Display this unit, as `RuntimeUnit` does, but without allocating.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// type Speed = Mul<Meter, Inv<Second>>;
+    /// assert_eq!(format!("{}", Speed::display()), "m * s^-1");
+    /// assert_eq!(format!("{}", Speed::display()), Speed::as_runtime().to_string());
+    /// ```
+    ///
+    /// # Performance note
+    ///
+    /// Units that fit in `SYMBOL` are rendered at compile time, so
+    /// displaying them merely copies their symbol, e.g. in logs. Other
+    /// units are displayed through `as_runtime`.
+    fn display() -> DisplayUnit<Self> {
+        DisplayUnit(PhantomData)
     }
 
     /// Add a compile-type unit to a dynamic unit, either
@@ -43,33 +421,80 @@ pub trait Unit: private::Sealed {
     /// or in negative position (if `positive` is `false`).
     ///
     /// Used internally by `as_runtime`, not particularly interesting otherwise.
-    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool);
+    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) -> Result<(), ExponentOverflow>;
+
+    /// The symbol of this unit, as displayed by `RuntimeUnit`, but
+    /// computed at compile time.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// type Speed = Mul<Meter, Inv<Second>>;
+    /// const SPEED: &'static str = Speed::SYMBOL;
+    /// assert_eq!(SPEED, "m * s^-1");
+    /// assert_eq!(SPEED, Speed::as_runtime().to_string());
+    ///
+    /// assert_eq!(Mul::<Inv<Second>, Mul<Meter, Second>>::SYMBOL, "m");
+    /// assert_eq!(Dimensionless::SYMBOL, "");
+    /// ```
+    ///
+    /// Units with more than `MAX_SYMBOL_DIMENSIONS` distinct base units,
+    /// or symbols longer than `MAX_SYMBOL_LEN` bytes, fail to compile if
+    /// they use `SYMBOL`.
+    const SYMBOL: &'static str = ::symbol::Symbol::<Self>::SYMBOL;
+
+    #[doc(hidden)]
+    const CANONICAL: ::symbol::Canonical;
 }
 impl<T: BaseUnit> Unit for T {
-    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) {
+    const CANONICAL: ::symbol::Canonical =
+        ::symbol::Canonical::base(T::NAMESPACE, T::NAME, T::DISAMBIGUATOR);
+
+    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) -> Result<(), ExponentOverflow> {
+        let dimension = Dimension::of::<T>();
         let is_empty = {
-            let entry = repr
-                .dimensions
-                .entry(TypeId::of::<T>())
-                .or_insert_with(|| (T::NAME.to_string(), 0));
-            if positive {
-                entry.1 += 1;
+            let entry = repr.dimensions.entry(dimension).or_insert(0);
+            let exponent = if positive {
+                entry.checked_add(1)
             } else {
-                entry.1 -= 1;
-            }
-            entry.1 == 0
+                entry.checked_sub(1)
+            };
+            *entry = exponent.ok_or_else(|| ExponentOverflow {
+                name: T::NAME.to_string(),
+                limit: i16::MAX,
+            })?;
+            *entry == 0
         };
         if is_empty {
-            repr.dimensions.remove(&TypeId::of::<T>());
+            repr.dimensions.remove(&dimension);
         }
+        Ok(())
     }
 }
 
+/// Ensure, in debug builds, that `Measure<T, U>` has the same layout
+/// as `T`, before reinterpreting one as the other.
+#[track_caller]
+fn check_layout<T, U: Unit>() {
+    debug_assert_eq!(
+        std::mem::size_of::<T>(),
+        std::mem::size_of::<Measure<T, U>>()
+    );
+    debug_assert_eq!(
+        std::mem::align_of::<T>(),
+        std::mem::align_of::<Measure<T, U>>()
+    );
+}
+
 /// A value with a unit.
+///
+/// A `Measure<T, U>` has the same representation as a `T`.
 #[allow(unused_attributes)]
-#[rustc_yaiouom_check_unify_measure]
+#[cfg_attr(feature = "checker", rustc_yaiouom_check_unify_measure)]
+#[repr(transparent)]
 pub struct Measure<T, U: Unit> {
-    value: T,
+    pub(crate) value: T,
     unit: PhantomData<U>,
 }
 
@@ -90,7 +515,7 @@ impl<T, U: Unit> Measure<T, U> {
     ///
     /// Future versions will make this constructor private and
     /// hide it behind syntactic sugar.
-    pub fn new(value: T) -> Self {
+    pub const fn new(value: T) -> Self {
         Self {
             value,
             unit: PhantomData,
@@ -156,118 +581,604 @@ impl<T, U: Unit> Measure<T, U> {
     /// ## Dynamic checks
     ///
     /// As a fallback, **in debug builds**, each call to `unify` will panic
-    /// if type `V` is not equivalent ot type `U`.
+    /// if type `V` is not equivalent ot type `U`. The message describes
+    /// both units and what the source unit is missing and has in excess,
+    /// e.g.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let speed = Meter::new(10.) / Second::new(2.);
+    /// let result = std::panic::catch_unwind(|| {
+    ///     let _: Measure<f64, Inv<Second>> = speed.unify();
+    /// });
+    /// if cfg!(debug_assertions) {
+    ///     let panic = result.unwrap_err();
+    ///     assert_eq!(
+    ///         panic.downcast_ref::<String>().unwrap(),
+    ///         "Cannot unify `Measure<f64, _>` from `m * s^-1` into `s^-1` (extra: m)"
+    ///     );
+    /// }
+    /// ```
+    ///
+    /// The message cannot show the value, as `unify` does not require
+    /// `T: Debug`. Use `checked_unify` for this.
     #[allow(unused_attributes)]
-    #[rustc_yaiouom_check_unify]
+    #[cfg_attr(feature = "checker", rustc_yaiouom_check_unify)]
+    #[track_caller]
     pub fn unify<V: Unit>(self) -> Measure<T, V> {
+        #[cfg(feature = "audit")]
+        ::audit::record::<T, U, V>(::audit::ConversionKind::Unify);
         // First, ensure that we can perform conversion.
-        debug_assert_eq!(U::as_runtime(), V::as_runtime());
+        if cfg!(debug_assertions) {
+            let found = U::as_runtime();
+            let expected = V::as_runtime();
+            if found != expected {
+                unify_failed::<T>(None, UnitMismatch::new(expected, found));
+            }
+        }
         Measure {
             value: self.value,
             unit: PhantomData,
         }
     }
 
-    /// Convert between two value representations (e.g. `u32` vs `u64`)
-    /// in the same unit.
-    ///
-    /// Ideally, this should be an implementation of `From`, but it conflicts
-    /// with the reflexive implementation.
+    /// Same as `unify`, but the dynamic check takes place in all builds
+    /// and its message shows the value, e.g.
     ///
     /// ```
     /// use yaiouom::*;
     /// use yaiouom::si::*;
     ///
-    /// let one_meter_usize = Meter::new(1 as i32);
-    /// let one_meter_isize : Measure<i64, Meter> = Measure::from(one_meter_usize);
+    /// let speed = Meter::new(10.) / Second::new(2.);
+    /// let result = std::panic::catch_unwind(|| {
+    ///     let _: Measure<f64, Inv<Second>> = speed.checked_unify();
+    /// });
+    /// let panic = result.unwrap_err();
+    /// assert_eq!(
+    ///     panic.downcast_ref::<String>().unwrap(),
+    ///     "Cannot unify `5.0` from `m * s^-1` into `s^-1` (extra: m)"
+    /// );
     /// ```
-    pub fn from<V>(value: Measure<V, U>) -> Self
+    #[allow(unused_attributes)]
+    #[cfg_attr(feature = "checker", rustc_yaiouom_check_unify)]
+    #[track_caller]
+    pub fn checked_unify<V: Unit>(self) -> Measure<T, V>
     where
-        T: From<V>,
+        T: std::fmt::Debug,
     {
-        Self {
-            value: From::from(value.value),
+        #[cfg(feature = "audit")]
+        ::audit::record::<T, U, V>(::audit::ConversionKind::Unify);
+        let found = U::as_runtime();
+        let expected = V::as_runtime();
+        if found != expected {
+            unify_failed::<T>(Some(&self.value), UnitMismatch::new(expected, found));
+        }
+        Measure {
+            value: self.value,
             unit: PhantomData,
         }
     }
 
-    /// Convert between two value representations (e.g. `u32` vs `u64`)
-    /// In the same unit.
+    /// Same as `unify`, without the dynamic check of debug builds.
     ///
-    /// Ideally, this should be an implementation of `From`, but it conflicts
-    /// with the reflexive implementation.
+    /// The linter checks `unify_trusted` just as it checks `unify`. This is
+    /// meant for hot code inside tight loops, in which the dynamic check
+    /// would dominate the profile of debug builds. Rather than calling it
+    /// directly, annotate such functions with `#[yaiouom::trusted]`
+    /// (feature `derive`), which replaces their calls to `unify`.
     ///
     /// ```
     /// use yaiouom::*;
     /// use yaiouom::si::*;
     ///
-    /// let one_meter_usize : Measure<i32, Meter> = Measure::new(1);
-    /// let one_meter_isize : Measure<i64, Meter> = one_meter_usize.into();
+    /// let speed = Meter::new(10.) / Second::new(2.);
+    /// let speed: Measure<f64, Mul<Inv<Second>, Meter>> = speed.unify_trusted();
+    /// assert_eq!(speed.as_ref(), &5.);
     /// ```
-    pub fn into<V>(self) -> Measure<V, U>
-    where
-        T: Into<V>,
-    {
+    #[allow(unused_attributes)]
+    #[cfg_attr(feature = "checker", rustc_yaiouom_check_unify)]
+    #[cfg_attr(feature = "audit", track_caller)]
+    pub fn unify_trusted<V: Unit>(self) -> Measure<T, V> {
+        #[cfg(feature = "audit")]
+        ::audit::record::<T, U, V>(::audit::ConversionKind::Unify);
         Measure {
-            value: Into::into(self.value),
+            value: self.value,
             unit: PhantomData,
         }
     }
 
-    /// Extract a runtime representation of a unit.
+    /// Compare two units of measure (**not** their values), at runtime.
     ///
-    /// This runtime representation is designed mainly for debugging purposes.
+    /// This is the dynamically checked counterpart of `unify`. The check
+    /// takes place in all builds, regardless of the linter.
     ///
     /// ```
     /// use yaiouom::*;
     /// use yaiouom::si::*;
     ///
-    /// let one_meter_usize : Measure<i32, Meter> = Measure::new(1);
-    /// assert_eq!(one_meter_usize.as_runtime().to_string(), "m");
+    /// let speed = Meter::new(10.) / Second::new(2.);
+    ///
+    /// let ok: Result<Measure<_, Mul<Inv<Second>, Meter>>, _> = speed.try_unify();
+    /// assert!(ok.is_ok());
+    ///
+    /// let err = speed.try_unify::<Mul<Meter, Second>>().unwrap_err();
+    /// assert_eq!(err.diff().to_string(), "missing: s; extra: s^-1");
     /// ```
     ///
     /// # Performance note
     ///
     /// This method is fine for debugging, but should not be used in a tight loop.
-    pub fn as_runtime(&self) -> RuntimeUnit {
-        U::as_runtime()
-    }
-}
-
-impl<T, A: Unit> Measure<T, Mul<A, A>>
-where
-    T: num_traits::float::Float,
-{
-    /// Compute the square root of a value.
-    pub fn sqrt(self) -> Measure<T, A> {
-        Measure {
-            value: self.value.sqrt(),
-            unit: PhantomData,
+    #[track_caller]
+    pub fn try_unify<V: Unit>(self) -> Result<Measure<T, V>, UnitMismatch> {
+        #[cfg(feature = "audit")]
+        ::audit::record::<T, U, V>(::audit::ConversionKind::TryUnify);
+        let found = U::as_runtime();
+        let expected = V::as_runtime();
+        if found != expected {
+            return Err(UnitMismatch::new(expected, found));
         }
-    }
-}
-
-impl<T, U: Unit> num_traits::ops::inv::Inv for Measure<T, U>
-where
-    T: num_traits::ops::inv::Inv,
-{
-    type Output = Measure<T::Output, Inv<U>>;
-    /// Unary operator for retrieving the multiplicative inverse, or reciprocal, of a value.
-    fn inv(self) -> Self::Output {
-        Measure {
-            value: self.value.inv(),
+        Ok(Measure {
+            value: self.value,
             unit: PhantomData,
-        }
+        })
     }
-}
-
-impl<T, U: Unit> AsRef<T> for Measure<T, U> {
-    fn as_ref(&self) -> &T {
-        &self.value
-    }
-}
 
-impl<T, U: Unit> Clone for Measure<T, U>
+    /// Convert to an equivalent unit, using a proof that both units are equal.
+    ///
+    /// This is the statically checked counterpart of `unify`, for use when
+    /// the linter is not available. See module [logics](logics/index.html)
+    /// for the list of proof combinators. Type parameters of a proof may
+    /// usually be left to inference.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    /// use yaiouom::logics::*;
+    ///
+    /// let speed = Meter::new(10f64) / Second::new(2f64);
+    ///
+    /// // m * s^-1 == s^-1 * m
+    /// let speed: Measure<_, Mul<Inv<Second>, Meter>> = speed.rewrite::<PComm<_, _>>();
+    ///
+    /// // Each step may be performed separately...
+    /// let distance = speed * Second::new(2f64);
+    /// let distance = distance
+    ///     .rewrite::<PAssoc<_, _, _>>()                   // s^-1 * (m * s)
+    ///     .rewrite::<PMul<PId<_>, PComm<_, _>>>()         // s^-1 * (s * m)
+    ///     .rewrite::<PSym<PAssoc<_, _, _>>>()             // (s^-1 * s) * m
+    ///     .rewrite::<PMul<PComm<_, _>, PId<_>>>()         // (s * s^-1) * m
+    ///     .rewrite::<PMul<PInverse<_>, PId<_>>>()         // Dimensionless * m
+    ///     .rewrite::<PComm<_, _>>()                       // m * Dimensionless
+    ///     .rewrite::<PDimensionless<_>>();                // m
+    /// let _: Measure<_, Meter> = distance;
+    /// assert_eq!(distance.as_ref(), &10.);
+    /// ```
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    /// use yaiouom::logics::*;
+    ///
+    /// // ...or composed into a single, reusable, proof.
+    /// type Regroup<A, B, C> = PThen<PAssoc<A, B, C>, PMul<PId<A>, PComm<B, C>>>;
+    ///
+    /// let volume = Meter::new(1f64) * Meter::new(2f64) * Second::new(3f64);
+    /// let volume: Measure<_, Mul<Meter, Mul<Second, Meter>>> = volume.rewrite::<Regroup<_, _, _>>();
+    /// assert_eq!(volume.as_ref(), &6.);
+    /// ```
+    ///
+    /// Unlike `unify`, `rewrite` cannot be used to convert between distinct units:
+    ///
+    /// ```compile_fail
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    /// use yaiouom::logics::*;
+    ///
+    /// let speed = Meter::new(10f64) / Second::new(2f64);
+    /// let speed: Measure<_, Mul<Meter, Second>> = speed.rewrite::<PComm<_, _>>();
+    /// ```
+    pub fn rewrite<P>(self) -> Measure<T, P::Right>
+    where
+        P: Proof<Left = U>,
+    {
+        Measure {
+            value: self.value,
+            unit: PhantomData,
+        }
+    }
+
+    /// Reinterpret a measure with another unit, deliberately, e.g. to turn
+    /// the raw value of a sensor into a physical quantity after calibration.
+    ///
+    /// Unlike `unify`, this is neither checked by yaiouom-checker nor at
+    /// runtime: `justification` documents why the new unit is correct.
+    /// Build with `yaiouom-checker --yaiouom-report-reinterpret` to list
+    /// all the calls to `reinterpret_with`, along with their justifications.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// struct Tick;
+    /// impl BaseUnit for Tick {
+    ///     const NAME: &'static str = "tick";
+    /// }
+    ///
+    /// let ticks: Measure<u64, Tick> = Measure::new(3_000);
+    /// let duration: Measure<u64, Second> = (ticks / 1_000).reinterpret_with("The clock runs at 1 kHz");
+    /// assert_eq!(duration, Second::new(3));
+    /// ```
+    #[cfg_attr(feature = "checker", rustc_yaiouom_reinterpret)]
+    #[cfg_attr(feature = "audit", track_caller)]
+    #[allow(unused_variables)]
+    pub fn reinterpret_with<V: Unit>(self, justification: &'static str) -> Measure<T, V> {
+        #[cfg(feature = "audit")]
+        ::audit::record::<T, U, V>(::audit::ConversionKind::Reinterpret(justification));
+        Measure {
+            value: self.value,
+            unit: PhantomData,
+        }
+    }
+
+    /// View a slice of measures as a slice of values, without copying.
+    ///
+    /// This is the converse of `Unit::from_slice`, e.g. to hand measures
+    /// over to a library that does not know about units.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let distances = vec![Meter::new(1.), Meter::new(2.)];
+    /// let raw: &[f64] = Measure::as_values(&distances);
+    /// assert_eq!(raw, &[1., 2.]);
+    /// ```
+    pub fn as_values(measures: &[Self]) -> &[T] {
+        check_layout::<T, U>();
+        // Safe, as `Measure<T, U>` is a `repr(transparent)` wrapper of `T`.
+        unsafe { std::slice::from_raw_parts(measures.as_ptr() as *const T, measures.len()) }
+    }
+
+    /// View a mutable slice of measures as a mutable slice of values,
+    /// without copying.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let mut distances = vec![Meter::new(2.), Meter::new(1.)];
+    /// Measure::as_values_mut(&mut distances).sort_by(|a, b| a.partial_cmp(b).unwrap());
+    /// assert_eq!(distances, vec![Meter::new(1.), Meter::new(2.)]);
+    /// ```
+    pub fn as_values_mut(measures: &mut [Self]) -> &mut [T] {
+        check_layout::<T, U>();
+        // Safe, as `Measure<T, U>` is a `repr(transparent)` wrapper of `T`.
+        unsafe { std::slice::from_raw_parts_mut(measures.as_mut_ptr() as *mut T, measures.len()) }
+    }
+
+    /// Convert between two value representations (e.g. `u32` vs `u64`)
+    /// in the same unit.
+    ///
+    /// Ideally, this should be an implementation of `From`, but it conflicts
+    /// with the reflexive implementation.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let one_meter_usize = Meter::new(1 as i32);
+    /// let one_meter_isize : Measure<i64, Meter> = Measure::from(one_meter_usize);
+    /// ```
+    pub fn from<V>(value: Measure<V, U>) -> Self
+    where
+        T: From<V>,
+    {
+        Self {
+            value: From::from(value.value),
+            unit: PhantomData,
+        }
+    }
+
+    /// Convert between two value representations (e.g. `u32` vs `u64`)
+    /// In the same unit.
+    ///
+    /// Ideally, this should be an implementation of `From`, but it conflicts
+    /// with the reflexive implementation.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let one_meter_usize : Measure<i32, Meter> = Measure::new(1);
+    /// let one_meter_isize : Measure<i64, Meter> = one_meter_usize.into();
+    /// ```
+    pub fn into<V>(self) -> Measure<V, U>
+    where
+        T: Into<V>,
+    {
+        Measure {
+            value: Into::into(self.value),
+            unit: PhantomData,
+        }
+    }
+
+    /// Extract a runtime representation of a unit.
+    ///
+    /// This runtime representation is designed mainly for debugging purposes.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let one_meter_usize : Measure<i32, Meter> = Measure::new(1);
+    /// assert_eq!(one_meter_usize.as_runtime().to_string(), "m");
+    /// ```
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    #[track_caller]
+    pub fn as_runtime(&self) -> RuntimeUnit {
+        U::as_runtime()
+    }
+
+    /// Split this measure into its value and its unit, e.g. for a layer
+    /// that serializes values and units separately.
+    ///
+    /// See `from_parts` for the converse.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let (value, unit) = (Meter::new(10.) / Second::new(2.)).into_parts();
+    /// assert_eq!(value, 5.);
+    /// assert_eq!(unit.to_string(), "m * s^-1");
+    ///
+    /// let speed: Measure<f64, Mul<Meter, Inv<Second>>> = Measure::from_parts(value, unit.clone()).unwrap();
+    /// assert_eq!(speed.as_ref(), &5.);
+    ///
+    /// let err = Measure::<f64, Meter>::from_parts(value, unit).unwrap_err();
+    /// assert_eq!(err.diff().to_string(), "extra: s^-1");
+    /// ```
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    #[track_caller]
+    pub fn into_parts(self) -> (T, RuntimeUnit) {
+        (self.value, U::as_runtime())
+    }
+
+    /// Rebuild a measure from its value and its unit, as returned by
+    /// `into_parts`, if `unit` is `U`.
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    #[track_caller]
+    pub fn from_parts(value: T, unit: RuntimeUnit) -> Result<Self, UnitMismatch> {
+        let expected = U::as_runtime();
+        if unit != expected {
+            return Err(UnitMismatch::new(expected, unit));
+        }
+        Ok(Measure::new(value))
+    }
+
+    /// Split this measure into its value and the symbol of its unit, e.g.
+    /// to hand it over to a scripting language.
+    ///
+    /// See `from_tagged` for the converse.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let tagged = (Meter::new(10.) / Second::new(2.)).to_tagged();
+    /// assert_eq!(tagged, (5., "m * s^-1"));
+    ///
+    /// // Factors may be given in any order, and repeated.
+    /// let speed: Measure<f64, Mul<Meter, Inv<Second>>> = Measure::from_tagged((5., "s^-1 * m")).unwrap();
+    /// assert_eq!(speed.as_ref(), &5.);
+    /// let area: Measure<f64, Mul<Meter, Meter>> = Measure::from_tagged((5., "m * m^1")).unwrap();
+    /// assert_eq!(area.as_ref(), &5.);
+    ///
+    /// let err = Measure::<f64, Meter>::from_tagged(tagged).unwrap_err();
+    /// assert_eq!(err.to_string(), "Unit mismatch: expected `m`, found `m * s^-1`");
+    ///
+    /// // Symbols cannot tell apart base units with the same name.
+    /// mod survey {
+    ///     pub struct Mile;
+    ///     impl yaiouom::BaseUnit for Mile {
+    ///         const NAMESPACE: &'static str = "survey";
+    ///         const NAME: &'static str = "m";
+    ///     }
+    /// }
+    /// assert!(Measure::<f64, Mul<Meter, survey::Mile>>::from_tagged((1., "m^2")).is_err());
+    /// ```
+    pub fn to_tagged(self) -> (T, &'static str) {
+        (self.value, U::SYMBOL)
+    }
+
+    /// Rebuild a measure from its value and the symbol of its unit, as
+    /// returned by `to_tagged`, if the symbol is that of `U`.
+    ///
+    /// Symbols only contain the `BaseUnit::NAME` of base units, so this
+    /// fails if `U` has several base units with the same name, in distinct
+    /// namespaces.
+    pub fn from_tagged((value, unit): (T, &str)) -> Result<Self, TagMismatch> {
+        if !symbol_matches(unit, &U::as_runtime()) {
+            return Err(TagMismatch {
+                expected: U::SYMBOL,
+                found: unit.to_string(),
+            });
+        }
+        Ok(Measure::new(value))
+    }
+}
+
+/// The exponent of each name in a symbol of unit, as displayed by
+/// `RuntimeUnit`, e.g. `m * s^-1`, or `None` if it is malformed.
+///
+/// Units written by hand or by other tools may list their factors in any
+/// order, repeat them, or write exponent 1, so they must not be compared
+/// as strings.
+pub(crate) fn symbol_exponents(symbol: &str) -> Option<BTreeMap<&str, i32>> {
+    let mut exponents = BTreeMap::new();
+    if symbol.trim().is_empty() {
+        return Some(exponents);
+    }
+    for factor in symbol.split('*').map(str::trim) {
+        // A `NAME` may contain `^`, e.g. `"x^y"`, if not followed by a
+        // number.
+        let (name, exponent) = match factor.rsplit_once('^') {
+            Some((name, exponent)) => match exponent.parse::<i16>() {
+                Ok(exponent) => (name.trim_end(), i32::from(exponent)),
+                Err(_) => (factor, 1),
+            },
+            None => (factor, 1),
+        };
+        if name.is_empty() {
+            return None;
+        }
+        *exponents.entry(name).or_insert(0) += exponent;
+    }
+    exponents.retain(|_, exponent| *exponent != 0);
+    Some(exponents)
+}
+
+/// Whether `symbol` is a symbol of `unit`, whatever the order of its
+/// factors.
+///
+/// If several base units of `unit` have the same name, e.g. in distinct
+/// namespaces, no symbol can tell them apart, so this returns `false`.
+pub(crate) fn symbol_matches(symbol: &str, unit: &RuntimeUnit) -> bool {
+    let found = match symbol_exponents(symbol) {
+        Some(found) => found,
+        None => return false,
+    };
+    let mut expected = BTreeMap::new();
+    for (dimension, &exponent) in &unit.dimensions {
+        if expected
+            .insert(dimension.name, i32::from(exponent))
+            .is_some()
+        {
+            return false;
+        }
+    }
+    found == expected
+}
+
+/// Report a failure of the dynamic check of `unify` or `checked_unify`.
+///
+/// Without a `value`, e.g. if `T` is not `Debug`, display its type.
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn unify_failed<T>(value: Option<&dyn std::fmt::Debug>, mismatch: UnitMismatch) -> ! {
+    let value = match value {
+        Some(value) => format!("{:?}", value),
+        None => format!("Measure<{}, _>", std::any::type_name::<T>()),
+    };
+    panic!(
+        "Cannot unify `{}` from `{}` into `{}` ({})",
+        value,
+        unit_name(mismatch.found()),
+        unit_name(mismatch.expected()),
+        mismatch.diff()
+    )
+}
+
+/// A unit, as displayed in panic messages.
+fn unit_name(unit: &RuntimeUnit) -> String {
+    let unit = unit.to_string();
+    if unit.is_empty() {
+        "Dimensionless".to_string()
+    } else {
+        unit
+    }
+}
+
+/// A type of measure, as displayed in panic messages, e.g. `Measure<i32, m>`.
+fn measure_name<T, U: Unit>() -> String {
+    format!(
+        "Measure<{}, {}>",
+        std::any::type_name::<T>(),
+        unit_name(&U::as_runtime())
+    )
+}
+
+/// Compute arithmetic operation `op`.
+///
+/// With feature `debug-math`, if `op` panics, e.g. because of an integer
+/// overflow, panic again at the location of the caller with a message that
+/// adds `describe()`, i.e. the operation and the units involved.
+#[cfg(feature = "debug-math")]
+#[track_caller]
+fn arith<R, F, D>(op: F, describe: D) -> R
+where
+    F: FnOnce() -> R,
+    D: FnOnce() -> String,
+{
+    let payload = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(op)) {
+        Ok(result) => return result,
+        Err(payload) => payload,
+    };
+    let message = if let Some(message) = payload.downcast_ref::<&'static str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        std::panic::resume_unwind(payload)
+    };
+    panic!("{}, in `{}`", message, describe())
+}
+#[cfg(not(feature = "debug-math"))]
+#[inline(always)]
+fn arith<R, F, D>(op: F, _: D) -> R
+where
+    F: FnOnce() -> R,
+    D: FnOnce() -> String,
+{
+    op()
+}
+
+impl<T, A: Unit> Measure<T, Mul<A, A>>
+where
+    T: num_traits::float::Float,
+{
+    /// Compute the square root of a value.
+    pub fn sqrt(self) -> Measure<T, A> {
+        Measure {
+            value: self.value.sqrt(),
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T, U: Unit> num_traits::ops::inv::Inv for Measure<T, U>
+where
+    T: num_traits::ops::inv::Inv,
+{
+    type Output = Measure<T::Output, Inv<U>>;
+    /// Unary operator for retrieving the multiplicative inverse, or reciprocal, of a value.
+    fn inv(self) -> Self::Output {
+        Measure {
+            value: self.value.inv(),
+            unit: PhantomData,
+        }
+    }
+}
+
+impl<T, U: Unit> AsRef<T> for Measure<T, U> {
+    fn as_ref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T, U: Unit> Clone for Measure<T, U>
 where
     T: Clone,
 {
@@ -290,7 +1201,27 @@ where
     }
 }
 
-impl<T, U: Unit> Eq for Measure<T, U> where T: PartialEq {}
+/// A measure is `Eq` only if its values are `Eq`.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// fn is_eq<X: Eq>() {}
+/// is_eq::<Measure<i32, Meter>>();
+/// ```
+///
+/// In particular, measures backed by floating-point numbers are not `Eq`,
+/// as `NaN != NaN`.
+///
+/// ```compile_fail
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// fn is_eq<X: Eq>() {}
+/// is_eq::<Measure<f64, Meter>>();
+/// ```
+impl<T, U: Unit> Eq for Measure<T, U> where T: Eq {}
 
 impl<T, U: Unit> PartialOrd for Measure<T, U>
 where
@@ -301,6 +1232,30 @@ where
     }
 }
 
+/// A measure is `Ord` only if its values are `Ord`.
+///
+/// ```compile_fail
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let mut lengths = vec![Meter::new(2.), Meter::new(1.)];
+/// lengths.sort(); // `f64` is not `Ord`.
+/// ```
+///
+/// Use e.g. `sort_by` and `partial_cmp` instead.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let mut lengths = vec![Meter::new(2.), Meter::new(1.)];
+/// lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(lengths, vec![Meter::new(1.), Meter::new(2.)]);
+///
+/// let mut integer_lengths = vec![Meter::new(2), Meter::new(1)];
+/// integer_lengths.sort();
+/// assert_eq!(integer_lengths, vec![Meter::new(1), Meter::new(2)]);
+/// ```
 impl<T, U: Unit> Ord for Measure<T, U>
 where
     T: Ord,
@@ -315,9 +1270,11 @@ where
     T: std::ops::Neg,
 {
     type Output = Measure<T::Output, U>;
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn neg(self) -> Self::Output {
+        let value = self.value;
         Measure {
-            value: self.value.neg(),
+            value: arith(|| value.neg(), || format!("-{}", measure_name::<T, U>())),
             unit: PhantomData,
         }
     }
@@ -338,10 +1295,117 @@ where
     }
 }
 
+/// The unit of the sum of a measure with unit `Self` and a measure with
+/// unit `Rhs`, as computed by `Measure::add_across`.
+///
+/// Out of the box, one may only add two measures with the same unit. Implement
+/// this trait to add measures with related units, e.g. a point in time and a
+/// duration, or a point and a vector.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// /// A date, in seconds since the epoch.
+/// struct Date;
+/// impl BaseUnit for Date {
+///     const NAME: &'static str = "date";
+/// }
+/// impl AddAcross<Second> for Date {
+///     type Output = Date;
+/// }
+///
+/// let start: Measure<i64, Date> = Measure::new(1_000);
+/// let end = start.add_across(Second::new(60));
+/// assert_eq!(end, Measure::<i64, Date>::new(1_060));
+/// ```
+pub trait AddAcross<Rhs: Unit>: Unit {
+    type Output: Unit;
+}
+
+/// The unit of the difference between a measure with unit `Self` and
+/// a measure with unit `Rhs`, as computed by `Measure::sub_across`.
+///
+/// Out of the box, one may only subtract two measures with the same unit,
+/// and the difference has that unit. Implement this trait to subtract
+/// measures with related units, e.g. a duration from a point in time, or
+/// to give another unit to the difference, e.g. between two points in time.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// struct Date;
+/// impl BaseUnit for Date {
+///     const NAME: &'static str = "date";
+/// }
+/// impl SubAcross<Second> for Date {
+///     type Output = Date;
+/// }
+/// impl SubAcross<Date> for Date {
+///     type Output = Second;
+/// }
+///
+/// let end: Measure<i64, Date> = Measure::new(1_060);
+/// let start = end.sub_across(Second::new(60));
+/// assert_eq!(start, Measure::<i64, Date>::new(1_000));
+///
+/// let duration = end.sub_across(start);
+/// assert_eq!(duration, Second::new(60));
+///
+/// // The difference of `-` still has the unit of its operands.
+/// let _: Measure<i64, Date> = end - start;
+/// ```
+pub trait SubAcross<Rhs: Unit>: Unit {
+    type Output: Unit;
+}
+
+impl<T, U: Unit> Measure<T, U> {
+    /// Add a measure with a unit related to `U` by `AddAcross`.
+    #[cfg_attr(feature = "debug-math", track_caller)]
+    pub fn add_across<V: Unit>(
+        self,
+        rhs: Measure<T, V>,
+    ) -> Measure<<T as std::ops::Add>::Output, U::Output>
+    where
+        T: std::ops::Add<T>,
+        U: AddAcross<V>,
+    {
+        let (lhs, rhs) = (self.value, rhs.value);
+        Measure {
+            value: arith(
+                || lhs + rhs,
+                || format!("{} + {}", measure_name::<T, U>(), measure_name::<T, V>()),
+            ),
+            unit: PhantomData,
+        }
+    }
+
+    /// Subtract a measure with a unit related to `U` by `SubAcross`.
+    #[cfg_attr(feature = "debug-math", track_caller)]
+    pub fn sub_across<V: Unit>(
+        self,
+        rhs: Measure<T, V>,
+    ) -> Measure<<T as std::ops::Sub>::Output, U::Output>
+    where
+        T: std::ops::Sub<T>,
+        U: SubAcross<V>,
+    {
+        let (lhs, rhs) = (self.value, rhs.value);
+        Measure {
+            value: arith(
+                || lhs - rhs,
+                || format!("{} - {}", measure_name::<T, U>(), measure_name::<T, V>()),
+            ),
+            unit: PhantomData,
+        }
+    }
+}
+
 /// Out of the box, one may only add two values with the same unit.
 ///
-/// It remains, however, possible to manually implement Add e.g. for
-/// a time and a duration or a point and a vector.
+/// To add measures with related units, e.g. a time and a duration or
+/// a point and a vector, see `AddAcross`.
 ///
 /// ```
 /// use yaiouom::*;
@@ -353,12 +1417,47 @@ where
 /// ```
 impl<T, U: Unit> std::ops::Add<Self> for Measure<T, U>
 where
-    T: std::ops::Add<Output = T>,
+    T: std::ops::Add<T>,
 {
-    type Output = Self;
-    fn add(self, rhs: Self) -> Self {
+    type Output = Measure<<T as std::ops::Add>::Output, U>;
+    #[cfg_attr(feature = "debug-math", track_caller)]
+    fn add(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs.value);
         Measure {
-            value: self.value + rhs.value,
+            value: arith(
+                || lhs + rhs,
+                || format!("{} + {}", measure_name::<T, U>(), measure_name::<T, U>()),
+            ),
+            unit: PhantomData,
+        }
+    }
+}
+
+/// Out of the box, one may only subtract two values with the same unit.
+///
+/// To subtract measures with related units, see `SubAcross`.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let three_meters : Measure<i32, Meter> = Measure::new(3);
+/// let two_meters = three_meters - Meter::new(1);
+/// assert_eq!(*two_meters.as_ref(), 2);
+/// ```
+impl<T, U: Unit> std::ops::Sub<Self> for Measure<T, U>
+where
+    T: std::ops::Sub<T>,
+{
+    type Output = Measure<<T as std::ops::Sub>::Output, U>;
+    #[cfg_attr(feature = "debug-math", track_caller)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs.value);
+        Measure {
+            value: arith(
+                || lhs - rhs,
+                || format!("{} - {}", measure_name::<T, U>(), measure_name::<T, U>()),
+            ),
             unit: PhantomData,
         }
     }
@@ -379,9 +1478,20 @@ where
     /// let ten_meters : Measure<i32, Meter> = one_meter * 10;
     /// assert_eq!(ten_meters.as_ref(), &10);
     /// ```
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn mul(self, rhs: T) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs);
         Measure {
-            value: self.value * rhs,
+            value: arith(
+                || lhs * rhs,
+                || {
+                    format!(
+                        "{} * {}",
+                        measure_name::<T, U>(),
+                        std::any::type_name::<T>()
+                    )
+                },
+            ),
             unit: PhantomData,
         }
     }
@@ -402,9 +1512,14 @@ where
     /// let four_sq_meters : Measure<i32, Mul<Meter, Meter>> = two_meters * two_meters;
     /// assert_eq!(four_sq_meters.as_ref(), &4);
     /// ```
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn mul(self, rhs: Measure<T, V>) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs.value);
         Measure {
-            value: self.value * rhs.value,
+            value: arith(
+                || lhs * rhs,
+                || format!("{} * {}", measure_name::<T, U>(), measure_name::<T, V>()),
+            ),
             unit: PhantomData,
         }
     }
@@ -427,9 +1542,14 @@ where
     ///
     /// assert_eq!(two_meters.as_ref(), other_two_meters.as_ref());
     /// ```
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn div(self, rhs: Measure<T, V>) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs.value);
         Measure {
-            value: self.value / rhs.value,
+            value: arith(
+                || lhs / rhs,
+                || format!("{} / {}", measure_name::<T, U>(), measure_name::<T, V>()),
+            ),
             unit: PhantomData,
         }
     }
@@ -450,20 +1570,101 @@ where
     /// let one_meter : Measure<i32, Meter> = ten_meters / 10;
     /// assert_eq!(one_meter.as_ref(), &1);
     /// ```
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn div(self, rhs: T) -> Self::Output {
+        let (lhs, rhs) = (self.value, rhs);
         Measure {
-            value: self.value / rhs,
+            value: arith(
+                || lhs / rhs,
+                || {
+                    format!(
+                        "{} / {}",
+                        measure_name::<T, U>(),
+                        std::any::type_name::<T>()
+                    )
+                },
+            ),
             unit: PhantomData,
         }
     }
 }
 
+/// Multiply or divide a dimensionless value by a measure, with the
+/// dimensionless value on the left-hand side.
+///
+/// Rust does not let us implement this for any `T`, so we implement it
+/// for primitive numeric types.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let two_seconds : Measure<f64, Second> = Measure::new(2.);
+/// let ten_seconds = 5. * two_seconds;
+/// assert_eq!(ten_seconds.as_ref(), &10.);
+///
+/// // Dividing by a measure produces the inverse unit.
+/// let half_hertz : Measure<f64, Inv<Second>> = 1. / two_seconds;
+/// assert_eq!(half_hertz.as_ref(), &0.5);
+///
+/// // Literals are inferred from the type of the measure.
+/// let two_meters = Meter::new(2f32);
+/// let half = 1. / two_meters;
+/// assert_eq!(half.as_ref(), &0.5f32);
+///
+/// let three_meters = Meter::new(3u8);
+/// assert_eq!((2 * three_meters).as_ref(), &6u8);
+/// ```
+///
+/// However, the scalar and the value of the measure must have the same type:
+///
+/// ```compile_fail
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let two_meters = Meter::new(2f32);
+/// let half = 1f64 / two_meters;
+/// ```
+macro_rules! impl_scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            impl<U: Unit> std::ops::Mul<Measure<$t, U>> for $t {
+                type Output = Measure<$t, U>;
+                #[cfg_attr(feature = "debug-math", track_caller)]
+                fn mul(self, rhs: Measure<$t, U>) -> Self::Output {
+                    let (lhs, rhs) = (self, rhs.value);
+                    Measure {
+                        value: arith(|| lhs * rhs, || format!("{} * {}", stringify!($t), measure_name::<$t, U>())),
+                        unit: PhantomData,
+                    }
+                }
+            }
+            impl<U: Unit> std::ops::Div<Measure<$t, U>> for $t {
+                type Output = Measure<$t, Inv<U>>;
+                #[cfg_attr(feature = "debug-math", track_caller)]
+                fn div(self, rhs: Measure<$t, U>) -> Self::Output {
+                    let (lhs, rhs) = (self, rhs.value);
+                    Measure {
+                        value: arith(|| lhs / rhs, || format!("{} / {}", stringify!($t), measure_name::<$t, U>())),
+                        unit: PhantomData,
+                    }
+                }
+            }
+        )*
+    };
+}
+impl_scalar_ops!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
 impl<T, U: Unit> std::iter::Sum for Measure<T, U>
 where
     T: std::iter::Sum,
 {
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn sum<I: std::iter::Iterator<Item = Self>>(iter: I) -> Self {
-        let sum = iter.map(|m| m.value).sum();
+        let sum = arith(
+            || iter.map(|m| m.value).sum(),
+            || format!("sum of `{}`", measure_name::<T, U>()),
+        );
         Measure {
             value: sum,
             unit: PhantomData,
@@ -475,8 +1676,12 @@ impl<T, U: Unit> std::iter::Product for Measure<T, U>
 where
     T: std::iter::Product,
 {
+    #[cfg_attr(feature = "debug-math", track_caller)]
     fn product<I: std::iter::Iterator<Item = Self>>(iter: I) -> Self {
-        let product = iter.map(|m| m.value).product();
+        let product = arith(
+            || iter.map(|m| m.value).product(),
+            || format!("product of `{}`", measure_name::<T, U>()),
+        );
         Measure {
             value: product,
             unit: PhantomData,
@@ -484,14 +1689,132 @@ where
     }
 }
 
+/// Sum measures by reference, e.g. when `T` is not `Copy`.
+impl<'a, T, U: Unit> std::iter::Sum<&'a Measure<T, U>> for Measure<T, U>
+where
+    T: std::iter::Sum<&'a T>,
+{
+    #[cfg_attr(feature = "debug-math", track_caller)]
+    fn sum<I: std::iter::Iterator<Item = &'a Self>>(iter: I) -> Self {
+        let sum = arith(
+            || iter.map(|m| &m.value).sum(),
+            || format!("sum of `&{}`", measure_name::<T, U>()),
+        );
+        Measure {
+            value: sum,
+            unit: PhantomData,
+        }
+    }
+}
+
+/// Debug a measure as its value followed by the canonical string of its
+/// unit, e.g. `1.5m * s^-1`, see `to_canonical_string`.
 impl<T, U: Unit> std::fmt::Debug for Measure<T, U>
 where
     T: std::fmt::Debug,
 {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
-        write!(fmt, "{:?}{}", self.value, U::as_runtime().to_string())
+        match ::symbol::Symbol::<U>::CANONICAL_STRING {
+            // Rendered at compile time, so this does not allocate.
+            Some(unit) => write!(fmt, "{:?}{}", self.value, unit),
+            None => write!(
+                fmt,
+                "{:?}{}",
+                self.value,
+                U::as_runtime().to_canonical_string()
+            ),
+        }
+    }
+}
+
+/// Display a measure as its value followed by its unit, e.g. `1.5 m * s^-1`.
+///
+/// Formatting options, e.g. the precision, apply to the value.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let speed: Measure<f64, Mul<Meter, Inv<Second>>> = Measure::new(1.2345);
+/// assert_eq!(format!("{:.2}", speed), "1.23 m * s^-1");
+/// assert_eq!(format!("{}", Measure::<f64, Dimensionless>::new(0.5)), "0.5");
+/// ```
+///
+/// This does not allocate, unless the unit is too large for `Unit::SYMBOL`,
+/// see `Unit::display`.
+impl<T, U: Unit> std::fmt::Display for Measure<T, U>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        self.value.fmt(fmt)?;
+        if ::symbol::Symbol::<U>::FAST == Some("") {
+            return Ok(());
+        }
+        write!(fmt, " {}", U::display())
+    }
+}
+
+/// A unit, displayed without allocating when possible, see `Unit::display`.
+pub struct DisplayUnit<U: Unit + ?Sized>(PhantomData<U>);
+impl<U: Unit + ?Sized> std::fmt::Display for DisplayUnit<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match ::symbol::Symbol::<U>::FAST {
+            Some(symbol) => fmt.write_str(symbol),
+            None => write!(fmt, "{}", U::as_runtime()),
+        }
+    }
+}
+impl<U: Unit + ?Sized> std::fmt::Debug for DisplayUnit<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "{}", self)
+    }
+}
+
+/// A value with a unit, e.g. a `Measure` or a newtype wrapping a `Measure`.
+///
+/// Generic containers may use this trait to reason about the units of their
+/// elements.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// struct Altitude(Measure<f64, Meter>);
+/// impl HasUnit for Altitude {
+///     type Unit = Meter;
+/// }
+///
+/// fn unit_of_elements<T: HasUnit>(_: &[T]) -> String {
+///     T::runtime_unit().to_string()
+/// }
+///
+/// assert_eq!(unit_of_elements(&[Altitude(Meter::new(1.))]), "m");
+/// assert_eq!(unit_of_elements(&[Second::new(1.)]), "s");
+/// ```
+///
+/// With feature `derive`, this trait may be derived for structs with a single field:
+///
+/// ```ignore
+/// #[derive(HasUnit)]
+/// struct Altitude(Measure<f64, Meter>);
+/// ```
+pub trait HasUnit {
+    /// The unit of this value.
+    type Unit: Unit;
+
+    /// Return a runtime representation of `Self::Unit`.
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    fn runtime_unit() -> RuntimeUnit {
+        Self::Unit::as_runtime()
     }
 }
+impl<T, U: Unit> HasUnit for Measure<T, U> {
+    type Unit = U;
+}
 
 /*
 impl<T, U: Unit> num_traits::Float for Measure<T, U> where T: num_traits::Float {
@@ -502,15 +1825,50 @@ impl<T, U: Unit> num_traits::Float for Measure<T, U> where T: num_traits::Float
 /// Runtime representation of a unit.
 ///
 /// Used mainly for debug assertions and for debug formatting.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct RuntimeUnit {
-    dimensions: HashMap<TypeId, (String, i32)>,
+    dimensions: HashMap<Dimension, i16>,
 }
+/// Debug a RuntimeUnit as its canonical string, see `to_canonical_string`.
 impl std::fmt::Debug for RuntimeUnit {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
-        write!(fmt, "{}", self.to_string())
+        write!(fmt, "{}", self.to_canonical_string())
+    }
+}
+
+/// Display a RuntimeUnit as a string.
+///
+/// Positives come before negatives, each sorted by namespace, name and
+/// disambiguator, as in `Unit::SYMBOL`. A dimensionless unit returns `""`.
+/// This is currently the canonical string, but only `to_canonical_string`
+/// is guaranteed not to change.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// let unit_str = Mul::<Meter, Inv<Second>>::as_runtime().to_string();
+/// assert_eq!(&unit_str, "m * s^-1");
+///
+/// let unit_str_2 = Mul::<Inv<Second>, Meter>::as_runtime().to_string();
+/// assert_eq!(&unit_str_2, "m * s^-1");
+///
+/// let unit_str_3 = Mul::<Inv<Second>, Mul<Inv<Second>, Meter>>::as_runtime().to_string();
+/// assert_eq!(&unit_str_3, "m * s^-2");
+///
+/// let unit_str_4 = Mul::<Inv<Ampere>, Mul<Inv<Second>, Meter>>::as_runtime().to_string();
+/// assert_eq!(&unit_str_4, "m * A^-1 * s^-1");
+/// ```
+///
+/// # Performance note
+///
+/// This is fine for debugging, but should not be used in a tight loop.
+impl std::fmt::Display for RuntimeUnit {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "{}", self.to_canonical_string())
     }
 }
+
 impl RuntimeUnit {
     fn new() -> Self {
         Self {
@@ -518,56 +1876,572 @@ impl RuntimeUnit {
         }
     }
 
-    /// Display a RuntimeUnit as a string.
-    ///
-    /// Positives come before negatives, but otherwise, the order of elements
-    /// is not specified. A dimensionless unit returns `""`.
+    /// The base units of this unit, with their exponents, sorted by
+    /// namespace, name and disambiguator.
     ///
     /// ```
     /// use yaiouom::*;
     /// use yaiouom::si::*;
     ///
-    /// let unit_str = Mul::<Meter, Inv<Second>>::as_runtime().to_string();
-    /// assert_eq!(&unit_str, "m * s^-1");
+    /// let speed = Mul::<Meter, Inv<Second>>::as_runtime();
+    /// let long: Vec<String> = speed
+    ///     .base_units()
+    ///     .iter()
+    ///     .map(|&(info, exponent)| format!("{}^{}", info.full_name(), exponent))
+    ///     .collect();
+    /// assert_eq!(long, ["metre^1", "second^-1"]);
+    ///
+    /// let (meter, _) = speed.base_units()[0];
+    /// assert_eq!(meter, BaseUnitInfo::of::<Meter>());
+    /// assert_eq!(meter.quantity(), "length");
+    /// assert_eq!(meter.system(), "SI");
+    /// ```
+    pub fn base_units(&self) -> Vec<(BaseUnitInfo, i16)> {
+        let mut base_units: Vec<_> = self
+            .dimensions
+            .iter()
+            .map(|(&dimension, &exponent)| (BaseUnitInfo { dimension }, exponent))
+            .collect();
+        base_units.sort();
+        base_units
+    }
+
+    /// The canonical representation of this unit, e.g. `m * A^-1 * s^-1`.
+    ///
+    /// Unlike `Display`, this format is guaranteed not to change across
+    /// versions of this crate, so `Debug` uses it for units as well as for
+    /// measures, and snapshot tests may rely on it:
+    /// - each base unit is written as its `BaseUnit::NAME`, followed by `^`
+    ///   and its exponent unless the exponent is 1;
+    /// - base units with positive exponents come first, then those with
+    ///   negative exponents;
+    /// - within each group, base units are sorted by `NAMESPACE`, then
+    ///   `NAME`, then `DISAMBIGUATOR`, i.e. never by hash;
+    /// - base units are separated by `" * "`;
+    /// - a dimensionless unit is `""`.
     ///
-    /// let unit_str_2 = Mul::<Inv<Second>, Meter>::as_runtime().to_string();
-    /// assert_eq!(&unit_str_2, "m * s^-1");
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
     ///
-    /// let unit_str_3 = Mul::<Inv<Second>, Mul<Inv<Second>, Meter>>::as_runtime().to_string();
-    /// assert_eq!(&unit_str_3, "m * s^-2");
+    /// type Charge = Mul<Inv<Second>, Mul<Ampere, Mul<Second, Second>>>;
+    /// assert_eq!(Charge::as_runtime().to_canonical_string(), "A * s");
     ///
-    /// let unit_str_4 = Mul::<Inv<Ampere>, Mul<Inv<Second>, Meter>>::as_runtime().to_string();
-    /// assert!(["m * s^-1 * A^-1", "m * A^-1 * s^-1"].iter().any(|x| *x == &unit_str_4));
+    /// let resistance = Mul::<Kg, Mul<Mul<Meter, Meter>, Inv<Mul<Mul<Second, Mul<Second, Second>>, Mul<Ampere, Ampere>>>>>::new(1.5);
+    /// assert_eq!(format!("{:?}", resistance), "1.5kg * m^2 * A^-2 * s^-3");
+    /// assert_eq!(format!("{:?}", DynMeasure::from(resistance)), "1.5kg * m^2 * A^-2 * s^-3");
     /// ```
     ///
     /// # Performance note
     ///
-    /// This method is fine for debugging, but should not be used in a tight loop.
-    pub fn to_string(&self) -> String {
+    /// This is fine for debugging, but should not be used in a tight loop.
+    pub fn to_canonical_string(&self) -> String {
+        let mut dimensions: Vec<_> = self.dimensions.iter().collect();
+        dimensions.sort();
         // First display the positive values.
-        let positives = self.dimensions.values().filter_map(|x| match x.1 {
+        let positives = dimensions.iter().filter_map(|&(dimension, &n)| match n {
             0 => panic!(),
-            1 => Some(x.0.clone()),
-            n if n > 1 => Some(format!("{}^{}", x.0, n)),
+            1 => Some(dimension.name.to_string()),
+            n if n > 1 => Some(format!("{}^{}", dimension.name, n)),
             _ => None,
         });
         // Then display the negative values.
-        let negatives = self.dimensions.values().filter_map(|x| match x.1 {
+        let negatives = dimensions.iter().filter_map(|&(dimension, &n)| match n {
             0 => panic!(),
-            n if n <= -1 => Some(format!("{}^{}", x.0, n)),
+            n if n <= -1 => Some(format!("{}^{}", dimension.name, n)),
             _ => None,
         });
-        format!("{}", positives.chain(negatives).format(" * "))
+        positives.chain(negatives).join(" * ")
+    }
+
+    /// Multiply `self` by `other`, if `positive` is `true`, or by its
+    /// inverse otherwise.
+    pub(crate) fn combine(
+        &mut self,
+        other: &RuntimeUnit,
+        positive: bool,
+    ) -> Result<(), ExponentOverflow> {
+        for (dimension, n) in &other.dimensions {
+            let is_empty = {
+                let entry = self.dimensions.entry(*dimension).or_insert(0);
+                let exponent = if positive {
+                    entry.checked_add(*n)
+                } else {
+                    entry.checked_sub(*n)
+                };
+                *entry = exponent.ok_or_else(|| ExponentOverflow {
+                    name: dimension.name.to_string(),
+                    limit: i16::MAX,
+                })?;
+                *entry == 0
+            };
+            if is_empty {
+                self.dimensions.remove(dimension);
+            }
+        }
+        Ok(())
+    }
+
+    /// Compare two units, describing what `self` is missing and has in
+    /// excess compared to `other`.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let speed = Mul::<Meter, Inv<Second>>::as_runtime();
+    /// let distance = Meter::as_runtime();
+    /// let area = Mul::<Meter, Meter>::as_runtime();
+    ///
+    /// assert_eq!(distance.diff(&speed).to_string(), "missing: s^-1");
+    /// assert_eq!(speed.diff(&distance).to_string(), "extra: s^-1");
+    /// assert_eq!(Second::as_runtime().diff(&distance).to_string(), "missing: m; extra: s");
+    /// assert_eq!(distance.diff(&area).to_string(), "missing: m^2; extra: m");
+    /// assert!(speed.diff(&speed).is_empty());
+    /// ```
+    ///
+    /// # Performance note
+    ///
+    /// This method is fine for debugging, but should not be used in a tight loop.
+    pub fn diff(&self, other: &RuntimeUnit) -> UnitDiff {
+        let exponent =
+            |unit: &RuntimeUnit, dimension: &Dimension| unit.dimensions.get(dimension).cloned();
+        let describe = |(dimension, n): (&Dimension, &i16)| (dimension.name.to_string(), *n);
+        let mut missing: Vec<_> = other
+            .dimensions
+            .iter()
+            .filter(|&(dimension, n)| exponent(self, dimension) != Some(*n))
+            .map(describe)
+            .collect();
+        let mut extra: Vec<_> = self
+            .dimensions
+            .iter()
+            .filter(|&(dimension, n)| exponent(other, dimension) != Some(*n))
+            .map(describe)
+            .collect();
+        missing.sort();
+        extra.sort();
+        UnitDiff { missing, extra }
+    }
+
+    /// The version of the format hashed by `stable_hash`.
+    pub const STABLE_HASH_VERSION: u32 = 1;
+
+    /// A hash of this unit which does not depend on the build, the version of
+    /// rustc or the platform, e.g. to key a persistent cache.
+    ///
+    /// Two units have the same hash if they are equal, i.e. if their base
+    /// units have the same `NAMESPACE`, `NAME` and `DISAMBIGUATOR`, with the
+    /// same exponents.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let speed = Mul::<Meter, Inv<Second>>::as_runtime();
+    /// assert_eq!(speed.stable_hash(), Mul::<Inv<Second>, Meter>::as_runtime().stable_hash());
+    /// assert!(speed.stable_hash() != Meter::as_runtime().stable_hash());
+    ///
+    /// // The same in every build.
+    /// assert_eq!(Meter::as_runtime().stable_hash(), 0xa93b_7398_1127_90f3);
+    /// ```
+    ///
+    /// # Format
+    ///
+    /// The hash is the 64-bit FNV-1a hash of:
+    ///
+    /// - `STABLE_HASH_VERSION`, as 4 little-endian bytes;
+    /// - then, for each base unit, sorted by namespace, name and disambiguator,
+    ///   its namespace and its name, each followed by a `0` byte, then its
+    ///   disambiguator as 4 little-endian bytes and its exponent as 2
+    ///   little-endian bytes.
+    ///
+    /// A change to this format will come with a new `STABLE_HASH_VERSION`.
+    pub fn stable_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+        let mut hash = OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for byte in bytes {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(PRIME);
+            }
+        };
+        write(&Self::STABLE_HASH_VERSION.to_le_bytes());
+        let mut dimensions: Vec<_> = self
+            .dimensions
+            .iter()
+            .map(|(dimension, exponent)| {
+                (
+                    dimension.namespace,
+                    dimension.name,
+                    dimension.disambiguator,
+                    *exponent,
+                )
+            })
+            .collect();
+        dimensions.sort();
+        for (namespace, name, disambiguator, exponent) in dimensions {
+            write(namespace.as_bytes());
+            write(&[0]);
+            write(name.as_bytes());
+            write(&[0]);
+            write(&disambiguator.to_le_bytes());
+            write(&exponent.to_le_bytes());
+        }
+        hash
+    }
+
+    /// The version of the format written by `to_canonical`.
+    pub const CANONICAL_VERSION: u32 = 1;
+
+    /// A versioned representation of this unit, e.g. to store it in a
+    /// database, to be read back with `from_canonical`.
+    ///
+    /// Unlike `Display` and `to_canonical_string`, which are meant for
+    /// humans, this identifies base units by their `NAMESPACE`, `NAME` and
+    /// `DISAMBIGUATOR`, as `unify` does, so distinct units that share a name
+    /// are not confused.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let speed = Mul::<Meter, Inv<Second>>::as_runtime();
+    /// assert_eq!(speed.to_canonical(), "v1;yaiouom::si/m^1;yaiouom::si/s^-1");
+    /// assert_eq!(Dimensionless::as_runtime().to_canonical(), "v1;");
+    ///
+    /// let read = RuntimeUnit::from_canonical(&speed.to_canonical(), &[Meter::as_runtime(), Second::as_runtime()]);
+    /// assert_eq!(read.unwrap(), speed);
+    /// ```
+    ///
+    /// # Format
+    ///
+    /// Version 1 is `v1;` followed by the base units, separated by `;`,
+    /// sorted by namespace, name and disambiguator. Each base unit is
+    /// written as its namespace, `/`, its name, then `#` and its
+    /// disambiguator unless it is 0, then `^` and its exponent. In
+    /// namespaces and names, the bytes of characters other than ASCII
+    /// letters, digits, `_`, `-`, `.` and `:` are escaped as `%` followed
+    /// by two uppercase hexadecimal digits.
+    ///
+    /// A change to this format will come with a new `CANONICAL_VERSION`,
+    /// and `from_canonical` will keep reading the previous versions.
+    pub fn to_canonical(&self) -> String {
+        let mut dimensions: Vec<_> = self.dimensions.iter().collect();
+        dimensions.sort();
+        let mut result = format!("v{};", Self::CANONICAL_VERSION);
+        let factors: Vec<String> = dimensions
+            .iter()
+            .map(|&(dimension, exponent)| {
+                let mut factor = format!(
+                    "{}/{}",
+                    escape_canonical(dimension.namespace),
+                    escape_canonical(dimension.name)
+                );
+                if dimension.disambiguator != 0 {
+                    factor.push_str(&format!("#{}", dimension.disambiguator));
+                }
+                factor.push_str(&format!("^{}", exponent));
+                factor
+            })
+            .collect();
+        result.push_str(&factors.join(";"));
+        result
+    }
+
+    /// Read a unit written by `to_canonical`, in any version.
+    ///
+    /// A stored string only identifies base units, so they are looked up
+    /// among the base units of the units of `known`, e.g. those supported
+    /// by the program. Base units may be listed in any order.
+    ///
+    /// ```
+    /// use yaiouom::*;
+    /// use yaiouom::si::*;
+    ///
+    /// let known = [Meter::as_runtime(), Second::as_runtime()];
+    /// let acceleration = RuntimeUnit::from_canonical("v1;yaiouom::si/s^-2;yaiouom::si/m^1", &known).unwrap();
+    /// assert_eq!(acceleration, Mul::<Meter, Inv<Mul<Second, Second>>>::as_runtime());
+    ///
+    /// let err = RuntimeUnit::from_canonical("v1;yaiouom::si/kg^1", &known).unwrap_err();
+    /// assert_eq!(err.to_string(), "Unknown base unit `yaiouom::si/kg`");
+    ///
+    /// let err = RuntimeUnit::from_canonical("v2;", &known).unwrap_err();
+    /// assert_eq!(err.to_string(), "Unsupported version of canonical unit `v2;`");
+    /// ```
+    pub fn from_canonical(
+        canonical: &str,
+        known: &[RuntimeUnit],
+    ) -> Result<RuntimeUnit, CanonicalError> {
+        let syntax = || CanonicalError::Syntax(canonical.to_string());
+        let factors = match canonical.find(';') {
+            Some(index) if &canonical[..index] == "v1" => &canonical[index + 1..],
+            _ => return Err(CanonicalError::UnsupportedVersion(canonical.to_string())),
+        };
+        let mut result = RuntimeUnit::new();
+        if factors.is_empty() {
+            return Ok(result);
+        }
+        for factor in factors.split(';') {
+            let (base, exponent) = match factor.rfind('^') {
+                Some(index) => (&factor[..index], &factor[index + 1..]),
+                None => return Err(syntax()),
+            };
+            let exponent: i16 = exponent.parse().map_err(|_| syntax())?;
+            let (path, disambiguator) = match base.find('#') {
+                Some(index) => {
+                    let disambiguator = &base[index + 1..];
+                    if disambiguator.starts_with('0') || disambiguator.starts_with('+') {
+                        return Err(syntax());
+                    }
+                    (&base[..index], disambiguator.parse().map_err(|_| syntax())?)
+                }
+                None => (base, 0),
+            };
+            let (namespace, name) = match path.find('/') {
+                Some(index) => (&path[..index], &path[index + 1..]),
+                None => return Err(syntax()),
+            };
+            let namespace = unescape_canonical(namespace).ok_or_else(syntax)?;
+            let name = unescape_canonical(name).ok_or_else(syntax)?;
+            let dimension = known
+                .iter()
+                .flat_map(|unit| unit.dimensions.keys())
+                .find(|dimension| {
+                    dimension.namespace == namespace
+                        && dimension.name == name
+                        && dimension.disambiguator == disambiguator
+                })
+                .ok_or_else(|| CanonicalError::UnknownUnit(path.to_string()))?;
+            if exponent == 0 || result.dimensions.insert(*dimension, exponent).is_some() {
+                return Err(syntax());
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Escape a namespace or a name for `RuntimeUnit::to_canonical`.
+fn escape_canonical(text: &str) -> String {
+    let mut result = String::new();
+    for byte in text.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b':' => {
+                result.push(byte as char)
+            }
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+/// The converse of `escape_canonical`, or `None` if `text` is not escaped
+/// properly.
+fn unescape_canonical(text: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut iter = text.bytes();
+    while let Some(byte) = iter.next() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_' | b'-' | b'.' | b':' => bytes.push(byte),
+            b'%' => {
+                let digits = [iter.next()?, iter.next()?];
+                let digits = std::str::from_utf8(&digits).ok()?;
+                if digits.bytes().any(|digit| digit.is_ascii_lowercase()) {
+                    return None;
+                }
+                bytes.push(u8::from_str_radix(digits, 16).ok()?);
+            }
+            _ => return None,
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// The difference between two units, as computed by `RuntimeUnit::diff`.
+///
+/// Each dimension is represented by its name and its exponent. A
+/// dimension that appears in both units with distinct exponents
+/// is both missing and extra.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitDiff {
+    missing: Vec<(String, i16)>,
+    extra: Vec<(String, i16)>,
+}
+impl UnitDiff {
+    /// The dimensions that are missing, ordered by name.
+    pub fn missing(&self) -> &[(String, i16)] {
+        &self.missing
+    }
+
+    /// The dimensions that are in excess, ordered by name.
+    pub fn extra(&self) -> &[(String, i16)] {
+        &self.extra
+    }
+
+    /// `true` if both units are identical.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+/// Display a UnitDiff as e.g. `"missing: s^-1; extra: m"`.
+impl std::fmt::Display for UnitDiff {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        let format = |dimensions: &[(String, i16)]| {
+            dimensions
+                .iter()
+                .map(|x| match x.1 {
+                    1 => x.0.clone(),
+                    n => format!("{}^{}", x.0, n),
+                })
+                .join(" * ")
+        };
+        let mut parts = vec![];
+        if !self.missing.is_empty() {
+            parts.push(format!("missing: {}", format(&self.missing)));
+        }
+        if !self.extra.is_empty() {
+            parts.push(format!("extra: {}", format(&self.extra)));
+        }
+        write!(fmt, "{}", parts.join("; "))
+    }
+}
+
+/// An error caused by attempting to convert a measure to an
+/// incompatible unit, e.g. with `try_unify`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitMismatch {
+    expected: RuntimeUnit,
+    found: RuntimeUnit,
+}
+impl UnitMismatch {
+    pub fn new(expected: RuntimeUnit, found: RuntimeUnit) -> Self {
+        Self { expected, found }
+    }
+
+    /// The unit we attempted to convert to.
+    pub fn expected(&self) -> &RuntimeUnit {
+        &self.expected
+    }
+
+    /// The actual unit.
+    pub fn found(&self) -> &RuntimeUnit {
+        &self.found
+    }
+
+    /// What `found` is missing and has in excess compared to `expected`.
+    pub fn diff(&self) -> UnitDiff {
+        self.found.diff(&self.expected)
+    }
+}
+impl std::fmt::Display for UnitMismatch {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "Unit mismatch: expected `{}`, found `{}` ({})",
+            self.expected,
+            self.found,
+            self.diff()
+        )
+    }
+}
+impl std::error::Error for UnitMismatch {}
+
+/// The symbol given to `Measure::from_tagged` is not that of the unit of
+/// the measure.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagMismatch {
+    pub(crate) expected: &'static str,
+    pub(crate) found: String,
+}
+impl TagMismatch {
+    /// The symbol of the unit we attempted to convert to.
+    pub fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// The symbol that was given.
+    pub fn found(&self) -> &str {
+        &self.found
+    }
+}
+impl std::fmt::Display for TagMismatch {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(
+            fmt,
+            "Unit mismatch: expected `{}`, found `{}`",
+            self.expected, self.found
+        )
+    }
+}
+impl std::error::Error for TagMismatch {}
+
+/// An exponent of a unit exceeds the limit requested from
+/// `Unit::try_as_runtime`, or does not fit in an `i16`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExponentOverflow {
+    name: String,
+    limit: i16,
+}
+impl ExponentOverflow {
+    /// The name of the offending dimension.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The limit that was exceeded.
+    pub fn limit(&self) -> i16 {
+        self.limit
+    }
+}
+
+/// Display an ExponentOverflow as e.g. "Exponent of `m` exceeds 16".
+impl std::fmt::Display for ExponentOverflow {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        write!(fmt, "Exponent of `{}` exceeds {}", self.name, self.limit)
+    }
+}
+impl std::error::Error for ExponentOverflow {}
+
+/// An error while reading a unit with `RuntimeUnit::from_canonical`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CanonicalError {
+    /// The string was not written by a known version of `to_canonical`.
+    UnsupportedVersion(String),
+
+    /// The string is malformed.
+    Syntax(String),
+
+    /// A base unit, given as its escaped namespace and name, is not among
+    /// the known units.
+    UnknownUnit(String),
+}
+impl std::fmt::Display for CanonicalError {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match *self {
+            CanonicalError::UnsupportedVersion(ref canonical) => {
+                write!(fmt, "Unsupported version of canonical unit `{}`", canonical)
+            }
+            CanonicalError::Syntax(ref canonical) => {
+                write!(fmt, "Malformed canonical unit `{}`", canonical)
+            }
+            CanonicalError::UnknownUnit(ref path) => write!(fmt, "Unknown base unit `{}`", path),
+        }
     }
 }
+impl std::error::Error for CanonicalError {}
 
 /// A unit without dimension.
 #[allow(unused_attributes)]
-#[rustc_yaiouom_combinator_dimensionless]
+#[cfg_attr(feature = "checker", rustc_yaiouom_combinator_dimensionless)]
 pub struct Dimensionless;
 impl Unit for Dimensionless {
-    fn add_to_runtime(_: &mut RuntimeUnit, _: bool) {
+    const CANONICAL: ::symbol::Canonical = ::symbol::Canonical::DIMENSIONLESS;
+
+    fn add_to_runtime(_: &mut RuntimeUnit, _: bool) -> Result<(), ExponentOverflow> {
         // Nothing to do.
+        Ok(())
     }
 }
 impl private::Sealed for Dimensionless {}
@@ -593,7 +2467,7 @@ impl<T> Measure<T, Dimensionless> {
 /// See the documentation of [unify](struct.Measure.html#method.unify) for details
 /// on how to work around this limitation.
 #[allow(unused_attributes)]
-#[rustc_yaiouom_combinator_mul]
+#[cfg_attr(feature = "checker", rustc_yaiouom_combinator_mul)]
 pub struct Mul<A, B>
 where
     A: Unit,
@@ -604,9 +2478,11 @@ where
 }
 impl<A: Unit, B: Unit> private::Sealed for Mul<A, B> {}
 impl<A: Unit, B: Unit> Unit for Mul<A, B> {
-    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) {
-        A::add_to_runtime(repr, positive);
-        B::add_to_runtime(repr, positive);
+    const CANONICAL: ::symbol::Canonical = A::CANONICAL.mul(&B::CANONICAL, true);
+
+    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) -> Result<(), ExponentOverflow> {
+        A::add_to_runtime(repr, positive)?;
+        B::add_to_runtime(repr, positive)
     }
 }
 
@@ -618,7 +2494,7 @@ impl<A: Unit, B: Unit> Unit for Mul<A, B> {
 /// See the documentation of [unify](struct.Measure.html#method.unify) for details
 /// on how to work around this limitation.
 #[allow(unused_attributes)]
-#[rustc_yaiouom_combinator_inv]
+#[cfg_attr(feature = "checker", rustc_yaiouom_combinator_inv)]
 pub struct Inv<A>
 where
     A: Unit,
@@ -627,7 +2503,75 @@ where
 }
 impl<A: Unit> private::Sealed for Inv<A> {}
 impl<A: Unit> Unit for Inv<A> {
-    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) {
-        A::add_to_runtime(repr, !positive);
+    const CANONICAL: ::symbol::Canonical =
+        ::symbol::Canonical::DIMENSIONLESS.mul(&A::CANONICAL, false);
+
+    fn add_to_runtime(repr: &mut RuntimeUnit, positive: bool) -> Result<(), ExponentOverflow> {
+        A::add_to_runtime(repr, !positive)
     }
 }
+
+/// Type-level product of units: `<U as UnitMul<V>>::Output` is the unit
+/// of the product of a `Measure<_, U>` and a `Measure<_, V>`.
+///
+/// Implemented for all units. This lets generic code name the units it
+/// computes without spelling out `Mul`, e.g. an integrator:
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// fn integrate<U: Unit, V: Unit>(samples: &[Measure<f64, U>], step: Measure<f64, V>)
+///     -> Measure<f64, <U as UnitMul<V>>::Output>
+/// {
+///     let total: Measure<f64, U> = samples.iter().cloned().sum();
+///     total * step
+/// }
+///
+/// let speeds = [Meter::new(1.) / Second::new(1.), Meter::new(3.) / Second::new(1.)];
+/// let distance: Measure<f64, Mul<Mul<Meter, Inv<Second>>, Second>> =
+///     integrate(&speeds, Second::new(0.5));
+/// assert_eq!(distance.as_ref(), &2.);
+/// ```
+pub trait UnitMul<V: Unit>: Unit {
+    type Output: Unit;
+}
+impl<U: Unit, V: Unit> UnitMul<V> for U {
+    type Output = Mul<U, V>;
+}
+
+/// Type-level quotient of units: `<U as UnitDiv<V>>::Output` is the unit
+/// of the quotient of a `Measure<_, U>` by a `Measure<_, V>`.
+///
+/// Implemented for all units.
+///
+/// ```
+/// use yaiouom::*;
+/// use yaiouom::si::*;
+///
+/// fn rate<U: Unit>(amount: Measure<f64, U>, duration: Measure<f64, Second>)
+///     -> Measure<f64, <U as UnitDiv<Second>>::Output>
+/// {
+///     amount / duration
+/// }
+///
+/// let speed: Measure<f64, Mul<Meter, Inv<Second>>> = rate(Meter::new(10.), Second::new(2.));
+/// assert_eq!(speed.as_ref(), &5.);
+/// ```
+pub trait UnitDiv<V: Unit>: Unit {
+    type Output: Unit;
+}
+impl<U: Unit, V: Unit> UnitDiv<V> for U {
+    type Output = Mul<U, Inv<V>>;
+}
+
+/// Type-level inverse of a unit: `<U as UnitInv>::Output` is the unit of
+/// the inverse of a `Measure<_, U>`.
+///
+/// Implemented for all units.
+pub trait UnitInv: Unit {
+    type Output: Unit;
+}
+impl<U: Unit> UnitInv for U {
+    type Output = Inv<U>;
+}
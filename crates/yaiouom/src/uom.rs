@@ -0,0 +1,173 @@
+//! Interoperability with crate `uom`, e.g. to migrate a codebase from
+//! one to the other, one module at a time.
+//!
+//! Base quantities convert from and to the corresponding units of
+//! `yaiouom::si` with `From`, e.g. `uom::si::f64::Length` and
+//! `Measure<f64, Meter>`. These conversions are checked at compile time.
+//!
+//! ```
+//! extern crate uom;
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//!
+//! # fn main() {
+//! let length = uom::si::f64::Length::new::<uom::si::length::kilometer>(1.);
+//! let measure: Measure<f64, Meter> = length.into();
+//! assert_eq!(measure, Meter::new(1000.));
+//!
+//! // `Measure::into` converts between value representations, so use `from`.
+//! let length = uom::si::f64::Length::from(measure);
+//! assert_eq!(length.get::<uom::si::length::meter>(), 1000.);
+//! # }
+//! ```
+//!
+//! Other quantities, e.g. velocities, convert with `try_from_uom` and
+//! `try_into_uom`, which compare dimensions at runtime, or into a
+//! `DynMeasure`.
+//!
+//! ```
+//! extern crate uom;
+//! extern crate yaiouom;
+//!
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::uom::*;
+//!
+//! # fn main() {
+//! use uom::si::velocity::meter_per_second;
+//! let velocity = uom::si::f64::Velocity::new::<meter_per_second>(5.);
+//!
+//! let speed: Measure<f64, Mul<Meter, Inv<Second>>> = try_from_uom(velocity).unwrap();
+//! assert_eq!(speed.as_ref(), &5.);
+//! assert!(try_from_uom::<_, _, Mul<Meter, Second>>(velocity).is_err());
+//!
+//! let velocity: uom::si::f64::Velocity = try_into_uom(speed).unwrap();
+//! assert_eq!(velocity.get::<meter_per_second>(), 5.);
+//!
+//! assert_eq!(DynMeasure::from(velocity).unit().to_string(), "m * s^-1");
+//! # }
+//! ```
+//!
+//! In all cases, values are expressed in SI base units, e.g. a length of
+//! 1 km is converted to 1000 m.
+
+use std::marker::PhantomData;
+
+use uom_crate::si::{Dimension, Quantity, Units, SI};
+use uom_crate::typenum::Integer;
+use uom_crate::Conversion;
+
+use dynamic::*;
+use si::*;
+use unit::*;
+
+/// Add `B^exponent` to `repr`.
+fn add_base<B: BaseUnit>(repr: &mut RuntimeUnit, exponent: i32) {
+    for _ in 0..exponent.abs() {
+        B::add_to_runtime(repr, exponent > 0).unwrap();
+    }
+}
+
+/// The unit of `uom` dimension `D`, in SI base units.
+///
+/// The kind of the quantity (e.g. angles vs. ratios) is ignored.
+pub fn runtime_unit<D: Dimension + ?Sized>() -> RuntimeUnit {
+    let mut repr = Dimensionless::as_runtime();
+    add_base::<Meter>(&mut repr, D::L::to_i32());
+    add_base::<Kg>(&mut repr, D::M::to_i32());
+    add_base::<Second>(&mut repr, D::T::to_i32());
+    add_base::<Ampere>(&mut repr, D::I::to_i32());
+    add_base::<Kelvin>(&mut repr, D::Th::to_i32());
+    add_base::<Mole>(&mut repr, D::N::to_i32());
+    add_base::<Candela>(&mut repr, D::J::to_i32());
+    repr
+}
+
+/// Convert a `uom` quantity to a measure, if the dimension of the quantity
+/// matches unit `U`.
+///
+/// # Performance note
+///
+/// This function is fine for debugging, but should not be used in a tight loop.
+pub fn try_from_uom<D, V, U>(quantity: Quantity<D, SI<V>, V>) -> Result<Measure<V, U>, UnitMismatch>
+where
+    D: Dimension + ?Sized,
+    V: ::uom_crate::num::Num + Conversion<V>,
+    SI<V>: Units<V>,
+    U: Unit,
+{
+    let found = runtime_unit::<D>();
+    let expected = U::as_runtime();
+    if found != expected {
+        return Err(UnitMismatch::new(expected, found));
+    }
+    Ok(Measure::new(quantity.value))
+}
+
+/// Convert a measure to a `uom` quantity, if unit `U` matches the dimension
+/// of the quantity.
+///
+/// # Performance note
+///
+/// This function is fine for debugging, but should not be used in a tight loop.
+pub fn try_into_uom<D, V, U>(measure: Measure<V, U>) -> Result<Quantity<D, SI<V>, V>, UnitMismatch>
+where
+    D: Dimension + ?Sized,
+    V: ::uom_crate::num::Num + Conversion<V>,
+    SI<V>: Units<V>,
+    U: Unit,
+{
+    let found = U::as_runtime();
+    let expected = runtime_unit::<D>();
+    if found != expected {
+        return Err(UnitMismatch::new(expected, found));
+    }
+    Ok(Quantity {
+        dimension: PhantomData,
+        units: PhantomData,
+        value: measure.value,
+    })
+}
+
+impl<D, V> From<Quantity<D, SI<V>, V>> for DynMeasure<V>
+where
+    D: Dimension + ?Sized,
+    V: ::uom_crate::num::Num + Conversion<V>,
+    SI<V>: Units<V>,
+{
+    fn from(quantity: Quantity<D, SI<V>, V>) -> Self {
+        DynMeasure::new(quantity.value, runtime_unit::<D>())
+    }
+}
+
+macro_rules! impl_base_quantities {
+    ($($value:ident: $($quantity:ident => $unit:ty),*;)*) => {
+        $($(
+            impl From<::uom_crate::si::$value::$quantity> for Measure<$value, $unit> {
+                fn from(quantity: ::uom_crate::si::$value::$quantity) -> Self {
+                    Measure::new(quantity.value)
+                }
+            }
+            impl From<Measure<$value, $unit>> for ::uom_crate::si::$value::$quantity {
+                fn from(measure: Measure<$value, $unit>) -> Self {
+                    Quantity {
+                        dimension: PhantomData,
+                        units: PhantomData,
+                        value: measure.value,
+                    }
+                }
+            }
+        )*)*
+    }
+}
+
+impl_base_quantities! {
+    f32: Length => Meter, Mass => Kg, Time => Second, ElectricCurrent => Ampere,
+        ThermodynamicTemperature => Kelvin, AmountOfSubstance => Mole,
+        LuminousIntensity => Candela;
+    f64: Length => Meter, Mass => Kg, Time => Second, ElectricCurrent => Ampere,
+        ThermodynamicTemperature => Kelvin, AmountOfSubstance => Mole,
+        LuminousIntensity => Candela;
+}
@@ -0,0 +1,200 @@
+//! Validating values received from third parties, e.g. telemetry, into
+//! measures.
+//!
+//! Such values arrive as numbers tagged with the symbol of their unit, as
+//! in `Measure::from_tagged`, and their magnitudes cannot be trusted. A
+//! `Validator` declares the expected unit, other units that are accepted
+//! with a scaling factor, and the plausible range of values, which may
+//! reject values or clamp them.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::validate::*;
+//!
+//! type Speed = Mul<Meter, Inv<Second>>;
+//!
+//! let validator = Validator::<Speed>::new()
+//!     .scale("km * s^-1", 1000.)
+//!     .clamp(Measure::new(0.), Measure::new(300.));
+//!
+//! let speed = validator.validate(20., "m * s^-1").unwrap();
+//! assert_eq!(speed.measure(), Measure::new(20.));
+//! assert_eq!(speed.clamped(), None);
+//!
+//! // Scaled, then clamped: the caller may log a warning.
+//! let speed = validator.validate(0.5, "s^-1 * km").unwrap();
+//! assert_eq!(speed.measure(), Measure::new(300.));
+//! assert_eq!(speed.clamped(), Some(500.));
+//!
+//! let err = validator.validate(20., "m").unwrap_err();
+//! assert_eq!(err.to_string(), "Unit mismatch: expected `m * s^-1`, found `m`");
+//!
+//! let err = validator.validate(std::f64::NAN, "m * s^-1").unwrap_err();
+//! assert_eq!(err, Rejection::NotFinite);
+//!
+//! // Rejecting implausible values instead.
+//! let strict = Validator::<Speed>::new().range(Measure::new(0.), Measure::new(300.));
+//! let err = strict.validate(-3., "m * s^-1").unwrap_err();
+//! assert_eq!(err.to_string(), "Value -3 is outside of plausible range [0, 300]");
+//! ```
+
+use std::marker::PhantomData;
+
+use unit::*;
+
+/// What to do with values outside of the plausible range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Policy {
+    Reject,
+    Clamp,
+}
+
+/// A validation pipeline for values of unit `U`.
+pub struct Validator<U: Unit> {
+    scales: Vec<(String, f64)>,
+    range: Option<(f64, f64, Policy)>,
+    unit: PhantomData<U>,
+}
+impl<U: Unit> Validator<U> {
+    /// A validator accepting any finite value in unit `U`.
+    pub fn new() -> Self {
+        Validator {
+            scales: vec![],
+            range: None,
+            unit: PhantomData,
+        }
+    }
+
+    /// Also accept values in unit `unit`, converted into `U` by multiplying
+    /// them by `factor`.
+    ///
+    /// yaiouom does not know about conversions between units, so it cannot
+    /// check `factor`.
+    pub fn scale(mut self, unit: &str, factor: f64) -> Self {
+        self.scales.push((unit.to_string(), factor));
+        self
+    }
+
+    /// Reject values outside of `[min, max]`, after scaling.
+    pub fn range(mut self, min: Measure<f64, U>, max: Measure<f64, U>) -> Self {
+        self.range = Some((min.value, max.value, Policy::Reject));
+        self
+    }
+
+    /// Clamp values outside of `[min, max]`, after scaling.
+    pub fn clamp(mut self, min: Measure<f64, U>, max: Measure<f64, U>) -> Self {
+        self.range = Some((min.value, max.value, Policy::Clamp));
+        self
+    }
+
+    /// Validate `value`, expressed in the unit of symbol `unit`.
+    ///
+    /// As in `Measure::from_tagged`, the factors of `unit` may be given in
+    /// any order.
+    pub fn validate(&self, value: f64, unit: &str) -> Result<Validated<U>, Rejection> {
+        let factor = if symbol_matches(unit, &U::as_runtime()) {
+            1.
+        } else {
+            let found = symbol_exponents(unit);
+            match self
+                .scales
+                .iter()
+                .find(|scale| found.is_some() && symbol_exponents(&scale.0) == found)
+            {
+                Some(&(_, factor)) => factor,
+                None => {
+                    return Err(Rejection::Unit(TagMismatch {
+                        expected: U::SYMBOL,
+                        found: unit.to_string(),
+                    }))
+                }
+            }
+        };
+        let value = value * factor;
+        if !value.is_finite() {
+            return Err(Rejection::NotFinite);
+        }
+        let (min, max, policy) = match self.range {
+            Some(range) => range,
+            None => return Ok(Validated::new(value, None)),
+        };
+        if value >= min && value <= max {
+            return Ok(Validated::new(value, None));
+        }
+        match policy {
+            Policy::Reject => Err(Rejection::OutOfRange { value, min, max }),
+            Policy::Clamp => Ok(Validated::new(value.max(min).min(max), Some(value))),
+        }
+    }
+}
+impl<U: Unit> Default for Validator<U> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value accepted by a `Validator`.
+pub struct Validated<U: Unit> {
+    measure: Measure<f64, U>,
+    clamped: Option<f64>,
+}
+impl<U: Unit> Validated<U> {
+    fn new(value: f64, clamped: Option<f64>) -> Self {
+        Validated {
+            measure: Measure::new(value),
+            clamped,
+        }
+    }
+
+    /// The measure, after scaling and clamping.
+    pub fn measure(&self) -> Measure<f64, U> {
+        self.measure
+    }
+
+    /// If the value was clamped, the value before clamping, after scaling.
+    pub fn clamped(&self) -> Option<f64> {
+        self.clamped
+    }
+}
+impl<U: Unit> Clone for Validated<U> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<U: Unit> Copy for Validated<U> {}
+impl<U: Unit> std::fmt::Debug for Validated<U> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        fmt.debug_struct("Validated")
+            .field("measure", &self.measure)
+            .field("clamped", &self.clamped)
+            .finish()
+    }
+}
+
+/// The reason why a `Validator` rejected a value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rejection {
+    /// The unit is neither the expected unit nor one of the scaled units.
+    Unit(TagMismatch),
+
+    /// The value, after scaling, is infinite or NaN.
+    NotFinite,
+
+    /// The value, after scaling, is outside of the plausible range.
+    OutOfRange { value: f64, min: f64, max: f64 },
+}
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        match *self {
+            Rejection::Unit(ref err) => write!(fmt, "{}", err),
+            Rejection::NotFinite => write!(fmt, "Value is not finite"),
+            Rejection::OutOfRange { value, min, max } => write!(
+                fmt,
+                "Value {} is outside of plausible range [{}, {}]",
+                value, min, max
+            ),
+        }
+    }
+}
+impl std::error::Error for Rejection {}
@@ -0,0 +1,72 @@
+//! Exposing measures to JavaScript with crate `wasm-bindgen`, e.g. for
+//! dashboards that display the results of a simulation compiled to WASM.
+//!
+//! Measures cross the boundary as instances of JavaScript class `Measure`
+//! (`JsMeasure` in Rust), with properties `value` and `unit`, the latter
+//! being the symbol of the unit, as in `Measure::to_tagged`. Any
+//! `Measure<f64, U>` converts into a `JsMeasure` or directly into a
+//! `JsValue`, so exported functions need no glue per unit.
+//!
+//! ```
+//! use yaiouom::*;
+//! use yaiouom::si::*;
+//! use yaiouom::wasm::*;
+//!
+//! let speed = JsMeasure::from(Meter::new(10.) / Second::new(2.));
+//! assert_eq!(speed.value(), 5.);
+//! assert_eq!(speed.unit(), "m * s^-1");
+//!
+//! let speed: Measure<f64, Mul<Meter, Inv<Second>>> = speed.into_measure().unwrap();
+//! assert_eq!(speed.as_ref(), &5.);
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use unit::*;
+
+/// A measure whose unit is only known at runtime, as seen from JavaScript.
+#[wasm_bindgen(js_name = Measure)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsMeasure {
+    value: f64,
+    unit: String,
+}
+
+#[wasm_bindgen(js_class = Measure)]
+impl JsMeasure {
+    #[wasm_bindgen(constructor)]
+    pub fn new(value: f64, unit: String) -> Self {
+        Self { value, unit }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// The symbol of the unit, e.g. `m * s^-1`.
+    #[wasm_bindgen(getter)]
+    pub fn unit(&self) -> String {
+        self.unit.clone()
+    }
+}
+
+impl JsMeasure {
+    /// Convert into a measure, if `unit` is the symbol of `U`.
+    pub fn into_measure<U: Unit>(self) -> Result<Measure<f64, U>, TagMismatch> {
+        Measure::from_tagged((self.value, &self.unit))
+    }
+}
+
+impl<U: Unit> From<Measure<f64, U>> for JsMeasure {
+    fn from(measure: Measure<f64, U>) -> Self {
+        let (value, unit) = measure.to_tagged();
+        Self::new(value, unit.to_string())
+    }
+}
+
+impl<U: Unit> From<Measure<f64, U>> for JsValue {
+    fn from(measure: Measure<f64, U>) -> Self {
+        JsMeasure::from(measure).into()
+    }
+}
@@ -0,0 +1,115 @@
+//! Ensure that units stored with `RuntimeUnit::to_canonical` can be read
+//! back by later versions of yaiouom.
+//!
+//! The strings below are stored data: never change them. A new version of
+//! the format should come with new tests, next to these.
+
+extern crate yaiouom;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+struct Custom;
+impl BaseUnit for Custom {
+    const NAMESPACE: &'static str = "my crate";
+    const NAME: &'static str = "m^2;%/#";
+    const DISAMBIGUATOR: u32 = 7;
+}
+
+fn known() -> Vec<RuntimeUnit> {
+    vec![
+        Meter::as_runtime(),
+        Second::as_runtime(),
+        Kg::as_runtime(),
+        Ampere::as_runtime(),
+        Custom::as_runtime(),
+    ]
+}
+
+/// Units, and their canonical representation in version 1.
+fn v1() -> Vec<(RuntimeUnit, &'static str)> {
+    vec![
+        (Dimensionless::as_runtime(), "v1;"),
+        (Meter::as_runtime(), "v1;yaiouom::si/m^1"),
+        (
+            Mul::<Meter, Inv<Second>>::as_runtime(),
+            "v1;yaiouom::si/m^1;yaiouom::si/s^-1",
+        ),
+        (
+            Mul::<
+                Kg,
+                Mul<
+                    Mul<Meter, Meter>,
+                    Inv<Mul<Mul<Second, Mul<Second, Second>>, Mul<Ampere, Ampere>>>,
+                >,
+            >::as_runtime(),
+            "v1;yaiouom::si/A^-2;yaiouom::si/kg^1;yaiouom::si/m^2;yaiouom::si/s^-3",
+        ),
+        (
+            Mul::<Custom, Inv<Meter>>::as_runtime(),
+            "v1;my%20crate/m%5E2%3B%25%2F%23#7^1;yaiouom::si/m^-1",
+        ),
+    ]
+}
+
+#[test]
+fn write_v1() {
+    assert_eq!(RuntimeUnit::CANONICAL_VERSION, 1);
+    for (unit, canonical) in v1() {
+        assert_eq!(unit.to_canonical(), canonical);
+    }
+}
+
+#[test]
+fn read_v1() {
+    let known = known();
+    for (unit, canonical) in v1() {
+        assert_eq!(RuntimeUnit::from_canonical(canonical, &known), Ok(unit));
+    }
+    // Any order.
+    assert_eq!(
+        RuntimeUnit::from_canonical("v1;yaiouom::si/s^-1;yaiouom::si/m^1", &known),
+        Ok(Mul::<Meter, Inv<Second>>::as_runtime())
+    );
+}
+
+#[test]
+fn reject_malformed() {
+    let known = known();
+    for canonical in &[
+        "",
+        "yaiouom::si/m^1",
+        "v0;yaiouom::si/m^1",
+        "V1;yaiouom::si/m^1",
+    ] {
+        assert_eq!(
+            RuntimeUnit::from_canonical(canonical, &known),
+            Err(CanonicalError::UnsupportedVersion(canonical.to_string()))
+        );
+    }
+    for canonical in &[
+        "v1;;",
+        "v1;yaiouom::si/m",
+        "v1;yaiouom::si/m^0",
+        "v1;yaiouom::si/m^x",
+        "v1;yaiouom::si/m^1;yaiouom::si/m^1",
+        "v1;yaiouom::si/m#0^1",
+        "v1;yaiouom::si m^1",
+        "v1;yaiouom::si/m%5e2^1",
+        "v1;yaiouom::si/m%5^1",
+        "v1;yaiouom::si/m^1;",
+    ] {
+        assert_eq!(
+            RuntimeUnit::from_canonical(canonical, &known),
+            Err(CanonicalError::Syntax(canonical.to_string())),
+            "{}",
+            canonical
+        );
+    }
+    assert_eq!(
+        RuntimeUnit::from_canonical("v1;my%20crate/m%5E2%3B%25%2F%23^1", &known),
+        Err(CanonicalError::UnknownUnit(
+            "my%20crate/m%5E2%3B%25%2F%23".to_string()
+        ))
+    );
+}
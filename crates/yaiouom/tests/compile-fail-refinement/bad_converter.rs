@@ -40,7 +40,7 @@ impl<A, B, F> Converter<A, B, F> where A: Unit, B: Unit, F: Fn(Measure<f64, A>)
     }
 }
 
-fn main() {
+fn main() { //~ NOTE While examining this function
     // Let's make sure that it doesn't cause a panic.
     let result_1 = convert_2d(|x| x, Mul::<Meter, Meter>::new(1.0));
     let converter = Converter {
@@ -56,7 +56,11 @@ fn main() {
         right: PhantomData,
         convert_1d: |x: Measure<f64, Second>| x,
     };
-    let result_3 = convert_inv(|x| x, Second::new(2.).unify()); //~ERROR
-    let result_4 = converter.convert_inv(Second::new(2.).unify()); //~ERROR
+    let result_3 = convert_inv(|x| x, Second::new(2.).unify()); //~ERROR Cannot resolve the following units of measures
+    //~| NOTE in this unification
+    //~| NOTE expected unit of measure
+    //~| NOTE missing:
+    //~| NOTE 2 unifications of this function cannot be resolved
+    let result_4 = converter.convert_inv(Second::new(2.).unify()); //~ NOTE Cannot resolve this unification either
     assert_eq!(result_3, result_4);
 }
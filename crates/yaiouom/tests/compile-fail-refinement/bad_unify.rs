@@ -11,6 +11,7 @@ impl BaseUnit for Kilometer {
 }
 fn get_speed_bad(distance: Measure<f64, Kilometer>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
     return ((Dimensionless::new(1.) / duration) * distance ).unify(); //~ERROR
+    //~^ HELP the unit of the receiver of `unify` is
 }
 
 fn main() {
@@ -8,9 +8,15 @@ trait Duration: Unit {}
 impl Distance for Meter {}
 impl Duration for Second {}
 
-fn get_speed_bad<A: Distance, B: Duration>(distance: Measure<f64, A>, duration: Measure<f64, B>) -> Measure<f64, Mul<A, Inv<B>>> {
-    (distance / duration)  //~ERROR
-        .unify::<Mul<A, Inv<Second>>>() //~^ERROR
+// Both unifications are wrong, but they are reported as a single error.
+fn get_speed_bad<A: Distance, B: Duration>(distance: Measure<f64, A>, duration: Measure<f64, B>) -> Measure<f64, Mul<A, Inv<B>>> { //~ NOTE While examining this function
+    (distance / duration)  //~ERROR Cannot resolve the following units of measures
+        //~| NOTE in this unification
+        //~| NOTE expected unit of measure
+        //~| NOTE Cannot resolve this unification either
+        //~| NOTE missing:
+        //~| NOTE 2 unifications of this function cannot be resolved
+        .unify::<Mul<A, Inv<Second>>>()
         .unify()
 }
 
@@ -0,0 +1,16 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+
+trait System {
+    type Length: Unit;
+    type Time: Unit;
+}
+
+// The following should compile with Rust but fail with the linter.
+fn get_speed_bad<S: System>(distance: Measure<f64, S::Length>, duration: Measure<f64, S::Time>) -> Measure<f64, Mul<S::Time, Inv<S::Length>>> {
+    (distance / duration).unify() //~ERROR
+}
+
+fn main() {
+}
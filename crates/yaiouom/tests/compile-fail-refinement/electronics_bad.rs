@@ -0,0 +1,23 @@
+extern crate yaiouom;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+// A broken variant of run-pass/electronics.rs.
+
+type Volt = Mul<Mul<Kg, Mul<Meter, Meter>>, Inv<Mul<Mul<Second, Mul<Second, Second>>, Ampere>>>;
+type Ohm = Mul<Volt, Inv<Ampere>>;
+type Farad = Mul<Mul<Ampere, Second>, Inv<Volt>>;
+
+// The following should compile with Rust but fail with the linter.
+fn time_constant(
+    resistance: Measure<f64, Ohm>,
+    capacitance: Measure<f64, Farad>,
+) -> Measure<f64, Second> {
+    // Should be `resistance * capacitance`.
+    (resistance / capacitance).unify() //~ERROR
+}
+
+fn main() {
+    let _ = time_constant(Measure::new(4.7e3), Measure::new(1e-7));
+}
@@ -0,0 +1,38 @@
+// aux-build:unify_macro.rs
+
+// Errors in calls to `unify` generated by macros are reported at the
+// call site of the macro, rather than in its definition.
+
+extern crate yaiouom;
+extern crate unify_macro;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+use unify_macro::unify_expr;
+
+struct Kilometer;
+impl BaseUnit for Kilometer {
+    const NAME: &'static str = "km";
+}
+
+macro_rules! speed {
+    ($distance:expr, $duration:expr) => {
+        ($distance / $duration).unify()
+    }
+}
+
+fn get_speed_bad(distance: Measure<f64, Kilometer>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
+    speed!(distance, duration) //~ERROR
+}
+
+fn get_speed_bad_2(distance: Measure<f64, Kilometer>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
+    unify_expr!((Dimensionless::new(1.) / duration) * distance) //~ERROR
+}
+
+fn main() {
+    let distance = Kilometer::new(1.0);
+    let duration = Second::new(1.0);
+    let _ = get_speed_bad(distance, duration);
+    let _ = get_speed_bad_2(distance, duration);
+}
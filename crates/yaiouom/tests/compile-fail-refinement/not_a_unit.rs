@@ -0,0 +1,19 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// References to local types are local types, so this is accepted by Rust.
+struct Kilometer;
+impl BaseUnit for &'static Kilometer {
+    const NAME: &'static str = "km";
+}
+
+// The following should compile with Rust but fail with the linter, rather than crash it.
+fn get_speed_bad(distance: Measure<f64, &'static Kilometer>, duration: Measure<f64, Second>) -> Measure<f64, Mul<&'static Kilometer, Inv<Second>>> {
+    (distance / duration).unify() //~ERROR
+}
+
+fn main() {
+    let _ = get_speed_bad(Measure::new(1.0), Second::new(1.0));
+}
@@ -0,0 +1,23 @@
+extern crate yaiouom;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+// A broken variant of run-pass/orbital.rs.
+
+type Speed = Mul<Meter, Inv<Second>>;
+type GravitationalParameter = Mul<Mul<Meter, Mul<Meter, Meter>>, Inv<Mul<Second, Second>>>;
+
+// The following should compile with Rust but fail with the linter.
+fn circular_speed(
+    mu: Measure<f64, GravitationalParameter>,
+    r: Measure<f64, Meter>,
+) -> Measure<f64, Speed> {
+    // Should be `mu / r`.
+    let squared: Measure<f64, Mul<Speed, Speed>> = (mu * r).unify(); //~ERROR
+    squared.sqrt()
+}
+
+fn main() {
+    let _ = circular_speed(Measure::new(3.986e14), (7000. * KILO * M).unify());
+}
@@ -0,0 +1,10 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// The target of `unify` cannot be inferred, as the result is discarded.
+fn main() {
+    let distance = Meter::new(1.0);
+    let _ = distance.unify(); //~ERROR
+}
@@ -0,0 +1,20 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+struct Date;
+impl BaseUnit for Date {
+    const NAME: &'static str = "date";
+}
+impl AddAcross<Second> for Date {
+    type Output = Date;
+}
+
+fn main() {
+    let date: Measure<i64, Date> = Measure::new(1_000);
+    let _ = date.add_across(Second::new(60));
+    let _ = date + Second::new(60); //~ERROR
+    let _ = Second::new(60).add_across(date); //~ERROR
+    let _ = date.sub_across(Second::new(60)); //~ERROR
+}
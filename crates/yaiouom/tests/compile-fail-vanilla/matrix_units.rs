@@ -0,0 +1,16 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+use yaiouom::matrix::*;
+
+type State = (Meter, Mul<Meter, Inv<Second>>);
+
+fn main() {
+    let state: UnitVector<f64, State> = (Meter::new(1.), Measure::new(2.)).into();
+    let transition: UnitMatrix<f64, State, (Second, Meter)> = UnitMatrix::zero();
+    let _ = transition * state; //~ERROR
+
+    let transition: UnitMatrix<f64, State, State> = UnitMatrix::zero();
+    let _ = transition.set::<I0, I1>(Meter::new(1.)); //~ERROR
+}
@@ -19,3 +19,6 @@ impl<T> Foo<T> where T: std::ops::Div<T> + Copy {
         self.distance / self.duration //~ERROR
     }
 }
+
+fn main() {
+}
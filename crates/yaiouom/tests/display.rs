@@ -0,0 +1,156 @@
+//! Ensure that displaying measures does not allocate, e.g. in logs, and
+//! that units too large for `Unit::SYMBOL` are still displayed, with
+//! `Debug` pinned to `RuntimeUnit::to_canonical_string`.
+
+extern crate yaiouom;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+/// Count the allocations of each thread, as tests run concurrently.
+struct Counting;
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// A buffer that does not allocate.
+struct Buffer {
+    bytes: [u8; 256],
+    len: usize,
+}
+impl Buffer {
+    fn new() -> Self {
+        Buffer {
+            bytes: [0; 256],
+            len: 0,
+        }
+    }
+    fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+impl Write for Buffer {
+    fn write_str(&mut self, text: &str) -> std::fmt::Result {
+        let end = self.len + text.len();
+        if end > self.bytes.len() {
+            return Err(std::fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(text.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Format `args` into `buffer`, returning the number of allocations.
+fn allocations(buffer: &mut Buffer, args: std::fmt::Arguments) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    buffer.write_fmt(args).unwrap();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn simple_units_do_not_allocate() {
+    let distance: Measure<f64, Meter> = Measure::new(1.5);
+    let speed: Measure<f64, Mul<Meter, Inv<Second>>> = Measure::new(2.);
+    let ratio: Measure<f64, Dimensionless> = Measure::new(0.25);
+
+    let mut buffer = Buffer::new();
+    assert_eq!(allocations(&mut buffer, format_args!("{}", distance)), 0);
+    assert_eq!(buffer.as_str(), "1.5 m");
+
+    let mut buffer = Buffer::new();
+    assert_eq!(allocations(&mut buffer, format_args!("{:.1}", speed)), 0);
+    assert_eq!(buffer.as_str(), "2.0 m * s^-1");
+
+    let mut buffer = Buffer::new();
+    assert_eq!(allocations(&mut buffer, format_args!("{}", ratio)), 0);
+    assert_eq!(buffer.as_str(), "0.25");
+
+    let mut buffer = Buffer::new();
+    assert_eq!(allocations(&mut buffer, format_args!("{:?}", speed)), 0);
+    assert_eq!(buffer.as_str(), "2.0m * s^-1");
+
+    // Whereas `RuntimeUnit` allocates.
+    let runtime = Meter::as_runtime();
+    let mut buffer = Buffer::new();
+    assert!(allocations(&mut buffer, format_args!("{}", runtime)) > 0);
+}
+
+#[test]
+fn display_matches_runtime_unit() {
+    fn check<U: Unit>() {
+        assert_eq!(U::display().to_string(), U::as_runtime().to_string());
+        assert_eq!(
+            U::display().to_string(),
+            U::as_runtime().to_canonical_string()
+        );
+    }
+    check::<Dimensionless>();
+    check::<Meter>();
+    check::<Inv<Second>>();
+    check::<Mul<Inv<Second>, Meter>>();
+    check::<Mul<Kg, Mul<Mul<Meter, Meter>, Inv<Mul<Second, Mul<Second, Ampere>>>>>>();
+}
+
+macro_rules! base_units {
+    ($($unit:ident),*) => {
+        $(
+            struct $unit;
+            impl BaseUnit for $unit {
+                const NAMESPACE: &'static str = "display";
+                const NAME: &'static str = stringify!($unit);
+            }
+        )*
+    };
+}
+base_units!(A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q);
+
+#[test]
+fn large_units_fall_back_to_runtime_unit() {
+    // More than `MAX_SYMBOL_DIMENSIONS` base units.
+    type Half = Mul<A, Mul<B, Mul<C, Mul<D, Mul<E, Mul<F, Mul<G, H>>>>>>>;
+    type Large = Mul<Half, Mul<I, Mul<J, Mul<K, Mul<L, Mul<M, Mul<N, Mul<O, Mul<P, Inv<Q>>>>>>>>>>;
+
+    let measure: Measure<f64, Large> = Measure::new(1.);
+    assert_eq!(
+        measure.to_string(),
+        "1 A * B * C * D * E * F * G * H * I * J * K * L * M * N * O * P * Q^-1"
+    );
+    assert_eq!(
+        format!("{:?}", measure),
+        format!("1.0{}", Large::as_runtime().to_canonical_string())
+    );
+}
+
+#[test]
+fn debug_is_the_canonical_string() {
+    fn check<U: Unit>() {
+        assert_eq!(
+            format!("{:?}", Measure::<i32, U>::new(1)),
+            format!("1{}", U::as_runtime().to_canonical_string())
+        );
+    }
+    check::<Dimensionless>();
+    check::<Meter>();
+    check::<Inv<Second>>();
+    check::<Mul<Inv<Second>, Meter>>();
+    check::<Mul<Kg, Mul<Mul<Meter, Meter>, Inv<Mul<Second, Mul<Second, Ampere>>>>>>();
+
+    type Half = Mul<A, Mul<B, Mul<C, Mul<D, Mul<E, Mul<F, Mul<G, H>>>>>>>;
+    check::<Mul<Half, Mul<I, Mul<J, Mul<K, Mul<L, Mul<M, Mul<N, Mul<O, Mul<P, Inv<Q>>>>>>>>>>>();
+}
@@ -0,0 +1,203 @@
+//! Build the doctests of this crate with yaiouom-checker.
+//!
+//! `cargo test --doc` builds doctests with rustdoc, hence with vanilla
+//! rustc, so the calls to `unify` of the examples are only checked
+//! dynamically. This extracts the doctests into programs which, when the
+//! test suite is run with yaiouom-checker, are built and run in mode
+//! run-pass, so the examples are checked just as user code.
+//!
+//! Skipped doctests:
+//! - those that are not run by rustdoc (`ignore`, `no_run`, `compile_fail`,
+//!   `should_panic`, other languages or editions);
+//! - those of modules and examples behind features, or that depend on
+//!   other crates;
+//! - those demonstrating the dynamic checks with `catch_unwind`, which the
+//!   checker rightly rejects.
+
+extern crate compiletest_rs as compiletest;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A doctest, as a program.
+struct Doctest {
+    /// e.g. `unit_522`, for a doctest starting at line 522 of `src/unit.rs`.
+    name: String,
+    source: String,
+}
+
+/// The modules of `lib.rs` that are behind a feature.
+fn gated_modules(lib: &str) -> Vec<String> {
+    let mut modules = vec![];
+    let mut gated = false;
+    for line in lib.lines() {
+        let line = line.trim();
+        if line.starts_with("#[cfg(feature") {
+            gated = true;
+            continue;
+        }
+        if gated {
+            let name = line
+                .trim_start_matches("pub ")
+                .strip_prefix("mod ")
+                .and_then(|name| name.strip_suffix(';'));
+            if let Some(name) = name {
+                modules.push(name.to_string());
+            }
+        }
+        gated = false;
+    }
+    modules
+}
+
+/// Whether rustdoc runs a code block with these attributes.
+fn is_run(attributes: &str) -> bool {
+    attributes
+        .split(',')
+        .map(str::trim)
+        .all(|attribute| attribute.is_empty() || attribute == "rust")
+}
+
+/// Turn the lines of a doctest into a program, as rustdoc does.
+fn program(lines: &[String]) -> String {
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|line| {
+            if line == "#" {
+                ""
+            } else if let Some(line) = line.strip_prefix("# ") {
+                line
+            } else {
+                line
+            }
+        })
+        .collect();
+    let mut header = vec!["#![allow(unused)]"];
+    let mut body = vec![];
+    let mut is_crate_attribute = false;
+    for line in &lines {
+        if line.starts_with("#![") || line.starts_with("extern crate") || is_crate_attribute {
+            header.push(line);
+            is_crate_attribute = false;
+        } else if line.starts_with("#[macro_use]") {
+            header.push(line);
+            is_crate_attribute = true;
+        } else {
+            body.push(*line);
+        }
+    }
+    if !header
+        .iter()
+        .any(|line| line.contains("extern crate yaiouom"))
+    {
+        header.push("extern crate yaiouom;");
+    }
+    let body = body.join("\n");
+    if body.contains("fn main") {
+        format!("{}\n{}\n", header.join("\n"), body)
+    } else {
+        format!("{}\nfn main() {{\n{}\n}}\n", header.join("\n"), body)
+    }
+}
+
+/// Extract the doctests of a source file.
+fn extract(path: &Path, module: &str, doctests: &mut Vec<Doctest>) {
+    let source = fs::read_to_string(path).unwrap();
+    let mut block: Option<(usize, bool, Vec<String>)> = None;
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim();
+        let doc = match line
+            .strip_prefix("///")
+            .or_else(|| line.strip_prefix("//!"))
+        {
+            Some(doc) => doc.strip_prefix(' ').unwrap_or(doc),
+            None => {
+                block = None;
+                continue;
+            }
+        };
+        if let Some(attributes) = doc.strip_prefix("```") {
+            block = match block.take() {
+                None => Some((number + 1, is_run(attributes), vec![])),
+                Some((start, true, lines)) => {
+                    let source = program(&lines);
+                    let is_dependent = source.contains("feature")
+                        || source.contains("catch_unwind")
+                        || source
+                            .lines()
+                            .any(|line| line.contains("extern crate") && !line.contains("yaiouom"));
+                    if !is_dependent {
+                        doctests.push(Doctest {
+                            name: format!("{}_{}", module, start),
+                            source,
+                        });
+                    }
+                    None
+                }
+                Some(_) => None,
+            };
+        } else if let Some((_, _, ref mut lines)) = block {
+            lines.push(doc.to_string());
+        }
+    }
+}
+
+fn doctests() -> Vec<Doctest> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let gated = gated_modules(&fs::read_to_string(src.join("lib.rs")).unwrap());
+    let mut paths: Vec<PathBuf> = fs::read_dir(&src)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().map(|ext| ext == "rs").unwrap_or(false))
+        .collect();
+    paths.sort();
+    let mut doctests = vec![];
+    for path in paths {
+        let module = path.file_stem().unwrap().to_str().unwrap().to_string();
+        if gated.contains(&module) {
+            continue;
+        }
+        extract(&path, &module, &mut doctests);
+    }
+    doctests
+}
+
+#[test]
+fn doctests_under_checker() {
+    let doctests = doctests();
+    assert!(
+        doctests
+            .iter()
+            .any(|doctest| doctest.name.starts_with("unit_")),
+        "No doctest found in `src/unit.rs`"
+    );
+
+    let rustc = std::env::var("RUSTC").ok().map(PathBuf::from);
+    let is_refinement = rustc
+        .as_ref()
+        .map(|rustc| rustc.ends_with("yaiouom-checker"))
+        .unwrap_or(false);
+    if !is_refinement {
+        // rustdoc already runs them with vanilla rustc.
+        return;
+    }
+
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("doctests");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for doctest in &doctests {
+        fs::write(dir.join(format!("{}.rs", doctest.name)), &doctest.source).unwrap();
+    }
+
+    let mut config = compiletest::Config {
+        src_base: dir,
+        mode: compiletest::common::Mode::RunPass,
+        ..Default::default()
+    };
+    config.link_deps();
+    config.clean_rmeta();
+    if let Some(rustc) = rustc {
+        config.rustc_path = rustc;
+    }
+    compiletest::run_tests(&config);
+}
@@ -0,0 +1,278 @@
+//! Metamorphic tests of unification.
+//!
+//! Generate random units, then rewrite them into equivalent units (by
+//! commutation, association, cancellation, ...) or mutate them into
+//! distinct units, and generate programs that `unify` the original and the
+//! rewritten units.
+//!
+//! Equivalent units must pass both the dynamic checks and yaiouom-checker.
+//! Distinct units must fail the dynamic checks and, when the test suite is
+//! run with yaiouom-checker, be rejected by it.
+//!
+//! The generator is seeded, so failures are reproducible.
+
+extern crate compiletest_rs as compiletest;
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Number of pairs of units, for each of the equivalent and distinct cases.
+const CASES: usize = 32;
+
+/// Maximal depth of the units initially generated.
+const DEPTH: usize = 3;
+
+const ATOMS: &[&str] = &["Meter", "Second", "Kg"];
+
+/// A xorshift generator, to avoid depending on a crate of random numbers.
+struct Rng(u64);
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `[0, bound)`.
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn atom(&mut self) -> Expr {
+        Expr::Atom(ATOMS[self.below(ATOMS.len())])
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Atom(&'static str),
+    Dimensionless,
+    Mul(Box<Expr>, Box<Expr>),
+    Inv(Box<Expr>),
+}
+
+fn mul(left: Expr, right: Expr) -> Expr {
+    Expr::Mul(Box::new(left), Box::new(right))
+}
+
+fn inv(expr: Expr) -> Expr {
+    Expr::Inv(Box::new(expr))
+}
+
+impl Expr {
+    fn generate(rng: &mut Rng, depth: usize) -> Expr {
+        if depth == 0 {
+            return rng.atom();
+        }
+        match rng.below(8) {
+            0 => Expr::Dimensionless,
+            1 | 2 => rng.atom(),
+            3 => inv(Expr::generate(rng, depth - 1)),
+            _ => mul(
+                Expr::generate(rng, depth - 1),
+                Expr::generate(rng, depth - 1),
+            ),
+        }
+    }
+
+    /// The unit, as a Rust type.
+    fn render(&self) -> String {
+        match *self {
+            Expr::Atom(name) => name.to_string(),
+            Expr::Dimensionless => "Dimensionless".to_string(),
+            Expr::Mul(ref left, ref right) => format!("Mul<{}, {}>", left.render(), right.render()),
+            Expr::Inv(ref expr) => format!("Inv<{}>", expr.render()),
+        }
+    }
+
+    /// The canonical form of the unit, i.e. the exponent of each atom.
+    fn exponents(&self) -> BTreeMap<&'static str, i32> {
+        fn walk(expr: &Expr, sign: i32, exponents: &mut BTreeMap<&'static str, i32>) {
+            match *expr {
+                Expr::Atom(name) => *exponents.entry(name).or_insert(0) += sign,
+                Expr::Dimensionless => {}
+                Expr::Mul(ref left, ref right) => {
+                    walk(left, sign, exponents);
+                    walk(right, sign, exponents);
+                }
+                Expr::Inv(ref expr) => walk(expr, -sign, exponents),
+            }
+        }
+        let mut exponents = BTreeMap::new();
+        walk(self, 1, &mut exponents);
+        exponents.retain(|_, exponent| *exponent != 0);
+        exponents
+    }
+
+    /// Apply a random rewrite that preserves the unit, somewhere in `self`.
+    fn rewrite(self, rng: &mut Rng) -> Expr {
+        // Either rewrite this node, or recurse.
+        if rng.below(3) != 0 {
+            match self {
+                Expr::Mul(left, right) => {
+                    if rng.below(2) == 0 {
+                        Expr::Mul(Box::new(left.rewrite(rng)), right)
+                    } else {
+                        Expr::Mul(left, Box::new(right.rewrite(rng)))
+                    }
+                }
+                Expr::Inv(expr) => Expr::Inv(Box::new(expr.rewrite(rng))),
+                expr => expr,
+            }
+        } else {
+            match (rng.below(6), self) {
+                // Commutation.
+                (0, Expr::Mul(left, right)) => Expr::Mul(right, left),
+                // Association, in both directions.
+                (1, Expr::Mul(left, right)) => match (*left, *right) {
+                    (Expr::Mul(a, b), c) => mul(*a, mul(*b, c)),
+                    (a, Expr::Mul(b, c)) => mul(mul(a, *b), *c),
+                    (a, b) => mul(b, a),
+                },
+                // Inverse of a product.
+                (2, Expr::Inv(expr)) => match *expr {
+                    Expr::Mul(left, right) => mul(inv(*left), inv(*right)),
+                    expr => inv(inv(inv(expr))),
+                },
+                // Cancellation.
+                (3, expr) => {
+                    let atom = rng.atom();
+                    mul(expr, mul(inv(atom.clone()), atom))
+                }
+                // Neutral element.
+                (4, expr) => mul(Expr::Dimensionless, expr),
+                // Double inverse.
+                (_, expr) => inv(inv(expr)),
+            }
+        }
+    }
+
+    /// Change the unit, then rewrite it.
+    fn mutate(self, rng: &mut Rng) -> Expr {
+        let atom = rng.atom();
+        let mutated = if rng.below(2) == 0 {
+            mul(self, atom)
+        } else {
+            mul(inv(atom), self)
+        };
+        mutated.rewrite(rng)
+    }
+}
+
+struct Case {
+    source: Expr,
+    target: Expr,
+}
+
+fn generate(rng: &mut Rng, equivalent: bool) -> Vec<Case> {
+    let mut cases = vec![];
+    while cases.len() < CASES {
+        let source = Expr::generate(rng, DEPTH);
+        let mut target = source.clone();
+        for _ in 0..1 + rng.below(4) {
+            target = target.rewrite(rng);
+        }
+        if !equivalent {
+            target = target.mutate(rng);
+        }
+        assert_eq!(
+            source.exponents() == target.exponents(),
+            equivalent,
+            "Bad rewrite from {} to {}",
+            source.render(),
+            target.render()
+        );
+        cases.push(Case { source, target });
+    }
+    cases
+}
+
+const PRELUDE: &str = "// Generated by tests/metamorphic.rs.\n\
+                       extern crate yaiouom;\n\
+                       \n\
+                       use yaiouom::*;\n\
+                       use yaiouom::si::*;\n";
+
+/// A program that checks equivalent units with `unify` and distinct units
+/// with `try_unify`.
+fn write_run_pass(path: &Path, equivalent: &[Case], distinct: &[Case]) {
+    let mut source = PRELUDE.to_string();
+    for (index, case) in equivalent.iter().enumerate() {
+        source.push_str(&format!(
+            "\nfn equivalent_{}(x: Measure<f64, {}>) -> Measure<f64, {}> {{\n    x.unify()\n}}\n",
+            index,
+            case.source.render(),
+            case.target.render()
+        ));
+    }
+    source.push_str("\nfn main() {\n");
+    for index in 0..equivalent.len() {
+        source.push_str(&format!("    equivalent_{}(Measure::new(1.));\n", index));
+    }
+    for case in distinct {
+        source.push_str(&format!(
+            "    assert!(Measure::<f64, {}>::new(1.).try_unify::<{}>().is_err());\n",
+            case.source.render(),
+            case.target.render()
+        ));
+    }
+    source.push_str("}\n");
+    fs::write(path, source).unwrap();
+}
+
+/// A program in which each unification fails.
+fn write_compile_fail(path: &Path, distinct: &[Case]) {
+    let mut source = PRELUDE.to_string();
+    for (index, case) in distinct.iter().enumerate() {
+        source.push_str(&format!(
+            "\nfn distinct_{}(x: Measure<f64, {}>) -> Measure<f64, {}> {{\n    x.unify() //~ERROR\n}}\n",
+            index,
+            case.source.render(),
+            case.target.render()
+        ));
+    }
+    source.push_str("\nfn main() {}\n");
+    fs::write(path, source).unwrap();
+}
+
+fn run(dir: PathBuf, mode: compiletest::common::Mode, rustc: &Option<PathBuf>) {
+    let mut config = compiletest::Config {
+        src_base: dir,
+        mode,
+        ..Default::default()
+    };
+    config.link_deps();
+    config.clean_rmeta();
+    if let Some(ref rustc) = *rustc {
+        config.rustc_path = rustc.clone();
+    }
+    compiletest::run_tests(&config);
+}
+
+#[test]
+fn metamorphic() {
+    let mut rng = Rng(0x5eed_1234_abcd_0001);
+    let equivalent = generate(&mut rng, true);
+    let distinct = generate(&mut rng, false);
+
+    let rustc = std::env::var("RUSTC").ok().map(PathBuf::from);
+    let is_refinement = rustc
+        .as_ref()
+        .map(|rustc| rustc.ends_with("yaiouom-checker"))
+        .unwrap_or(false);
+
+    let base = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("metamorphic");
+    let run_pass = base.join("run-pass");
+    let compile_fail = base.join("compile-fail");
+    fs::create_dir_all(&run_pass).unwrap();
+    fs::create_dir_all(&compile_fail).unwrap();
+    write_run_pass(&run_pass.join("metamorphic.rs"), &equivalent, &distinct);
+    write_compile_fail(&compile_fail.join("metamorphic.rs"), &distinct);
+
+    run(run_pass, compiletest::common::Mode::RunPass, &rustc);
+    if is_refinement {
+        run(compile_fail, compiletest::common::Mode::CompileFail, &rustc);
+    }
+}
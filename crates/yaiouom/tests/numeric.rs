@@ -0,0 +1,109 @@
+//! Measures of numeric types other than primitives, e.g. wrappers that are
+//! not `Copy`, or whose operators return another type.
+
+extern crate ordered_float;
+extern crate yaiouom;
+
+use std::ops::{Add, Div, Mul, Sub};
+
+use ordered_float::NotNan;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+#[test]
+fn not_nan() {
+    let distance: Measure<NotNan<f64>, Meter> = Measure::new(NotNan::new(6.).unwrap());
+    let duration: Measure<NotNan<f64>, Second> = Measure::new(NotNan::new(2.).unwrap());
+
+    let speed = distance / duration;
+    assert_eq!(speed.as_ref(), &NotNan::new(3.).unwrap());
+    assert_eq!(-(distance + distance - distance), -distance);
+    assert_eq!(
+        distance * NotNan::new(0.5).unwrap(),
+        Measure::new(NotNan::new(3.).unwrap())
+    );
+
+    let distances = [distance, distance];
+    assert_eq!(
+        distances.iter().cloned().sum::<Measure<_, Meter>>(),
+        distance * NotNan::new(2.).unwrap()
+    );
+    assert!(distance > Measure::new(NotNan::new(1.).unwrap()));
+}
+
+/// A number that is not `Copy`.
+#[derive(Clone, Debug, PartialEq)]
+struct Exact(i64);
+impl Add for Exact {
+    type Output = Exact;
+    fn add(self, rhs: Exact) -> Exact {
+        Exact(self.0 + rhs.0)
+    }
+}
+impl Sub for Exact {
+    type Output = Exact;
+    fn sub(self, rhs: Exact) -> Exact {
+        Exact(self.0 - rhs.0)
+    }
+}
+impl Mul for Exact {
+    type Output = Exact;
+    fn mul(self, rhs: Exact) -> Exact {
+        Exact(self.0 * rhs.0)
+    }
+}
+impl<'a> std::iter::Sum<&'a Exact> for Exact {
+    fn sum<I: Iterator<Item = &'a Exact>>(iter: I) -> Exact {
+        Exact(iter.map(|x| x.0).sum())
+    }
+}
+
+/// A number whose division returns another type.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Cents(i64);
+impl Div for Cents {
+    type Output = f64;
+    fn div(self, rhs: Cents) -> f64 {
+        self.0 as f64 / rhs.0 as f64
+    }
+}
+impl Add for Cents {
+    type Output = Option<Cents>;
+    fn add(self, rhs: Cents) -> Option<Cents> {
+        self.0.checked_add(rhs.0).map(Cents)
+    }
+}
+
+#[test]
+fn not_copy() {
+    let distance: Measure<Exact, Meter> = Measure::new(Exact(3));
+    let area = distance.clone() * distance.clone();
+    assert_eq!(area.as_ref(), &Exact(9));
+    let sum = distance.clone() + distance.clone() - distance.clone();
+    assert_eq!(sum, distance);
+
+    let distances = [distance.clone(), distance.clone()];
+    let total: Measure<Exact, Meter> = distances.iter().sum();
+    assert_eq!(total.as_ref(), &Exact(6));
+
+    let dyn_area = DynMeasure::from(distance.clone()) * DynMeasure::from(distance);
+    assert_eq!(dyn_area, DynMeasure::from(area));
+}
+
+#[test]
+fn other_output() {
+    let price: Measure<Cents, Meter> = Measure::new(Cents(150));
+    let ratio: Measure<f64, Dimensionless> = (price / price).unify();
+    assert_eq!(ratio.unwrap(), 1.);
+
+    let total: Measure<Option<Cents>, Meter> = price + price;
+    assert_eq!(total.as_ref(), &Some(Cents(300)));
+    let overflow = Measure::<Cents, Meter>::new(Cents(i64::MAX)) + price;
+    assert_eq!(overflow.as_ref(), &None);
+
+    let sum = DynMeasure::from(price)
+        .try_add(DynMeasure::from(price))
+        .unwrap();
+    assert_eq!(sum.value(), &Some(Cents(300)));
+}
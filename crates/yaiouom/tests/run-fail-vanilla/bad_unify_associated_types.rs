@@ -0,0 +1,26 @@
+// should-fail
+
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+trait System {
+    type Length: Unit;
+    type Time: Unit;
+}
+
+struct SI;
+impl System for SI {
+    type Length = Meter;
+    type Time = Second;
+}
+
+// The following should compile with Rust but fail with the linter.
+fn get_speed_bad<S: System>(distance: Measure<f64, S::Length>, duration: Measure<f64, S::Time>) -> Measure<f64, Mul<S::Time, Inv<S::Length>>> {
+    (distance / duration).unify() //~ERROR
+}
+
+fn main() {
+    let _ = get_speed_bad::<SI>(Meter::new(10.), Second::new(2.));
+}
@@ -0,0 +1,46 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// A position on a line, and a displacement along that line.
+struct Point;
+impl BaseUnit for Point {
+    const NAME: &'static str = "point";
+}
+struct Vector;
+impl BaseUnit for Vector {
+    const NAME: &'static str = "vector";
+}
+
+// Point - Point = Vector, Point + Vector = Point, Point - Vector = Point.
+impl SubAcross<Point> for Point {
+    type Output = Vector;
+}
+impl AddAcross<Vector> for Point {
+    type Output = Point;
+}
+impl SubAcross<Vector> for Point {
+    type Output = Point;
+}
+
+fn main() {
+    let start: Measure<i32, Point> = Measure::new(2);
+    let end: Measure<i32, Point> = Measure::new(5);
+
+    let move_by: Measure<i32, Vector> = end.sub_across(start);
+    assert_eq!(move_by.as_ref(), &3);
+
+    let further: Measure<i32, Point> = end.add_across(move_by);
+    assert_eq!(further.as_ref(), &8);
+
+    let back: Measure<i32, Point> = further.sub_across(move_by);
+    assert_eq!(back, end);
+
+    // Same-unit arithmetic is unaffected, e.g. for inference.
+    let total = Meter::new(1) + Meter::new(2);
+    let _: Measure<i32, Meter> = total;
+    let sum = Measure::new(1) + Meter::new(2);
+    assert_eq!(sum, Meter::new(3));
+    let _: Measure<i32, Point> = end - start;
+}
@@ -0,0 +1,34 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// Units expressed as associated types.
+trait System {
+    type Length: Unit;
+    type Time: Unit;
+}
+
+struct SI;
+impl System for SI {
+    type Length = Meter;
+    type Time = Second;
+}
+
+// The following should build unsafely with Rust, then yaiouom-driver will ensure the safety of `unify`.
+// Projections `S::Length`, `S::Time` cannot be resolved, so they are handled as atoms.
+fn get_speed<S: System>(distance: Measure<f64, S::Length>, duration: Measure<f64, S::Time>) -> Measure<f64, Mul<Inv<S::Time>, S::Length>> {
+    (distance / duration).unify()
+}
+
+// The following should build unsafely with Rust, then yaiouom-driver will ensure the safety of `unify`.
+// Projections `<SI as System>::Length`, `<SI as System>::Time` are resolved to `Meter`, `Second`.
+fn get_speed_si(distance: Measure<f64, <SI as System>::Length>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Inv<<SI as System>::Time>, Meter>> {
+    (distance / duration).unify()
+}
+
+fn main() {
+    let a = get_speed::<SI>(Meter::new(10.), Second::new(2.));
+    let b = get_speed_si(Meter::new(10.), Second::new(2.));
+    assert_eq!(a, b);
+}
@@ -0,0 +1,13 @@
+// no-prefer-dynamic
+
+#![crate_type = "proc-macro"]
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+
+/// `unify_expr!(e)` expands to `(e).unify()`, with spans from the call site.
+#[proc_macro]
+pub fn unify_expr(input: TokenStream) -> TokenStream {
+    format!("({}).unify()", input).parse().unwrap()
+}
@@ -0,0 +1,77 @@
+extern crate yaiouom;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+// A small electronics calculator, with derived units defined in terms of
+// SI base units.
+
+type Volt = Mul<Mul<Kg, Mul<Meter, Meter>>, Inv<Mul<Mul<Second, Mul<Second, Second>>, Ampere>>>;
+type Ohm = Mul<Volt, Inv<Ampere>>;
+type Watt = Mul<Volt, Ampere>;
+type Farad = Mul<Mul<Ampere, Second>, Inv<Volt>>;
+
+const VOLT: Measure<f64, Volt> = Measure::new(1.);
+const OHM: Measure<f64, Ohm> = Measure::new(1.);
+const FARAD: Measure<f64, Farad> = Measure::new(1.);
+
+fn current(voltage: Measure<f64, Volt>, resistance: Measure<f64, Ohm>) -> Measure<f64, Ampere> {
+    (voltage / resistance).unify()
+}
+
+fn power(voltage: Measure<f64, Volt>, current: Measure<f64, Ampere>) -> Measure<f64, Watt> {
+    voltage * current
+}
+
+fn divider(
+    input: Measure<f64, Volt>,
+    r1: Measure<f64, Ohm>,
+    r2: Measure<f64, Ohm>,
+) -> Measure<f64, Volt> {
+    let ratio: Measure<f64, Dimensionless> = (r2 / (r1 + r2)).unify();
+    (input * ratio).unify()
+}
+
+fn parallel(resistors: &[Measure<f64, Ohm>]) -> Measure<f64, Ohm> {
+    let conductance: Measure<f64, Inv<Ohm>> = resistors.iter().map(|r| 1. / *r).sum();
+    (1. / conductance).unify()
+}
+
+fn time_constant(
+    resistance: Measure<f64, Ohm>,
+    capacitance: Measure<f64, Farad>,
+) -> Measure<f64, Second> {
+    (resistance * capacitance).unify()
+}
+
+fn assert_close<U: Unit>(actual: Measure<f64, U>, expected: Measure<f64, U>) {
+    let (actual, expected) = (*actual.as_ref(), *expected.as_ref());
+    assert!(
+        (actual - expected).abs() <= 1e-9 * expected.abs(),
+        "{} != {}",
+        actual,
+        expected
+    );
+}
+
+fn main() {
+    let supply = 3.3 * VOLT;
+    let led = 330. * OHM;
+
+    let i = current(supply, led);
+    assert_close(i, (10. * MILLI * A).unify());
+    assert_close(power(supply, i), Measure::new(0.033));
+
+    assert_close(
+        divider(
+            5. * VOLT,
+            (10. * KILO * OHM).unify(),
+            (10. * KILO * OHM).unify(),
+        ),
+        2.5 * VOLT,
+    );
+    assert_close(parallel(&[100. * OHM, 100. * OHM, 50. * OHM]), 25. * OHM);
+
+    let tau = time_constant((4.7 * KILO * OHM).unify(), (100. * NANO * FARAD).unify());
+    assert_close(tau, (470. * MICRO * S).unify());
+}
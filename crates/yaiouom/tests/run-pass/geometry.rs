@@ -0,0 +1,40 @@
+extern crate yaiouom;
+
+use yaiouom::geometry::*;
+use yaiouom::si::*;
+use yaiouom::*;
+
+struct Inch;
+impl BaseUnit for Inch {
+    const NAME: &'static str = "in";
+}
+
+// Volumes built in distinct orders, which the linter must recognize as
+// the same unit.
+fn tank_volume<L: Unit>(
+    radius: Measure<f64, L>,
+    height: Measure<f64, L>,
+) -> Measure<f64, Volume<L>> {
+    let base: Measure<f64, Area<L>> = circle_area(radius);
+    (base * height).unify()
+}
+
+fn shell_volume<L: Unit>(
+    outer: Measure<f64, L>,
+    inner: Measure<f64, L>,
+) -> Measure<f64, Mul<Mul<L, L>, L>> {
+    (sphere_volume(outer) - sphere_volume(inner)).unify()
+}
+
+fn main() {
+    let volume = tank_volume(Meter::new(1.), Meter::new(2.));
+    assert_eq!(volume, cylinder_volume(Meter::new(1.), Meter::new(2.)));
+
+    let shell = shell_volume(Inch::new(2.), Inch::new(1.));
+    let expected = 28. / 3. * std::f64::consts::PI;
+    assert!((shell.as_ref() - expected).abs() < 1e-9);
+
+    let area: Measure<f64, Area<Inch>> = rectangle_area(Inch::new(2.), Inch::new(3.));
+    let side: Measure<f64, Inch> = (area / 6.).sqrt();
+    assert_eq!(side, Inch::new(1.));
+}
@@ -0,0 +1,31 @@
+// aux-build:unify_macro.rs
+
+// Calls to `unify` generated by macros are checked as any other call.
+
+extern crate yaiouom;
+extern crate unify_macro;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+use unify_macro::unify_expr;
+
+macro_rules! speed {
+    ($distance:expr, $duration:expr) => {
+        ($distance / $duration).unify()
+    }
+}
+
+fn get_speed(distance: Measure<f64, Meter>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
+    speed!(distance, duration)
+}
+
+fn get_speed_2(distance: Measure<f64, Meter>, duration: Measure<f64, Second>) -> Measure<f64, Mul<Meter, Inv<Second>>> {
+    unify_expr!((Dimensionless::new(1.) / duration) * distance)
+}
+
+fn main() {
+    let distance = Meter::new(1.0);
+    let duration = Second::new(1.0);
+    assert_eq!(get_speed(distance, duration), get_speed_2(distance, duration));
+}
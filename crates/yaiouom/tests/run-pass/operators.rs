@@ -0,0 +1,135 @@
+extern crate yaiouom;
+
+// Exercise every operator, for each numeric type, with a few representative units.
+//
+// Output types are spelled out, so that the test fails to build with vanilla Rust
+// if an operator produces the wrong unit, and with yaiouom-driver if a `unify` is
+// unsafe.
+macro_rules! operator_matrix {
+    ($($name:ident: $t:ty),*) => {
+        $(
+            mod $name {
+                use yaiouom::*;
+                use yaiouom::si::*;
+
+                fn value(x: i8) -> $t {
+                    x as $t
+                }
+
+                fn base_unit() {
+                    let two : Measure<$t, Meter> = Meter::new(value(2));
+                    let three : Measure<$t, Meter> = Meter::new(value(3));
+
+                    let sum : Measure<$t, Meter> = two + three;
+                    assert_eq!(sum.as_ref(), &value(5));
+
+                    let difference : Measure<$t, Meter> = three - two;
+                    assert_eq!(difference.as_ref(), &value(1));
+
+                    let neg : Measure<$t, Meter> = -two;
+                    assert_eq!(neg.as_ref(), &value(-2));
+
+                    let right_scalar : Measure<$t, Meter> = two * value(4);
+                    assert_eq!(right_scalar.as_ref(), &value(8));
+
+                    let left_scalar : Measure<$t, Meter> = value(4) * two;
+                    assert_eq!(left_scalar.as_ref(), &value(8));
+
+                    let div_scalar : Measure<$t, Meter> = left_scalar / value(4);
+                    assert_eq!(div_scalar.as_ref(), &value(2));
+
+                    let inv : Measure<$t, Inv<Meter>> = value(4) / two;
+                    assert_eq!(inv.as_ref(), &value(2));
+
+                    let square : Measure<$t, Mul<Meter, Meter>> = two * three;
+                    assert_eq!(square.as_ref(), &value(6));
+
+                    let ratio : Measure<$t, Mul<Mul<Meter, Meter>, Inv<Meter>>> = square / three;
+                    assert_eq!(ratio.as_ref(), &value(2));
+                    let ratio : Measure<$t, Meter> = ratio.unify();
+                    assert_eq!(ratio, two);
+
+                    let dimensionless : Measure<$t, Mul<Meter, Inv<Meter>>> = two / two;
+                    let dimensionless : Measure<$t, Dimensionless> = dimensionless.unify();
+                    assert_eq!(dimensionless.as_ref(), &value(1));
+                }
+
+                fn compound_unit() {
+                    let distance : Measure<$t, Meter> = Meter::new(value(6));
+                    let duration : Measure<$t, Second> = Second::new(value(2));
+
+                    let speed : Measure<$t, Mul<Meter, Inv<Second>>> = distance / duration;
+                    assert_eq!(speed.as_ref(), &value(3));
+
+                    let sum : Measure<$t, Mul<Meter, Inv<Second>>> = speed + speed;
+                    assert_eq!(sum.as_ref(), &value(6));
+
+                    let neg : Measure<$t, Mul<Meter, Inv<Second>>> = -speed;
+                    assert_eq!(neg.as_ref(), &value(-3));
+
+                    let left_scalar : Measure<$t, Mul<Meter, Inv<Second>>> = value(2) * speed;
+                    assert_eq!(left_scalar.as_ref(), &value(6));
+
+                    let pace : Measure<$t, Inv<Mul<Meter, Inv<Second>>>> = value(6) / speed;
+                    assert_eq!(pace.as_ref(), &value(2));
+                    let pace : Measure<$t, Mul<Second, Inv<Meter>>> = pace.unify();
+                    assert_eq!(pace.as_ref(), &value(2));
+
+                    let back : Measure<$t, Mul<Mul<Meter, Inv<Second>>, Second>> = speed * duration;
+                    let back : Measure<$t, Meter> = back.unify();
+                    assert_eq!(back, distance);
+
+                    let commuted : Measure<$t, Mul<Inv<Second>, Meter>> = speed.unify();
+                    assert_eq!(commuted.as_ref(), speed.as_ref());
+                }
+
+                fn generic_unit<U: Unit>() {
+                    let two : Measure<$t, U> = U::new(value(2));
+
+                    let sum : Measure<$t, U> = two + two;
+                    assert_eq!(sum.as_ref(), &value(4));
+
+                    let left_scalar : Measure<$t, U> = value(3) * two;
+                    assert_eq!(left_scalar.as_ref(), &value(6));
+
+                    let square : Measure<$t, Mul<U, U>> = two * two;
+                    let inv : Measure<$t, Inv<U>> = (value(8) / square * two).unify();
+                    assert_eq!(inv.as_ref(), &value(4));
+                }
+
+                fn iterators() {
+                    let sum : Measure<$t, Second> = (1..4)
+                        .map(value)
+                        .map(Second::new)
+                        .sum();
+                    assert_eq!(sum.as_ref(), &value(6));
+
+                    let product : Measure<$t, Second> = (1..4)
+                        .map(value)
+                        .map(Second::new)
+                        .product();
+                    assert_eq!(product.as_ref(), &value(6));
+                }
+
+                pub fn run() {
+                    base_unit();
+                    compound_unit();
+                    generic_unit::<Meter>();
+                    generic_unit::<Mul<Meter, Inv<Second>>>();
+                    iterators();
+                }
+            }
+        )*
+
+        fn main() {
+            $($name::run();)*
+        }
+    };
+}
+
+operator_matrix!(
+    with_i32: i32,
+    with_i64: i64,
+    with_f32: f32,
+    with_f64: f64
+);
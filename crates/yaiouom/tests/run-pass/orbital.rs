@@ -0,0 +1,100 @@
+extern crate yaiouom;
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+// A small simulation of a satellite in a circular orbit, exercising
+// generic code and many unifications.
+
+type Speed = Mul<Meter, Inv<Second>>;
+type Acceleration = Mul<Speed, Inv<Second>>;
+type GravitationalParameter = Mul<Mul<Meter, Mul<Meter, Meter>>, Inv<Mul<Second, Second>>>;
+
+/// One step of integration, for any quantity and its rate of change.
+fn integrate<U: Unit>(
+    value: Measure<f64, U>,
+    rate: Measure<f64, Mul<U, Inv<Second>>>,
+    dt: Measure<f64, Second>,
+) -> Measure<f64, U> {
+    value + (rate * dt).unify()
+}
+
+fn norm<U: Unit>(x: Measure<f64, U>, y: Measure<f64, U>) -> Measure<f64, U> {
+    (x * x + y * y).sqrt()
+}
+
+fn gravity(
+    mu: Measure<f64, GravitationalParameter>,
+    x: Measure<f64, Meter>,
+    y: Measure<f64, Meter>,
+) -> (Measure<f64, Acceleration>, Measure<f64, Acceleration>) {
+    let r = norm(x, y);
+    let factor: Measure<f64, Inv<Mul<Second, Second>>> = (mu / (r * r * r)).unify();
+    ((-factor * x).unify(), (-factor * y).unify())
+}
+
+fn circular_speed(
+    mu: Measure<f64, GravitationalParameter>,
+    r: Measure<f64, Meter>,
+) -> Measure<f64, Speed> {
+    let squared: Measure<f64, Mul<Speed, Speed>> = (mu / r).unify();
+    squared.sqrt()
+}
+
+fn period(
+    mu: Measure<f64, GravitationalParameter>,
+    r: Measure<f64, Meter>,
+) -> Measure<f64, Second> {
+    let circumference = 2. * std::f64::consts::PI * r;
+    (circumference / circular_speed(mu, r)).unify()
+}
+
+struct Satellite {
+    x: Measure<f64, Meter>,
+    y: Measure<f64, Meter>,
+    vx: Measure<f64, Speed>,
+    vy: Measure<f64, Speed>,
+}
+impl Satellite {
+    fn step(&mut self, mu: Measure<f64, GravitationalParameter>, dt: Measure<f64, Second>) {
+        // Semi-implicit Euler, which keeps the orbit stable.
+        let (ax, ay) = gravity(mu, self.x, self.y);
+        self.vx = integrate(self.vx, ax.unify(), dt);
+        self.vy = integrate(self.vy, ay.unify(), dt);
+        self.x = integrate(self.x, self.vx.unify(), dt);
+        self.y = integrate(self.y, self.vy.unify(), dt);
+    }
+}
+
+fn main() {
+    let earth: Measure<f64, GravitationalParameter> = Measure::new(3.986e14);
+    let radius: Measure<f64, Meter> = (7000. * KILO * M).unify();
+
+    let mut satellite = Satellite {
+        x: radius,
+        y: 0. * M,
+        vx: Measure::new(0.),
+        vy: circular_speed(earth, radius),
+    };
+    let dt = 1. * S;
+    let steps = *(period(earth, radius) / dt)
+        .unify::<Dimensionless>()
+        .as_ref() as usize;
+    for _ in 0..steps {
+        satellite.step(earth, dt);
+    }
+
+    // After one period, the satellite is back near its starting point.
+    let drift = *(norm(satellite.x - radius, satellite.y) / radius)
+        .unify::<Dimensionless>()
+        .as_ref();
+    assert!(drift < 0.01, "drift: {}", drift);
+    let altitude_error = *((norm(satellite.x, satellite.y) - radius) / radius)
+        .unify::<Dimensionless>()
+        .as_ref();
+    assert!(
+        altitude_error.abs() < 0.01,
+        "altitude error: {}",
+        altitude_error
+    );
+}
@@ -0,0 +1,38 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// Literals built from SI prefixes and unit constants, without annotations.
+//
+// Output types are spelled out where they are checked, so that the test fails
+// to build with vanilla Rust if a product has the wrong unit.
+
+type Volt = Mul<Mul<Kg, Mul<Meter, Meter>>, Inv<Mul<Mul<Second, Mul<Second, Second>>, Ampere>>>;
+const V: Measure<f64, Volt> = Measure::new(1.);
+
+fn main() {
+    // A prefix times a scalar remains dimensionless, on either side.
+    let milli = 3.3 * MILLI;
+    let _: Measure<f64, Dimensionless> = milli;
+    let _: Measure<f64, Dimensionless> = MILLI * 3.3;
+    assert_eq!(*milli.as_ref(), 3.3e-3);
+
+    // `3.3 * MILLI * V` is inferred without annotations...
+    let voltage = 3.3 * MILLI * V;
+    let _: Measure<f64, Mul<Dimensionless, Volt>> = voltage;
+
+    // ... and unifies with the unit of its recipient.
+    let voltage: Measure<f64, Volt> = voltage.unify();
+    assert_eq!(*voltage.as_ref(), 3.3e-3);
+
+    let distance: Measure<f64, Meter> = (7. * KILO * M).unify();
+    assert_eq!(distance, Meter::new(7000.));
+
+    let frequency: Measure<f64, Inv<Second>> = (2. * GIGA / S).unify();
+    assert_eq!(*frequency.as_ref(), 2e9);
+
+    // Prefixes compose.
+    let tiny: Measure<f64, Meter> = (1. * MICRO * MILLI * M).unify();
+    assert_eq!(*tiny.as_ref(), 1e-9);
+}
@@ -0,0 +1,39 @@
+extern crate yaiouom;
+
+use yaiouom::*;
+use yaiouom::si::*;
+
+// A generic abstraction which computes the unit of its output.
+trait Integrator<U: Unit, V: Unit> {
+    fn integrate(&self, samples: &[Measure<f64, U>], step: Measure<f64, V>) -> Measure<f64, <U as UnitMul<V>>::Output>;
+}
+
+struct Rectangles;
+impl<U: Unit, V: Unit> Integrator<U, V> for Rectangles {
+    fn integrate(&self, samples: &[Measure<f64, U>], step: Measure<f64, V>) -> Measure<f64, <U as UnitMul<V>>::Output> {
+        let total: Measure<f64, U> = samples.iter().cloned().sum();
+        total * step
+    }
+}
+
+// The following should build unsafely with Rust, then yaiouom-driver will ensure the safety of `unify`.
+fn distance<I: Integrator<Mul<Meter, Inv<Second>>, Second>>(integrator: &I, speeds: &[Measure<f64, Mul<Meter, Inv<Second>>>]) -> Measure<f64, Meter> {
+    integrator.integrate(speeds, Second::new(0.5)).unify()
+}
+
+fn average<U: Unit>(samples: &[Measure<f64, U>]) -> Measure<f64, U> {
+    let total: Measure<f64, U> = samples.iter().cloned().sum();
+    let count: Measure<f64, Inv<Dimensionless>> = Measure::new(1. / samples.len() as f64);
+    let average: Measure<f64, <U as UnitMul<Inv<Dimensionless>>>::Output> = total * count;
+    average.unify()
+}
+
+fn main() {
+    let speeds = [Meter::new(1.) / Second::new(1.), Meter::new(3.) / Second::new(1.)];
+    assert_eq!(distance(&Rectangles, &speeds), Meter::new(2.));
+    assert_eq!(average(&[Kg::new(1.), Kg::new(3.)]), Kg::new(2.));
+
+    let frequency: Measure<f64, <Second as UnitInv>::Output> = (Dimensionless::new(1.) / Second::new(2.)).unify();
+    let speed: Measure<f64, <Meter as UnitDiv<Second>>::Output> = (frequency * Meter::new(1.)).unify();
+    assert_eq!(speed, Meter::new(1.) / Second::new(2.));
+}
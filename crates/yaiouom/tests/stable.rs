@@ -0,0 +1,39 @@
+//! Ensure that yaiouom keeps building with stable Rust, i.e. that the
+//! attributes used by yaiouom-checker remain behind feature `checker`.
+//!
+//! Building with a stable toolchain would catch this, but the test suite
+//! is also run with yaiouom-checker, which requires nightly.
+
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn checker_attributes_are_gated() {
+    let mut offending = vec![];
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    for entry in fs::read_dir(&src).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().map(|ext| ext != "rs").unwrap_or(true) {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with("//") {
+                continue;
+            }
+            let is_attribute = line.contains("rustc_yaiouom") || line.starts_with("#![feature");
+            let is_gated = line.starts_with("#[cfg_attr(feature = \"checker\",")
+                || line.starts_with("#![cfg_attr(feature = \"checker\",");
+            if is_attribute && !is_gated {
+                offending.push(format!("{}:{}: {}", path.display(), number + 1, line));
+            }
+        }
+    }
+    assert!(
+        offending.is_empty(),
+        "These attributes should be behind `cfg_attr(feature = \"checker\", ...)`:\n{}",
+        offending.join("\n")
+    );
+}
+
@@ -3,9 +3,11 @@ extern crate compiletest_rs as compiletest;
 use std::path::PathBuf;
 
 fn run_mode(mode: &'static str) {
-    let mut config = compiletest::Config::default();
+    let mut config = compiletest::Config {
+        src_base: PathBuf::from(format!("tests/{}", mode)),
+        ..Default::default()
+    };
 
-    config.src_base = PathBuf::from(format!("tests/{}", mode));
     config.link_deps(); // Populate config.target_rustcflags with dependencies on the path
     config.clean_rmeta(); // If your tests import the parent crate, this helps with E0464
 
@@ -0,0 +1,45 @@
+//! Ensure that panics in the dynamic checks report the caller's location,
+//! rather than a location within yaiouom.
+
+extern crate yaiouom;
+
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+use yaiouom::si::*;
+use yaiouom::*;
+
+/// Run `f`, which should panic, and return the file and line of the panic.
+fn panic_location<F: FnOnce() + panic::UnwindSafe>(f: F) -> (String, u32) {
+    let location = Arc::new(Mutex::new(None));
+    let hook_location = location.clone();
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let location = info.location().unwrap();
+        *hook_location.lock().unwrap() = Some((location.file().to_string(), location.line()));
+    }));
+    let result = panic::catch_unwind(f);
+    panic::set_hook(previous);
+    assert!(result.is_err());
+    let location = location.lock().unwrap().take().unwrap();
+    location
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn unify_reports_caller() {
+    let (file, line) = panic_location(|| {
+        let _: Measure<f64, Meter> = Second::new(1.).unify();
+    });
+    assert_eq!(file, file!());
+    assert_eq!(line, line!() - 3);
+}
+
+#[test]
+fn checked_unify_reports_caller() {
+    let (file, line) = panic_location(|| {
+        let _: Measure<f64, Meter> = Second::new(1.).checked_unify();
+    });
+    assert_eq!(file, file!());
+    assert_eq!(line, line!() - 3);
+}